@@ -0,0 +1,77 @@
+//! Compares the `Ok` path of a plain `Result` with the same `Result`
+//! passed through `context` and `with_context`. The timings should be
+//! indistinguishable as no context is created and no backtraces are
+//! captured unless there is an error.
+//!
+//! Run with `cargo bench --features backtraces`.
+
+use snafu::{ResultExt, Snafu};
+use std::{
+    ptr,
+    time::{Duration, Instant},
+};
+
+const ITERATIONS: u32 = 10_000_000;
+
+// Prevents the optimizer from discarding the work being measured.
+fn black_box<T: Copy>(value: T) -> T {
+    unsafe { ptr::read_volatile(&value) }
+}
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Wrapped {
+        id: u32,
+        source: InnerError,
+        backtrace: snafu::Backtrace,
+    },
+}
+
+#[inline(never)]
+fn succeed(i: u32) -> Result<u32, InnerError> {
+    if black_box(i) == std::u32::MAX {
+        Boom.fail()
+    } else {
+        Ok(i)
+    }
+}
+
+fn plain(i: u32) -> Result<u32, InnerError> {
+    succeed(i)
+}
+
+fn context(i: u32) -> Result<u32, Error> {
+    succeed(i).context(Wrapped { id: i })
+}
+
+fn with_context(i: u32) -> Result<u32, Error> {
+    succeed(i).with_context(|| Wrapped { id: i })
+}
+
+fn measure<E>(name: &str, f: impl Fn(u32) -> Result<u32, E>) -> Duration {
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let _ = black_box(f(black_box(i)).is_ok());
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:>12}: {:?} total, {:.3} ns/iter",
+        name,
+        elapsed,
+        elapsed.as_nanos() as f64 / f64::from(ITERATIONS),
+    );
+
+    elapsed
+}
+
+fn main() {
+    measure("plain", plain);
+    measure("context", context);
+    measure("with_context", with_context);
+}
@@ -33,3 +33,119 @@ fn backtrace_contains_function_names() {
     assert!(text.contains("check_less_than"));
     assert!(text.contains("example"));
 }
+
+#[test]
+fn backtrace_records_the_thread_and_time() {
+    let before = std::time::SystemTime::now();
+    let e = std::thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| example(0).unwrap_err())
+        .unwrap()
+        .join()
+        .unwrap();
+    let backtrace = ErrorCompat::backtrace(&e).expect("Must have a backtrace");
+
+    assert_eq!(backtrace.thread_name(), Some("worker"));
+    assert!(backtrace.captured_at() >= before);
+    assert!(backtrace.to_string().contains("thread 'worker'"));
+}
+
+#[test]
+fn backtrace_frames_are_available_as_data() {
+    let e = example(0).unwrap_err();
+    let backtrace = ErrorCompat::backtrace(&e).expect("Must have a backtrace");
+
+    let symbols: Vec<_> = backtrace.frames().flat_map(|f| f.symbols).collect();
+    let check = symbols
+        .iter()
+        .find(|s| {
+            s.name
+                .as_ref()
+                .map_or(false, |n| n.contains("check_less_than"))
+        })
+        .expect("Must have a frame for `check_less_than`");
+
+    let filename = check.filename.as_ref().expect("Must have a filename");
+    assert!(filename.ends_with("backtrace.rs"), "{:?}", filename);
+    assert!(check.lineno.is_some());
+}
+
+#[test]
+fn disabled_backtraces_have_no_frames() {
+    use snafu::GenerateBacktrace;
+
+    assert_eq!(Backtrace::disabled().frames().count(), 0);
+}
+
+#[test]
+fn backtrace_display_starts_at_the_code_that_created_the_error() {
+    let e = example(0).unwrap_err();
+    let text = ErrorCompat::backtrace(&e)
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    let first_frame = text.lines().nth(1).unwrap_or_default();
+    assert!(first_frame.contains("check_less_than"), "{}", text);
+    assert!(!text.contains("GenerateBacktrace"), "{}", text);
+}
+
+struct Request;
+
+impl Request {
+    fn fail(&self) -> Error {
+        InvalidUser { user_id: 0 }.build()
+    }
+}
+
+#[test]
+fn backtrace_display_keeps_user_methods_named_like_selector_methods() {
+    let e = Request.fail();
+    let text = ErrorCompat::backtrace(&e)
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    let first_frame = text.lines().nth(1).unwrap_or_default();
+    assert!(first_frame.contains("Request::fail"), "{}", text);
+}
+
+mod context {
+    use snafu::{Backtrace, ErrorCompat, ResultExt, Snafu};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Read {
+            source: std::io::Error,
+            backtrace: Backtrace,
+        },
+    }
+
+    fn read_config() -> Result<(), Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "gone")).context(Read)
+    }
+
+    #[test]
+    fn backtrace_display_starts_at_the_context_call() {
+        let e = read_config().unwrap_err();
+        let text = ErrorCompat::backtrace(&e)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+
+        let first_frame = text.lines().nth(1).unwrap_or_default();
+        assert!(first_frame.contains("read_config"), "{}", text);
+    }
+
+    fn build() -> Result<(), Error> {
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "gone")).context(Read)
+    }
+
+    #[test]
+    fn backtrace_display_keeps_user_functions_named_build() {
+        let e = build().unwrap_err();
+        let text = ErrorCompat::backtrace(&e)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+
+        let first_frame = text.lines().nth(1).unwrap_or_default();
+        assert!(first_frame.contains("context::build"), "{}", text);
+    }
+}
@@ -0,0 +1,19 @@
+use snafu::{Backtrace, ErrorCompat, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    InvalidUser { backtrace: Backtrace },
+}
+
+// The environment variable is only read once per process, so this
+// is the only test in this file.
+#[test]
+fn full_backtraces_include_snafu_frames() {
+    std::env::set_var("SNAFU_BACKTRACE", "full");
+
+    let e = InvalidUser.build();
+    let text = ErrorCompat::backtrace(&e)
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    assert!(text.contains("GenerateBacktrace"), "{}", text);
+}
@@ -7,6 +7,11 @@ mod enum_misuse {
     #[snafu(source(true))]
     #[snafu(backtrace)]
     #[snafu(context)]
+    #[snafu(compare(false))]
+    #[snafu(hash(false))]
+    #[snafu(code("E0001"))]
+    #[snafu(help("help"))]
+    #[snafu(exit_code(2))]
     enum EnumError {
         AVariant,
     }
@@ -22,6 +27,12 @@ mod variant_misuse {
         #[snafu(source)]
         #[snafu(backtrace)]
         #[snafu(crate_root(XXXX))]
+        #[snafu(compare)]
+        #[snafu(hash)]
+        #[snafu(debug)]
+        #[snafu(kind)]
+        #[snafu(send_sync)]
+        #[snafu(display_prefix("prefix"))]
         AVariant,
     }
 }
@@ -38,6 +49,13 @@ mod field_misuse {
             #[snafu(source(from(XXXX, Box::new)))]
             #[snafu(context)]
             #[snafu(crate_root(XXXX))]
+            #[snafu(compare)]
+            #[snafu(hash)]
+            #[snafu(debug)]
+            #[snafu(kind)]
+            #[snafu(code("E0001"))]
+            #[snafu(help("help"))]
+            #[snafu(display_prefix("prefix"))]
             source: String,
         },
     }
@@ -56,7 +74,22 @@ mod struct_misuse {
     #[snafu(source(true))]
     #[snafu(backtrace)]
     #[snafu(context)]
+    #[snafu(compare(false))]
+    #[snafu(hash(false))]
+    #[snafu(kind)]
+    #[snafu(code("E0001"))]
+    #[snafu(help("help"))]
     struct StructError(Box<UsableError>);
 }
 
+mod named_struct_misuse {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(code("E0001"))]
+    #[snafu(help("help"))]
+    #[snafu(exit_code(2))]
+    struct StructError {}
+}
+
 fn main() {}
@@ -12,6 +12,10 @@ enum Error {
         #[snafu(source(5))]
         a: InnerError,
     },
+
+    // Does not fit in an `i32`; should error
+    #[snafu(exit_code(3000000000))]
+    BVariant,
 }
 
 fn main() {}
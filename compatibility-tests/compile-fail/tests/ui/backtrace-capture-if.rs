@@ -0,0 +1,24 @@
+use snafu::{Backtrace, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum OnSourceError {
+    OnSource {
+        #[snafu(backtrace(capture_if = "true"))]
+        source: InnerError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum NotAnExpressionError {
+    NotAnExpression {
+        #[snafu(backtrace(capture_if = "1 +"))]
+        backtrace: Option<Backtrace>,
+    },
+}
+
+fn main() {}
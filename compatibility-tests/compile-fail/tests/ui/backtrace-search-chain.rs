@@ -0,0 +1,27 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(backtrace(search_chain))]
+enum EnumError {
+    Alpha,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(backtrace(search_chain))]
+    WithoutSource,
+
+    #[snafu(whatever, backtrace(search_chain), display("{}", message))]
+    Whatever {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+
+    Field {
+        #[snafu(backtrace(search_chain))]
+        source: std::io::Error,
+    },
+}
+
+fn main() {}
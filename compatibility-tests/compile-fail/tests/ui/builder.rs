@@ -0,0 +1,24 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(builder)]
+enum EnumError {
+    AVariant,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(builder)]
+    Tuple(#[snafu(source(false))] i32, Option<u8>),
+
+    #[snafu(builder, context(false))]
+    NoContext { source: std::io::Error },
+
+    #[snafu(builder, whatever, display("{}", message))]
+    Whatever {
+        source: Option<Box<dyn std::error::Error>>,
+        message: String,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum EnumError {
+    NotClone { source: std::io::Error },
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(default))]
+enum EnumError {
+    Alpha { user_id: u32 },
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(context(false), context(default))]
+    WithoutContext { source: std::io::Error },
+}
+
+fn main() {}
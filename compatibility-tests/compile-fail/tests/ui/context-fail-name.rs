@@ -0,0 +1,23 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(fail_name(raise)))]
+enum EnumError {
+    HasSource {
+        source: std::io::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(context(fail_name(raise)))]
+    WithSource { source: std::io::Error },
+
+    #[snafu(context(fail_name(build)))]
+    Build,
+
+    #[snafu(context(fail_name(raise)), context(fail_name(throw)))]
+    Duplicate,
+}
+
+fn main() {}
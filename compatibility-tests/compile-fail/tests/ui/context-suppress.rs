@@ -0,0 +1,22 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suppress))]
+enum EnumError {
+    Alpha,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(context(suppress))]
+    WithSource { source: std::io::Error },
+
+    #[snafu(context(suppress), builder, context(fail_name(raise)))]
+    WithSelectorAttributes { id: Option<u32> },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suppress))]
+struct StructError;
+
+fn main() {}
@@ -0,0 +1,27 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(convert(clone))]
+enum EnumError {
+    #[snafu(convert(clone))]
+    Variant {
+        #[snafu(convert(to_owned), convert(into))]
+        name: String,
+    },
+
+    WithSource {
+        #[snafu(convert(clone))]
+        source: std::io::Error,
+    },
+
+    WithDefault {
+        #[snafu(default, convert(to_owned))]
+        name: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(convert(into))]
+struct StructError(Box<dyn std::error::Error>);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(default)]
+enum EnumError {
+    #[snafu(default)]
+    AVariant {
+        #[snafu(default)]
+        source: std::io::Error,
+        #[snafu(default, default = 1)]
+        retries: u32,
+    },
+}
+
+fn main() {}
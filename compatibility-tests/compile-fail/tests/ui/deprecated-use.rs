@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(deprecated = "use `Beta` instead")]
+    Alpha { id: u32 },
+
+    Beta { id: u32 },
+}
+
+fn main() {
+    let _ = Alpha { id: 1_u32 }.build();
+    let _ = Beta { id: 1_u32 }.build();
+}
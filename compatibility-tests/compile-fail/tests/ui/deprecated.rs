@@ -0,0 +1,20 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(deprecated)]
+enum EnumError {
+    Alpha,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(context(false), deprecated)]
+    WithoutContext { source: std::io::Error },
+
+    Field {
+        #[snafu(deprecated)]
+        id: u32,
+    },
+}
+
+fn main() {}
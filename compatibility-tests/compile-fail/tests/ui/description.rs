@@ -0,0 +1,17 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(description = "enum")]
+enum EnumError {
+    #[snafu(description = "one", description = "two")]
+    Alpha {
+        #[snafu(description = "field")]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(description = "tuple")]
+struct TupleError(Box<dyn std::error::Error>);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use snafu::Snafu;
+
+const MESSAGE: &str = "User {user_id} was not found";
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display(fmt = MESSAGE, user_id = user_id))]
+    UserNotFound { user_id: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(display_with_name)]
+    Alpha,
+
+    Field {
+        #[snafu(display_with_name)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_with_name)]
+struct StructError;
+
+fn main() {}
@@ -0,0 +1,15 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum NotAString {
+    #[snafu(display(with = display_error))]
+    Alpha,
+}
+
+#[derive(Debug, Snafu)]
+enum NotAPath {
+    #[snafu(display(with = "not a path"))]
+    Alpha,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    /// Could not read {flename}
+    ReadConfig { filename: String },
+
+    /// Escaped {{braces}} and {filename:?} are fine
+    Allowed { filename: String },
+}
+
+#[derive(Debug, Snafu)]
+/// Missing {user_di}
+struct UserError {
+    user_id: i32,
+}
+
+fn main() {}
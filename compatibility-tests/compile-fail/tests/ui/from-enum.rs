@@ -0,0 +1,37 @@
+use snafu::Snafu;
+
+mod missing_counterpart {
+    use snafu::Snafu;
+
+    mod low {
+        use snafu::Snafu;
+
+        #[derive(Debug, Snafu)]
+        pub enum Error {
+            Shared,
+            LowLevelOnly,
+        }
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(from_enum(low::Error))]
+    enum Error {
+        Shared,
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(from_enum(false))]
+enum EnumError {
+    #[snafu(from_enum(EnumError))]
+    AVariant {
+        #[snafu(from_enum(false))]
+        name: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(from_enum(true))]
+enum UnparseableError {}
+
+fn main() {}
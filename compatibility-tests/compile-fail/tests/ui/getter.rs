@@ -0,0 +1,34 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(getter)]
+enum EnumError {
+    #[snafu(getter)]
+    First {
+        #[snafu(getter)]
+        id: i32,
+    },
+
+    Positional(#[snafu(source(false), getter)] i32),
+}
+
+#[derive(Debug, Snafu)]
+enum MismatchedError {
+    First {
+        #[snafu(getter)]
+        id: i32,
+    },
+
+    Second {
+        #[snafu(getter)]
+        id: u64,
+    },
+}
+
+#[derive(Debug, Snafu)]
+struct StructError {
+    #[snafu(getter)]
+    id: i32,
+}
+
+fn main() {}
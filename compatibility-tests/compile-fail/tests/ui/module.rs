@@ -0,0 +1,24 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum VariantError {
+    #[snafu(module)]
+    Alpha,
+
+    Field {
+        #[snafu(module)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+struct StructError;
+
+#[derive(Debug, Snafu)]
+#[snafu(module, module(other))]
+enum DuplicateError {
+    Beta,
+}
+
+fn main() {}
@@ -13,4 +13,13 @@ enum Error {
     HasUserFields { source: BoxError, a: i32, b: i32 },
 }
 
+#[derive(Debug, Snafu)]
+enum DuplicateSourceTypes {
+    #[snafu(context(false))]
+    First { source: BoxError },
+
+    #[snafu(context(false))]
+    Second { source: BoxError },
+}
+
 fn main() {}
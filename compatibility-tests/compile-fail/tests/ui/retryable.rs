@@ -0,0 +1,23 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(retryable)]
+enum EnumError {
+    #[snafu(retryable, retryable(false))]
+    Alpha {
+        #[snafu(retryable)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(retryable)]
+struct StructError {
+    id: u32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(retryable)]
+struct TupleError(Box<dyn std::error::Error>);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use snafu::Snafu;
+use std::{cell::Cell, rc::Rc};
+
+#[derive(Debug, Snafu)]
+#[snafu(send_sync)]
+enum Error {
+    NotSend { shared: Rc<i32> },
+
+    NotSync { counter: Cell<u8> },
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize, serialize)]
+enum EnumError {
+    #[snafu(serialize)]
+    Alpha {
+        #[snafu(serialize)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+struct StructError;
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+struct OpaqueError(EnumError);
+
+fn main() {}
@@ -0,0 +1,22 @@
+use snafu::Snafu;
+use std::sync::Arc;
+
+#[derive(Debug, Snafu)]
+#[snafu(source(arc))]
+enum EnumError {
+    NotArc {
+        #[snafu(source(arc))]
+        source: Box<std::io::Error>,
+    },
+
+    OptedOut {
+        #[snafu(source(false), source(arc))]
+        source: Arc<std::io::Error>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(source(arc))]
+struct TupleError(Box<EnumError>);
+
+fn main() {}
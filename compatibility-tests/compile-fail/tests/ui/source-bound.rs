@@ -0,0 +1,20 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(source(bound = "T: Send"))]
+enum EnumError<T> {
+    NotASource {
+        #[snafu(source(bound = "T: Send"))]
+        value: T,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum InvalidBoundError {
+    InvalidBound {
+        #[snafu(source(bound = "not a bound"))]
+        source: std::io::Error,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use snafu::Snafu;
+
+#[derive(Debug)]
+struct LegacyError;
+
+impl std::fmt::Display for LegacyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "legacy")
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(source(display_only))]
+enum EnumError {
+    #[snafu(transparent)]
+    Transparent {
+        #[snafu(source(display_only))]
+        source: LegacyError,
+    },
+
+    Delegated {
+        #[snafu(source(display_only), backtrace)]
+        source: LegacyError,
+    },
+
+    OptedOut {
+        #[snafu(source(false), source(display_only))]
+        source: LegacyError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(source(display_only))]
+struct TupleError(Box<EnumError>);
+
+fn main() {}
@@ -0,0 +1,24 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum InnerError {}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(transparent)]
+    HasUserFields { source: InnerError, id: i32 },
+
+    #[snafu(transparent)]
+    MissingSource {},
+
+    #[snafu(transparent, display("Boom"))]
+    HasDisplay { source: InnerError },
+
+    #[snafu(transparent, whatever)]
+    IsWhatever {
+        source: Option<Box<dyn std::error::Error>>,
+        message: String,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(variant_name, variant_name)]
+enum EnumError {
+    #[snafu(variant_name)]
+    Alpha {
+        #[snafu(variant_name)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(variant_name)]
+struct StructError;
+
+fn main() {}
@@ -0,0 +1,17 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(variants_const, variants_const)]
+enum EnumError {
+    #[snafu(variants_const)]
+    Alpha {
+        #[snafu(variants_const)]
+        id: u32,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(variants_const)]
+struct StructError;
+
+fn main() {}
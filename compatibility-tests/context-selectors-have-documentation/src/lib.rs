@@ -10,4 +10,8 @@ use snafu::Snafu;
 pub enum Error {
     /// Variant docs
     Variant,
+
+    /// Another variant's docs
+    #[snafu(doc("Custom context selector docs"))]
+    CustomDocs,
 }
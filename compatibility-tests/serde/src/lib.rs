@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use serde_json::json;
+use snafu::{Backtrace, Location, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+enum Error {
+    #[snafu(display("User {} is not allowed", user_id))]
+    InvalidUser { user_id: i32, reason: String },
+
+    #[snafu(display("Could not read {}", path))]
+    Read {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Nothing happened"))]
+    Nothing,
+}
+
+fn read(path: &str) -> Result<Vec<u8>, Error> {
+    std::fs::read(path).context(Read { path })
+}
+
+#[test]
+fn variants_serialize_their_name_message_and_fields() {
+    let error = InvalidUser {
+        user_id: 42,
+        reason: "banned",
+    }
+    .build();
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "variant": "InvalidUser",
+            "message": "User 42 is not allowed",
+            "source": null,
+            "fields": {
+                "user_id": 42,
+                "reason": "banned",
+            },
+        }),
+    );
+}
+
+#[test]
+fn sources_serialize_their_message_and_backtraces_are_skipped() {
+    let error = read("/this/does/not/exist").unwrap_err();
+    let source = std::fs::read("/this/does/not/exist").unwrap_err();
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "variant": "Read",
+            "message": "Could not read /this/does/not/exist",
+            "source": source.to_string(),
+            "fields": {
+                "path": "/this/does/not/exist",
+            },
+        }),
+    );
+}
+
+#[test]
+fn variants_without_fields_serialize_empty_fields() {
+    let error = Nothing.build();
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "variant": "Nothing",
+            "message": "Nothing happened",
+            "source": null,
+            "fields": {},
+        }),
+    );
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize, display("Request {} failed", r#type))]
+struct RequestError {
+    r#type: u8,
+    #[snafu(location)]
+    location: Location,
+}
+
+#[test]
+fn structs_serialize_with_their_own_name() {
+    let error = RequestContext { r#type: 3 }.build();
+    let location = error.location;
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "variant": "RequestError",
+            "message": "Request 3 failed",
+            "source": null,
+            "fields": {
+                "type": 3,
+                "location": {
+                    "file": location.file,
+                    "line": location.line,
+                    "column": location.column,
+                },
+            },
+        }),
+    );
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+struct OpaqueError(Error);
+
+#[test]
+fn opaque_errors_serialize_as_the_inner_error() {
+    let error = OpaqueError::from(Nothing.build());
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(value, serde_json::to_value(&error.0).unwrap());
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(serialize)]
+enum GenericError<T>
+where
+    T: std::fmt::Display,
+{
+    #[snafu(display("Got {}", value))]
+    Unexpected { value: T },
+}
+
+#[test]
+fn generic_fields_serialize() {
+    let error: GenericError<f64> = Unexpected { value: 1.5 }.build();
+
+    let value = serde_json::to_value(&error).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "variant": "Unexpected",
+            "message": "Got 1.5",
+            "source": null,
+            "fields": {
+                "value": 1.5,
+            },
+        }),
+    );
+}
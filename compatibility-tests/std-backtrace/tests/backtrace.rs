@@ -0,0 +1,54 @@
+use snafu::{ensure, Backtrace, ErrorCompat, GenerateBacktrace, Snafu};
+use std::backtrace::BacktraceStatus;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    InvalidUser { user_id: i32, backtrace: Backtrace },
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+fn check_less_than(user_id: i32) -> Result<()> {
+    ensure!(user_id >= 42, InvalidUser { user_id });
+    Ok(())
+}
+
+fn example(user_id: i32) -> Result<()> {
+    check_less_than(user_id)?;
+    Ok(())
+}
+
+// The standard library reads the environment variables once per
+// process, so this checks whichever outcome the test run was set up for.
+#[test]
+fn backtrace_follows_the_environment() {
+    let e = example(0).unwrap_err();
+    let enabled = std::env::var_os("RUST_LIB_BACKTRACE")
+        .or_else(|| std::env::var_os("RUST_BACKTRACE"))
+        .map_or(false, |v| v != "0");
+
+    match ErrorCompat::backtrace(&e) {
+        Some(backtrace) => {
+            assert!(enabled);
+            assert_eq!(backtrace.status(), BacktraceStatus::Captured);
+            assert!(backtrace.to_string().contains("check_less_than"));
+        }
+        None => assert!(!enabled),
+    }
+}
+
+#[test]
+fn backtrace_wraps_the_std_type() {
+    fn expects_std_type(_: &std::backtrace::Backtrace) {}
+
+    let backtrace = Backtrace::from(std::backtrace::Backtrace::force_capture());
+    expects_std_type(backtrace.as_std());
+    assert!(backtrace.as_backtrace().is_some());
+}
+
+#[test]
+fn disabled_backtraces_are_not_returned() {
+    let backtrace = Backtrace::disabled();
+    assert_eq!(backtrace.status(), BacktraceStatus::Disabled);
+    assert!(backtrace.as_backtrace().is_none());
+}
@@ -1,11 +1,12 @@
 #![recursion_limit = "128"] // https://github.com/rust-lang/rust/issues/62059
+#![allow(clippy::large_enum_variant)]
 
 extern crate proc_macro;
 
 use crate::parse::attributes_from_syn;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt;
 
 mod parse;
@@ -13,7 +14,6 @@ mod shared;
 
 /// See the crate-level documentation for SNAFU which contains tested
 /// examples of this macro.
-
 #[proc_macro_derive(Snafu, attributes(snafu))]
 pub fn snafu_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).expect("Could not parse type to derive Error for");
@@ -38,17 +38,96 @@ struct EnumInfo {
     generics: syn::Generics,
     variants: Vec<FieldContainer>,
     default_visibility: UserInput,
+    default_suffix: Option<syn::Ident>,
+    serialize: bool,
+    compare: bool,
+    hash: bool,
+    kind: bool,
+    send_sync: Option<Vec<syn::Type>>,
+    clone: bool,
+    debug: bool,
+    display_prefix: Option<syn::LitStr>,
+    display_with_name: bool,
+    variant_name: bool,
+    variants_const: bool,
+    from_enum: Option<syn::Path>,
+    module: Option<syn::Ident>,
+    visibility: syn::Visibility,
 }
 
 struct FieldContainer {
     name: syn::Ident,
     backtrace_field: Option<Field>,
+    backtrace_capture_if: Option<syn::Expr>,
+    backtrace_search_chain: bool,
+    location_field: Option<Field>,
+    default_fields: Vec<DefaultField>,
     selector_kind: ContextSelectorKind,
-    display_format: Option<UserInput>,
+    selector_name: Option<SelectorName>,
+    fail_name: Option<syn::Ident>,
+    builder: bool,
+    selector_default: bool,
+    suppress_selector: bool,
+    deprecated: Option<Option<syn::LitStr>>,
+    selector_doc: Option<String>,
+    code: Option<syn::LitStr>,
+    description: Option<syn::LitStr>,
+    help: Option<syn::LitStr>,
+    retryable: Option<bool>,
+    exit_code: Option<i32>,
+    is_transparent: bool,
+    skip_from_enum: bool,
+    uncompared_fields: Vec<syn::Member>,
+    unhashed_fields: Vec<syn::Member>,
+    /// Every field, in the order it was declared.
+    members: Vec<syn::Member>,
+    getters: Vec<(Field, proc_macro2::TokenStream)>,
+    display_format: Option<DisplayFormat>,
     doc_comment: String,
     visibility: Option<UserInput>,
 }
 
+impl FieldContainer {
+    /// The name of the context selector, derived from `base` unless
+    /// the user has provided an exact name.
+    fn selector_name(&self, base: &str, default_suffix: &str) -> syn::Ident {
+        let span = self.name.span();
+        match &self.selector_name {
+            Some(SelectorName::Exact(name)) => name.clone(),
+            Some(SelectorName::Suffix(suffix)) => format_ident!("{}{}", base, suffix, span = span),
+            None => format_ident!("{}{}", base, default_suffix, span = span),
+        }
+    }
+
+    /// The name of the method that returns the error wrapped in
+    /// `Err`, unless the user has renamed it.
+    fn fail_name(&self) -> syn::Ident {
+        self.fail_name
+            .clone()
+            .unwrap_or_else(|| format_ident!("fail", span = self.name.span()))
+    }
+}
+
+/// How the `Display` implementation of a variant is written.
+pub(crate) enum DisplayFormat {
+    /// `#[snafu(display("format", args...))]`; passed to `write!`.
+    Arguments(UserInput),
+    /// `#[snafu(display(with = "path"))]`; called with the fields and
+    /// the formatter.
+    With(syn::Path),
+    /// `#[snafu(display(fmt = EXPR, args...))]`; the format string is
+    /// only known at runtime.
+    Runtime {
+        format: syn::Expr,
+        arguments: Vec<syn::Expr>,
+    },
+}
+
+enum SelectorName {
+    Suffix(syn::Ident),
+    Exact(syn::Ident),
+}
+
 enum ContextSelectorKind {
     Context {
         source_field: Option<SourceField>,
@@ -58,13 +137,26 @@ enum ContextSelectorKind {
     NoContext {
         source_field: SourceField,
     },
+
+    Whatever {
+        source_field: Option<SourceField>,
+        message_field: Field,
+    },
 }
 
 impl ContextSelectorKind {
+    fn is_whatever(&self) -> bool {
+        match self {
+            ContextSelectorKind::Whatever { .. } => true,
+            _ => false,
+        }
+    }
+
     fn user_fields(&self) -> &[Field] {
         match self {
             ContextSelectorKind::Context { user_fields, .. } => user_fields,
             ContextSelectorKind::NoContext { .. } => &[],
+            ContextSelectorKind::Whatever { .. } => &[],
         }
     }
 
@@ -72,6 +164,15 @@ impl ContextSelectorKind {
         match self {
             ContextSelectorKind::Context { source_field, .. } => source_field.as_ref(),
             ContextSelectorKind::NoContext { source_field } => Some(source_field),
+            ContextSelectorKind::Whatever { source_field, .. } => source_field.as_ref(),
+        }
+    }
+
+    fn message_field(&self) -> Option<&Field> {
+        match self {
+            ContextSelectorKind::Context { .. } => None,
+            ContextSelectorKind::NoContext { .. } => None,
+            ContextSelectorKind::Whatever { message_field, .. } => Some(message_field),
         }
     }
 }
@@ -80,6 +181,12 @@ struct NamedStructInfo {
     crate_root: UserInput,
     field_container: FieldContainer,
     generics: syn::Generics,
+    serialize: bool,
+    compare: bool,
+    hash: bool,
+    send_sync: Option<Vec<syn::Type>>,
+    clone: bool,
+    debug: bool,
 }
 
 struct TupleStructInfo {
@@ -87,31 +194,90 @@ struct TupleStructInfo {
     name: syn::Ident,
     generics: syn::Generics,
     transformation: Transformation,
+    serialize: bool,
+    source_bounds: Vec<syn::WherePredicate>,
+    compare: bool,
+    hash: bool,
+    send_sync: Option<Vec<syn::Type>>,
+    clone: bool,
+    debug: bool,
 }
 
 #[derive(Clone)]
 pub(crate) struct Field {
     name: syn::Ident,
+    member: syn::Member,
     ty: syn::Type,
     original: syn::Field,
+    visibility: Option<proc_macro2::TokenStream>,
+    conversion: Conversion,
+}
+
+/// How a context selector's field is converted into the error's field.
+#[derive(Copy, Clone)]
+pub(crate) enum Conversion {
+    Into,
+    ToOwned,
+    Clone,
 }
 
 impl Field {
     fn name(&self) -> &syn::Ident {
         &self.name
     }
+
+    fn member(&self) -> &syn::Member {
+        &self.member
+    }
+
+    fn visibility(&self) -> Option<&proc_macro2::TokenStream> {
+        self.visibility.as_ref()
+    }
+
+    fn is_positional(&self) -> bool {
+        match self.member {
+            syn::Member::Named(_) => false,
+            syn::Member::Unnamed(_) => true,
+        }
+    }
+}
+
+/// A field that is left out of the context selector and filled in
+/// with `value`, or `Default::default()`, when the error is built.
+pub(crate) struct DefaultField {
+    field: Field,
+    value: Option<syn::Expr>,
 }
 
 struct SourceField {
     name: syn::Ident,
+    member: syn::Member,
     transformation: Transformation,
     backtrace_delegate: bool,
+    is_arc: bool,
+    is_display_only: bool,
+    bounds: Vec<syn::WherePredicate>,
 }
 
 impl SourceField {
     fn name(&self) -> &syn::Ident {
         &self.name
     }
+
+    fn member(&self) -> &syn::Member {
+        &self.member
+    }
+
+    /// The bound source, looking through the `Arc` created by
+    /// `source(arc)` so that the original error is exposed.
+    fn deref_arc(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        if self.is_arc {
+            quote! { (**#name) }
+        } else {
+            quote! { #name }
+        }
+    }
 }
 
 enum Transformation {
@@ -380,7 +546,7 @@ where
     ///
     /// This can help with additional manual error checks beyond the duplication checks that
     /// `AtMostOne` handles for you.
-    fn iter(&self) -> std::collections::vec_deque::Iter<(T, U)> {
+    fn iter(&self) -> std::collections::vec_deque::Iter<'_, (T, U)> {
         self.values.iter()
     }
 
@@ -422,6 +588,7 @@ fn parse_snafu_information(ty: syn::DeriveInput) -> MultiSynResult<SnafuInfo> {
     let span = ty.span();
     let syn::DeriveInput {
         ident,
+        vis,
         generics,
         data,
         attrs,
@@ -429,7 +596,9 @@ fn parse_snafu_information(ty: syn::DeriveInput) -> MultiSynResult<SnafuInfo> {
     } = ty;
 
     match data {
-        Data::Enum(enum_) => parse_snafu_enum(enum_, ident, generics, attrs).map(SnafuInfo::Enum),
+        Data::Enum(enum_) => {
+            parse_snafu_enum(enum_, ident, vis, generics, attrs).map(SnafuInfo::Enum)
+        }
         Data::Struct(struct_) => parse_snafu_struct(struct_, ident, generics, attrs, span),
         _ => Err(vec![syn::Error::new(
             span,
@@ -443,6 +612,31 @@ const ATTR_DISPLAY: OnlyValidOn = OnlyValidOn {
     valid_on: "enum variants or structs with named fields",
 };
 
+const ATTR_DISPLAY_PREFIX: OnlyValidOn = OnlyValidOn {
+    attribute: "display_prefix",
+    valid_on: "an enum",
+};
+
+const ATTR_DISPLAY_WITH_NAME: OnlyValidOn = OnlyValidOn {
+    attribute: "display_with_name",
+    valid_on: "an enum",
+};
+
+const ATTR_VARIANT_NAME: OnlyValidOn = OnlyValidOn {
+    attribute: "variant_name",
+    valid_on: "an enum",
+};
+
+const ATTR_VARIANTS_CONST: OnlyValidOn = OnlyValidOn {
+    attribute: "variants_const",
+    valid_on: "an enum",
+};
+
+const ATTR_DOC: OnlyValidOn = OnlyValidOn {
+    attribute: "doc",
+    valid_on: "enum variants or structs with named fields",
+};
+
 const ATTR_SOURCE: OnlyValidOn = OnlyValidOn {
     attribute: "source",
     valid_on: "enum variant or struct fields with a name",
@@ -458,24 +652,84 @@ const ATTR_SOURCE_FALSE: WrongField = WrongField {
     valid_field: "source",
 };
 
+const ATTR_SOURCE_FALSE_POSITIONAL: OnlyValidOn = OnlyValidOn {
+    attribute: "source(false)",
+    valid_on: "the first field of a tuple variant",
+};
+
 const ATTR_SOURCE_FROM: OnlyValidOn = OnlyValidOn {
     attribute: "source(from)",
     valid_on: "enum variant or struct fields with a name",
 };
 
+const ATTR_SOURCE_BOUND: OnlyValidOn = OnlyValidOn {
+    attribute: "source(bound)",
+    valid_on: "enum variant or struct fields, or a tuple struct",
+};
+
+const ATTR_SOURCE_DISPLAY_ONLY: OnlyValidOn = OnlyValidOn {
+    attribute: "source(display_only)",
+    valid_on: "enum variant or struct fields",
+};
+
+const ATTR_SOURCE_ARC: OnlyValidOn = OnlyValidOn {
+    attribute: "source(arc)",
+    valid_on: "enum variant or struct fields with a name",
+};
+
+const ATTR_SOURCE_ARC_TYPE: OnlyValidOn = OnlyValidOn {
+    attribute: "source(arc)",
+    valid_on: "fields of type `Arc`",
+};
+
 const ATTR_BACKTRACE: OnlyValidOn = OnlyValidOn {
     attribute: "backtrace",
     valid_on: "enum variant or struct fields with a name",
 };
 
+const ATTR_BACKTRACE_SEARCH_CHAIN: OnlyValidOn = OnlyValidOn {
+    attribute: "backtrace(search_chain)",
+    valid_on: "enum variants or structs with named fields",
+};
+
 const ATTR_BACKTRACE_FALSE: WrongField = WrongField {
     attribute: "backtrace(false)",
     valid_field: "backtrace",
 };
 
+const ATTR_LOCATION: OnlyValidOn = OnlyValidOn {
+    attribute: "location",
+    valid_on: "enum variant or struct fields with a name",
+};
+
+const ATTR_LOCATION_FALSE: OnlyValidOn = OnlyValidOn {
+    attribute: "location(false)",
+    valid_on: "fields of type `snafu::Location`",
+};
+
 const ATTR_VISIBILITY: OnlyValidOn = OnlyValidOn {
     attribute: "visibility",
-    valid_on: "an enum, enum variants, or a struct with named fields",
+    valid_on: "an enum, enum variants, context fields, or a struct with named fields",
+};
+
+const ATTR_COMPARE: OnlyValidOn = OnlyValidOn {
+    attribute: "compare",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_COMPARE_FALSE: OnlyValidOn = OnlyValidOn {
+    attribute: "compare(false)",
+    valid_on: "enum variant or struct fields with a name",
+};
+
+const ATTR_HASH: OnlyValidOn = OnlyValidOn {
+    attribute: "hash",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_HASH_FALSE: OnlyValidOn = OnlyValidOn {
+    attribute: "hash(false)",
+    valid_on: "enum variant or struct fields with a name",
 };
 
 const ATTR_CONTEXT: OnlyValidOn = OnlyValidOn {
@@ -483,27 +737,185 @@ const ATTR_CONTEXT: OnlyValidOn = OnlyValidOn {
     valid_on: "enum variants or structs with named fields",
 };
 
+const ATTR_CONTEXT_FLAG: OnlyValidOn = OnlyValidOn {
+    attribute: "context(bool)",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_CONTEXT_NAME: OnlyValidOn = OnlyValidOn {
+    attribute: "context(name)",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_CONTEXT_FAIL_NAME: OnlyValidOn = OnlyValidOn {
+    attribute: "context(fail_name)",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_CONTEXT_DEFAULT: OnlyValidOn = OnlyValidOn {
+    attribute: "context(default)",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_CONTEXT_SUPPRESS: OnlyValidOn = OnlyValidOn {
+    attribute: "context(suppress)",
+    valid_on: "enum variants",
+};
+
+const ATTR_CONVERT: OnlyValidOn = OnlyValidOn {
+    attribute: "convert",
+    valid_on: "enum variant or struct fields",
+};
+
 const ATTR_CRATE_ROOT: OnlyValidOn = OnlyValidOn {
     attribute: "crate_root",
     valid_on: "an enum or a struct",
 };
 
+const ATTR_BUILDER: OnlyValidOn = OnlyValidOn {
+    attribute: "builder",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_DEFAULT: OnlyValidOn = OnlyValidOn {
+    attribute: "default",
+    valid_on: "enum variant or struct fields",
+};
+
+const ATTR_CLONE: OnlyValidOn = OnlyValidOn {
+    attribute: "clone",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_DEBUG: OnlyValidOn = OnlyValidOn {
+    attribute: "debug",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_CODE: OnlyValidOn = OnlyValidOn {
+    attribute: "code",
+    valid_on: "enum variants",
+};
+
+const ATTR_DESCRIPTION: OnlyValidOn = OnlyValidOn {
+    attribute: "description",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_DEPRECATED: OnlyValidOn = OnlyValidOn {
+    attribute: "deprecated",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_RETRYABLE: OnlyValidOn = OnlyValidOn {
+    attribute: "retryable",
+    valid_on: "enum variants",
+};
+
+const ATTR_HELP: OnlyValidOn = OnlyValidOn {
+    attribute: "help",
+    valid_on: "enum variants",
+};
+
+const ATTR_EXIT_CODE: OnlyValidOn = OnlyValidOn {
+    attribute: "exit_code",
+    valid_on: "enum variants",
+};
+
+const ATTR_FROM_ENUM: OnlyValidOn = OnlyValidOn {
+    attribute: "from_enum",
+    valid_on: "an enum",
+};
+
+const ATTR_FROM_ENUM_FALSE: OnlyValidOn = OnlyValidOn {
+    attribute: "from_enum(false)",
+    valid_on: "enum variants",
+};
+
+const ATTR_GETTER: OnlyValidOn = OnlyValidOn {
+    attribute: "getter",
+    valid_on: "enum variant fields with a name",
+};
+
+const ATTR_KIND: OnlyValidOn = OnlyValidOn {
+    attribute: "kind",
+    valid_on: "an enum",
+};
+
+const ATTR_MODULE: OnlyValidOn = OnlyValidOn {
+    attribute: "module",
+    valid_on: "an enum",
+};
+
+const ATTR_SEND_SYNC: OnlyValidOn = OnlyValidOn {
+    attribute: "send_sync",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_SERIALIZE: OnlyValidOn = OnlyValidOn {
+    attribute: "serialize",
+    valid_on: "an enum or a struct",
+};
+
+const ATTR_TRANSPARENT: OnlyValidOn = OnlyValidOn {
+    attribute: "transparent",
+    valid_on: "enum variants or structs with named fields",
+};
+
+const ATTR_WHATEVER: OnlyValidOn = OnlyValidOn {
+    attribute: "whatever",
+    valid_on: "enum variants or structs with named fields",
+};
+
 const SOURCE_BOOL_FROM_INCOMPATIBLE: IncompatibleAttributes =
     IncompatibleAttributes(&["source(false)", "source(from)"]);
 
+const SOURCE_BOOL_ARC_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["source(false)", "source(arc)"]);
+
+const SOURCE_BOOL_DISPLAY_ONLY_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["source(false)", "source(display_only)"]);
+
+const WHATEVER_CONTEXT_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["whatever", "context"]);
+
+const TRANSPARENT_DISPLAY_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["transparent", "display"]);
+
+const TRANSPARENT_WHATEVER_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["transparent", "whatever"]);
+
+const TRANSPARENT_SOURCE_DISPLAY_ONLY_INCOMPATIBLE: IncompatibleAttributes =
+    IncompatibleAttributes(&["transparent", "source(display_only)"]);
+
 fn parse_snafu_enum(
     enum_: syn::DataEnum,
     name: syn::Ident,
+    visibility: syn::Visibility,
     generics: syn::Generics,
     attrs: Vec<syn::Attribute>,
 ) -> MultiSynResult<EnumInfo> {
-    use syn::spanned::Spanned;
+    use quote::ToTokens;
     use syn::Fields;
 
     let mut errors = SyntaxErrors::default();
 
     let mut default_visibilities = AtMostOne::new("visibility", ErrorLocation::OnEnum);
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnEnum);
+    let mut default_suffixes = AtMostOne::new("context(suffix)", ErrorLocation::OnEnum);
+    let mut compares = AtMostOne::new("compare", ErrorLocation::OnEnum);
+    let mut hashes = AtMostOne::new("hash", ErrorLocation::OnEnum);
+    let mut kinds = AtMostOne::new("kind", ErrorLocation::OnEnum);
+    let mut send_syncs = AtMostOne::new("send_sync", ErrorLocation::OnEnum);
+    let mut display_prefixes = AtMostOne::new("display_prefix", ErrorLocation::OnEnum);
+    let mut display_with_names = AtMostOne::new("display_with_name", ErrorLocation::OnEnum);
+    let mut variant_names = AtMostOne::new("variant_name", ErrorLocation::OnEnum);
+    let mut variants_consts = AtMostOne::new("variants_const", ErrorLocation::OnEnum);
+    let mut clones = AtMostOne::new("clone", ErrorLocation::OnEnum);
+    let mut debugs = AtMostOne::new("debug", ErrorLocation::OnEnum);
+    let mut serializes = AtMostOne::new("serialize", ErrorLocation::OnEnum);
+    let mut from_enums = AtMostOne::new("from_enum", ErrorLocation::OnEnum);
+    let mut modules = AtMostOne::new("module", ErrorLocation::OnEnum);
     let mut enum_errors = errors.scoped(ErrorLocation::OnEnum);
 
     for attr in attributes_from_syn(attrs)? {
@@ -512,11 +924,21 @@ fn parse_snafu_enum(
                 default_visibilities.add(v, tokens);
             }
             SnafuAttribute::Display(tokens, ..) => enum_errors.add(tokens, ATTR_DISPLAY),
+            SnafuAttribute::DisplayPrefix(tokens, p) => display_prefixes.add(p, tokens),
+            SnafuAttribute::DisplayWithName(tokens) => display_with_names.add((), tokens),
+            SnafuAttribute::VariantName(tokens) => variant_names.add((), tokens),
+            SnafuAttribute::VariantsConst(tokens) => variants_consts.add((), tokens),
+            SnafuAttribute::Doc(tokens, ..) => enum_errors.add(tokens, ATTR_DOC),
             SnafuAttribute::Source(tokens, ss) => {
                 for s in ss {
                     match s {
                         Source::Flag(..) => enum_errors.add(tokens.clone(), ATTR_SOURCE_BOOL),
                         Source::From(..) => enum_errors.add(tokens.clone(), ATTR_SOURCE_FROM),
+                        Source::Arc => enum_errors.add(tokens.clone(), ATTR_SOURCE_ARC),
+                        Source::DisplayOnly => {
+                            enum_errors.add(tokens.clone(), ATTR_SOURCE_DISPLAY_ONLY)
+                        }
+                        Source::Bound(..) => enum_errors.add(tokens.clone(), ATTR_SOURCE_BOUND),
                     }
                 }
             }
@@ -524,31 +946,132 @@ fn parse_snafu_enum(
                 crate_roots.add(root, tokens);
             }
             SnafuAttribute::Backtrace(tokens, ..) => enum_errors.add(tokens, ATTR_BACKTRACE),
-            SnafuAttribute::Context(tokens, ..) => enum_errors.add(tokens, ATTR_CONTEXT),
+            SnafuAttribute::BacktraceSearchChain(tokens) => {
+                enum_errors.add(tokens, ATTR_BACKTRACE_SEARCH_CHAIN)
+            }
+            SnafuAttribute::Compare(tokens, c) => {
+                if c {
+                    compares.add((), tokens);
+                } else {
+                    enum_errors.add(tokens, ATTR_COMPARE_FALSE);
+                }
+            }
+            SnafuAttribute::Hash(tokens, h) => {
+                if h {
+                    hashes.add((), tokens);
+                } else {
+                    enum_errors.add(tokens, ATTR_HASH_FALSE);
+                }
+            }
+            SnafuAttribute::Location(tokens, ..) => enum_errors.add(tokens, ATTR_LOCATION),
+            SnafuAttribute::Convert(tokens, ..) => enum_errors.add(tokens, ATTR_CONVERT),
+            SnafuAttribute::Context(tokens, c) => match c {
+                Context::Suffix(suffix) => default_suffixes.add(suffix, tokens),
+                Context::Flag(..) => enum_errors.add(tokens, ATTR_CONTEXT_FLAG),
+                Context::Name(..) => enum_errors.add(tokens, ATTR_CONTEXT_NAME),
+                Context::FailName(..) => enum_errors.add(tokens, ATTR_CONTEXT_FAIL_NAME),
+                Context::Default => enum_errors.add(tokens, ATTR_CONTEXT_DEFAULT),
+                Context::Suppress => enum_errors.add(tokens, ATTR_CONTEXT_SUPPRESS),
+            },
+            SnafuAttribute::Serialize(tokens) => serializes.add((), tokens),
+            SnafuAttribute::Builder(tokens) => enum_errors.add(tokens, ATTR_BUILDER),
+            SnafuAttribute::Default(tokens, ..) => enum_errors.add(tokens, ATTR_DEFAULT),
+            SnafuAttribute::Code(tokens, ..) => enum_errors.add(tokens, ATTR_CODE),
+            SnafuAttribute::Description(tokens, ..) => enum_errors.add(tokens, ATTR_DESCRIPTION),
+            SnafuAttribute::Help(tokens, ..) => enum_errors.add(tokens, ATTR_HELP),
+            SnafuAttribute::Retryable(tokens, ..) => enum_errors.add(tokens, ATTR_RETRYABLE),
+            SnafuAttribute::Deprecated(tokens, ..) => enum_errors.add(tokens, ATTR_DEPRECATED),
+            SnafuAttribute::ExitCode(tokens, ..) => enum_errors.add(tokens, ATTR_EXIT_CODE),
+            SnafuAttribute::FromEnum(tokens, f) => match f {
+                Some(path) => from_enums.add(path, tokens),
+                None => enum_errors.add(tokens, ATTR_FROM_ENUM_FALSE),
+            },
+            SnafuAttribute::Getter(tokens) => enum_errors.add(tokens, ATTR_GETTER),
+            SnafuAttribute::Kind(tokens) => kinds.add((), tokens),
+            SnafuAttribute::Module(tokens, m) => modules.add(m, tokens),
+            SnafuAttribute::SendSync(tokens) => send_syncs.add((), tokens),
+            SnafuAttribute::Clone(tokens) => clones.add((), tokens),
+            SnafuAttribute::Debug(tokens) => debugs.add((), tokens),
+            SnafuAttribute::Transparent(tokens) => enum_errors.add(tokens, ATTR_TRANSPARENT),
+            SnafuAttribute::Whatever(tokens) => enum_errors.add(tokens, ATTR_WHATEVER),
             SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
         }
     }
 
+    let (module, errs) = modules.finish();
+    errors.extend(errs);
+
+    // Selectors placed in a module must be visible to its parent,
+    // where the error is.
+    let module = module.map(|m| m.unwrap_or_else(|| snake_case_ident(&name)));
+
     let (maybe_default_visibility, errs) = default_visibilities.finish();
-    let default_visibility = maybe_default_visibility.unwrap_or_else(private_visibility);
+    let default_visibility = maybe_default_visibility.unwrap_or_else(|| {
+        if module.is_some() {
+            Box::new(quote! { pub(super) })
+        } else {
+            private_visibility()
+        }
+    });
     errors.extend(errs);
 
     let (maybe_crate_root, errs) = crate_roots.finish();
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let (default_suffix, errs) = default_suffixes.finish();
+    errors.extend(errs);
+
+    let serialize = serialize_requested(serializes, &mut errors);
+
+    let (compare, errs) = compares.finish();
+    errors.extend(errs);
+
+    let (hash, errs) = hashes.finish();
+    errors.extend(errs);
+
+    let (kind, errs) = kinds.finish();
+    errors.extend(errs);
+
+    let (send_sync, errs) = send_syncs.finish();
+    errors.extend(errs);
+
+    let (display_prefix, errs) = display_prefixes.finish();
+    errors.extend(errs);
+
+    let (display_with_name, errs) = display_with_names.finish();
+    errors.extend(errs);
+
+    let (variant_name, errs) = variant_names.finish();
+    errors.extend(errs);
+
+    let (variants_const, errs) = variants_consts.finish();
+    errors.extend(errs);
+
+    let (clone, errs) = clones.finish();
+    errors.extend(errs);
+
+    let (debug, errs) = debugs.finish();
+    errors.extend(errs);
+
+    let (from_enum, errs) = from_enums.finish();
+    errors.extend(errs);
+
+    let send_sync = send_sync.map(|()| {
+        enum_
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(|field| field.ty.clone()))
+            .collect()
+    });
+
     let variants: sponge::AllErrors<_, _> = enum_
         .variants
         .into_iter()
         .map(|variant| {
             let fields = match variant.fields {
                 Fields::Named(f) => f.named.into_iter().collect(),
-                Fields::Unnamed(_) => {
-                    return Err(vec![syn::Error::new(
-                        variant.fields.span(),
-                        "Only struct-like and unit enum variants are supported",
-                    )]);
-                }
+                Fields::Unnamed(f) => f.unnamed.into_iter().collect(),
                 Fields::Unit => vec![],
             };
 
@@ -569,7 +1092,43 @@ fn parse_snafu_enum(
         })
         .collect();
 
-    let variants = errors.absorb(variants.into_result())?;
+    let variants: Vec<_> = errors.absorb(variants.into_result())?;
+
+    let mut errors = SyntaxErrors::default();
+    let mut no_context_source_types = BTreeSet::new();
+    let mut getter_types = BTreeMap::new();
+
+    for variant in &variants {
+        if let ContextSelectorKind::NoContext { source_field } = &variant.selector_kind {
+            let ty = source_field.transformation.ty();
+            if !no_context_source_types.insert(ty.to_token_stream().to_string()) {
+                errors.add(
+                    ty,
+                    "Context selectors without context must have distinct source types",
+                );
+            }
+        }
+
+        // A single method is generated for each getter name, so
+        // every field it returns must have the same type.
+        for (field, tokens) in &variant.getters {
+            let ty = field.ty.to_token_stream().to_string();
+            let expected = getter_types
+                .entry(field.name.to_string())
+                .or_insert(ty.clone());
+            if *expected != ty {
+                errors.add(
+                    tokens,
+                    format!(
+                        "Fields with the getter `{}` must all have the same type",
+                        field.name
+                    ),
+                );
+            }
+        }
+    }
+
+    errors.finish()?;
 
     Ok(EnumInfo {
         crate_root,
@@ -577,6 +1136,21 @@ fn parse_snafu_enum(
         generics,
         variants,
         default_visibility,
+        default_suffix,
+        serialize,
+        compare: compare.is_some(),
+        hash: hash.is_some(),
+        kind: kind.is_some(),
+        send_sync,
+        clone: clone.is_some(),
+        debug: debug.is_some(),
+        display_prefix,
+        display_with_name: display_with_name.is_some(),
+        variant_name: variant_name.is_some(),
+        variants_const: variants_const.is_some(),
+        from_enum,
+        module,
+        visibility,
     })
 }
 
@@ -597,28 +1171,90 @@ fn field_container(
     let mut display_formats = AtMostOne::new("display", outer_error_location);
     let mut visibilities = AtMostOne::new("visibility", outer_error_location);
     let mut contexts = AtMostOne::new("context", outer_error_location);
+    let mut fail_names = AtMostOne::new("context(fail_name)", outer_error_location);
+    let mut whatevers = AtMostOne::new("whatever", outer_error_location);
+    let mut transparents = AtMostOne::new("transparent", outer_error_location);
+    let mut selector_docs = AtMostOne::new("doc", outer_error_location);
+    let mut codes = AtMostOne::new("code", outer_error_location);
+    let mut descriptions = AtMostOne::new("description", outer_error_location);
+    let mut helps = AtMostOne::new("help", outer_error_location);
+    let mut retryables = AtMostOne::new("retryable", outer_error_location);
+    let mut deprecations = AtMostOne::new("deprecated", outer_error_location);
+    let mut builders = AtMostOne::new("builder", outer_error_location);
+    let mut selector_defaults = AtMostOne::new("context(default)", outer_error_location);
+    let mut exit_codes = AtMostOne::new("exit_code", outer_error_location);
+    let mut search_chains = AtMostOne::new("backtrace(search_chain)", outer_error_location);
+    let mut skip_from_enums = AtMostOne::new("from_enum(false)", outer_error_location);
     let mut doc_comment = String::new();
+    let mut doc_comment_tokens = None;
     let mut reached_end_of_doc_comment = false;
 
+    let all_field_names: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(name) => name.to_string().trim_start_matches("r#").to_string(),
+            None => format!("_{}", index),
+        })
+        .collect();
+
     for attr in attrs {
         match attr {
             SnafuAttribute::Display(tokens, d) => display_formats.add(d, tokens),
+            SnafuAttribute::DisplayPrefix(tokens, ..) => {
+                outer_errors.add(tokens, ATTR_DISPLAY_PREFIX)
+            }
+            SnafuAttribute::DisplayWithName(tokens) => {
+                outer_errors.add(tokens, ATTR_DISPLAY_WITH_NAME)
+            }
+            SnafuAttribute::VariantName(tokens) => outer_errors.add(tokens, ATTR_VARIANT_NAME),
+            SnafuAttribute::VariantsConst(tokens) => outer_errors.add(tokens, ATTR_VARIANTS_CONST),
+            SnafuAttribute::Doc(tokens, d) => selector_docs.add(d, tokens),
             SnafuAttribute::Visibility(tokens, v) => visibilities.add(v, tokens),
+            SnafuAttribute::Context(tokens, Context::FailName(n)) => fail_names.add(n, tokens),
+            SnafuAttribute::Context(tokens, Context::Default) => selector_defaults.add((), tokens),
             SnafuAttribute::Context(tokens, c) => contexts.add(c, tokens),
+            SnafuAttribute::Convert(tokens, ..) => outer_errors.add(tokens, ATTR_CONVERT),
+            SnafuAttribute::Builder(tokens) => builders.add((), tokens),
+            SnafuAttribute::Code(tokens, c) => codes.add(c, tokens),
+            SnafuAttribute::Description(tokens, d) => descriptions.add(d, tokens),
+            SnafuAttribute::Help(tokens, h) => helps.add(h, tokens),
+            SnafuAttribute::Retryable(tokens, r) => retryables.add(r, tokens),
+            SnafuAttribute::Deprecated(tokens, d) => deprecations.add(d, tokens),
+            SnafuAttribute::ExitCode(tokens, c) => exit_codes.add(c, tokens),
+            SnafuAttribute::FromEnum(tokens, f) => match f {
+                Some(_) => outer_errors.add(tokens, ATTR_FROM_ENUM),
+                None => skip_from_enums.add((), tokens),
+            },
+            SnafuAttribute::Getter(tokens) => outer_errors.add(tokens, ATTR_GETTER),
+            SnafuAttribute::Kind(tokens) => outer_errors.add(tokens, ATTR_KIND),
+            SnafuAttribute::Module(tokens, ..) => outer_errors.add(tokens, ATTR_MODULE),
+            SnafuAttribute::SendSync(tokens) => outer_errors.add(tokens, ATTR_SEND_SYNC),
+            SnafuAttribute::Clone(tokens) => outer_errors.add(tokens, ATTR_CLONE),
+            SnafuAttribute::Debug(tokens) => outer_errors.add(tokens, ATTR_DEBUG),
+            SnafuAttribute::Transparent(tokens) => transparents.add((), tokens),
+            SnafuAttribute::Whatever(tokens) => whatevers.add((), tokens),
             SnafuAttribute::Source(tokens, ..) => outer_errors.add(tokens, ATTR_SOURCE),
             SnafuAttribute::Backtrace(tokens, ..) => outer_errors.add(tokens, ATTR_BACKTRACE),
+            SnafuAttribute::BacktraceSearchChain(tokens) => search_chains.add((), tokens),
+            SnafuAttribute::Compare(tokens, ..) => outer_errors.add(tokens, ATTR_COMPARE),
+            SnafuAttribute::Hash(tokens, ..) => outer_errors.add(tokens, ATTR_HASH),
+            SnafuAttribute::Location(tokens, ..) => outer_errors.add(tokens, ATTR_LOCATION),
             SnafuAttribute::CrateRoot(tokens, ..) => outer_errors.add(tokens, ATTR_CRATE_ROOT),
-            SnafuAttribute::DocComment(_tts, doc_comment_line) => {
+            SnafuAttribute::Serialize(tokens) => outer_errors.add(tokens, ATTR_SERIALIZE),
+            SnafuAttribute::Default(tokens, ..) => outer_errors.add(tokens, ATTR_DEFAULT),
+            SnafuAttribute::DocComment(tts, doc_comment_line) => {
                 // We join all the doc comment attributes with a space,
                 // but end once the summary of the doc comment is
                 // complete, which is indicated by an empty line.
                 if !reached_end_of_doc_comment {
+                    doc_comment_tokens.get_or_insert(tts);
                     let trimmed = doc_comment_line.trim();
                     if trimmed.is_empty() {
                         reached_end_of_doc_comment = true;
                     } else {
                         if !doc_comment.is_empty() {
-                            doc_comment.push_str(" ");
+                            doc_comment.push(' ');
                         }
                         doc_comment.push_str(trimmed);
                     }
@@ -628,20 +1264,55 @@ fn field_container(
     }
 
     let mut user_fields = Vec::new();
+    let mut default_fields = Vec::new();
     let mut source_fields = AtMostOne::new("source", inner_error_location);
     let mut backtrace_fields = AtMostOne::new("backtrace", inner_error_location);
-
-    for syn_field in fields {
+    let mut location_fields = AtMostOne::new("location", inner_error_location);
+    let mut uncompared_fields = Vec::new();
+    let mut unhashed_fields = Vec::new();
+    let mut getters = Vec::new();
+    let mut members = Vec::new();
+
+    // The first field of a tuple variant is only treated as the source
+    // when no other field has explicitly claimed that role. Errors in
+    // the attributes are ignored here; they are reported below.
+    let has_explicit_positional_source = fields
+        .iter()
+        .skip(1)
+        .filter(|f| f.ident.is_none())
+        .any(has_explicit_source_attribute);
+
+    for (index, syn_field) in fields.into_iter().enumerate() {
         let original = syn_field.clone();
         let span = syn_field.span();
-        let name = syn_field
-            .ident
-            .as_ref()
-            .ok_or_else(|| vec![syn::Error::new(span, "Must have a named field")])?;
-        let field = Field {
+
+        // Positional fields are bound as `_0`, `_1`, etc. so that they
+        // can be referred to in display format arguments. The first
+        // positional field plays the role of a field named `source`.
+        let (name, member, is_implicit_source) = match &syn_field.ident {
+            Some(name) => (
+                name.clone(),
+                syn::Member::Named(name.clone()),
+                name == "source",
+            ),
+            None => (
+                format_ident!("_{}", index, span = span),
+                syn::Member::Unnamed(syn::Index {
+                    index: index as u32,
+                    span,
+                }),
+                index == 0 && !has_explicit_positional_source,
+            ),
+        };
+        let name = &name;
+        members.push(member.clone());
+        let mut field = Field {
             name: name.clone(),
+            member,
             ty: syn_field.ty.clone(),
             original,
+            visibility: None,
+            conversion: Conversion::Into,
         };
 
         // Check whether we have multiple source/backtrace attributes on this field.
@@ -650,15 +1321,36 @@ fn field_container(
         // logic after the attribute loop.  For example, we need to know whether there's a
         // source transformation before we record a source field, but it might be on a
         // later attribute.  We use the data field of `source_attrs` to track any
-        // transformations in case it was a `source(from(...))`, but for backtraces we
-        // don't need any more data.
+        // transformations in case it was a `source(from(...))`, and the data field of
+        // `backtrace_attrs` to track the condition of a `backtrace(capture_if(...))`.
         let mut source_attrs = AtMostOne::new("source", ErrorLocation::OnField);
         let mut backtrace_attrs = AtMostOne::new("backtrace", ErrorLocation::OnField);
+        let mut location_attrs = AtMostOne::new("location", ErrorLocation::OnField);
+        let mut default_attrs = AtMostOne::new("default", ErrorLocation::OnField);
+        let mut visibility_attrs = AtMostOne::new("visibility", ErrorLocation::OnField);
+        let mut convert_attrs = AtMostOne::new("convert", ErrorLocation::OnField);
+        let mut getter_attrs = AtMostOne::new("getter", ErrorLocation::OnField);
 
         // Keep track of the negative markers so we can check for inconsistencies and
         // exclude fields even if they have the "source" or "backtrace" name.
         let mut source_opt_out = false;
         let mut backtrace_opt_out = false;
+        let mut location_opt_out = false;
+
+        // `source(arc)` stores the source in an `Arc`, which is
+        // looked through when returning it from `Error::source`.
+        let mut source_is_arc = false;
+
+        // `source(display_only)` implies `source`, but the value is
+        // left out of `Error::source` as it isn't an `Error`.
+        let mut source_display_only_tokens = Vec::new();
+
+        // `source(bound)` adds where clauses to the code that converts
+        // into the source, but doesn't mark the field as a source by itself.
+        let mut source_bounds = Vec::new();
+        let mut source_bound_tokens = Vec::new();
+
+        let is_implicit_location = is_location_type(&field.ty);
 
         let mut field_errors = errors.scoped(ErrorLocation::OnField);
 
@@ -679,8 +1371,10 @@ fn field_container(
                                 }
                                 if v {
                                     source_attrs.add(None, tokens.clone());
-                                } else if name == "source" {
+                                } else if is_implicit_source {
                                     source_opt_out = true;
+                                } else if field.is_positional() {
+                                    field_errors.add(tokens.clone(), ATTR_SOURCE_FALSE_POSITIONAL);
                                 } else {
                                     field_errors.add(tokens.clone(), ATTR_SOURCE_FALSE);
                                 }
@@ -691,22 +1385,106 @@ fn field_container(
                                 }
                                 source_attrs.add(Some((t, e)), tokens.clone());
                             }
+                            Source::Arc => match arc_inner_type(&field.ty) {
+                                Some(inner) => {
+                                    if source_opt_out {
+                                        field_errors
+                                            .add(tokens.clone(), SOURCE_BOOL_ARC_INCOMPATIBLE);
+                                    }
+                                    let wrap = syn::parse_quote! { ::std::sync::Arc::new };
+                                    source_attrs.add(Some((inner.clone(), wrap)), tokens.clone());
+                                    source_is_arc = true;
+                                }
+                                None => field_errors.add(tokens.clone(), ATTR_SOURCE_ARC_TYPE),
+                            },
+                            Source::Bound(bounds) => {
+                                source_bounds.extend(bounds);
+                                source_bound_tokens.push(tokens.clone());
+                            }
+                            Source::DisplayOnly => {
+                                if source_opt_out {
+                                    field_errors
+                                        .add(tokens.clone(), SOURCE_BOOL_DISPLAY_ONLY_INCOMPATIBLE);
+                                }
+                                source_display_only_tokens.push(tokens.clone());
+                            }
                         }
                     }
                 }
-                SnafuAttribute::Backtrace(tokens, v) => {
+                SnafuAttribute::Backtrace(tokens, v, capture_if) => {
                     if v {
-                        backtrace_attrs.add((), tokens);
+                        backtrace_attrs.add(capture_if, tokens);
                     } else if name == "backtrace" {
                         backtrace_opt_out = true;
                     } else {
                         field_errors.add(tokens, ATTR_BACKTRACE_FALSE);
                     }
                 }
-                SnafuAttribute::Visibility(tokens, ..) => field_errors.add(tokens, ATTR_VISIBILITY),
+                SnafuAttribute::Location(tokens, v) => {
+                    if v {
+                        location_attrs.add((), tokens);
+                    } else if is_implicit_location {
+                        location_opt_out = true;
+                    } else {
+                        field_errors.add(tokens, ATTR_LOCATION_FALSE);
+                    }
+                }
+                SnafuAttribute::Compare(tokens, c) => {
+                    if c {
+                        field_errors.add(tokens, ATTR_COMPARE);
+                    } else {
+                        uncompared_fields.push(field.member.clone());
+                    }
+                }
+                SnafuAttribute::Hash(tokens, h) => {
+                    if h {
+                        field_errors.add(tokens, ATTR_HASH);
+                    } else {
+                        unhashed_fields.push(field.member.clone());
+                    }
+                }
+                SnafuAttribute::Visibility(tokens, v) => visibility_attrs.add(v, tokens),
+                SnafuAttribute::Convert(tokens, c) => convert_attrs.add(c, tokens),
+                SnafuAttribute::Getter(tokens) => getter_attrs.add((), tokens),
                 SnafuAttribute::Display(tokens, ..) => field_errors.add(tokens, ATTR_DISPLAY),
+                SnafuAttribute::DisplayPrefix(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_DISPLAY_PREFIX)
+                }
+                SnafuAttribute::DisplayWithName(tokens) => {
+                    field_errors.add(tokens, ATTR_DISPLAY_WITH_NAME)
+                }
+                SnafuAttribute::VariantName(tokens) => field_errors.add(tokens, ATTR_VARIANT_NAME),
+                SnafuAttribute::VariantsConst(tokens) => {
+                    field_errors.add(tokens, ATTR_VARIANTS_CONST)
+                }
+                SnafuAttribute::Doc(tokens, ..) => field_errors.add(tokens, ATTR_DOC),
                 SnafuAttribute::Context(tokens, ..) => field_errors.add(tokens, ATTR_CONTEXT),
                 SnafuAttribute::CrateRoot(tokens, ..) => field_errors.add(tokens, ATTR_CRATE_ROOT),
+                SnafuAttribute::Builder(tokens) => field_errors.add(tokens, ATTR_BUILDER),
+                SnafuAttribute::BacktraceSearchChain(tokens) => {
+                    field_errors.add(tokens, ATTR_BACKTRACE_SEARCH_CHAIN)
+                }
+                SnafuAttribute::Default(tokens, v) => default_attrs.add(v, tokens),
+                SnafuAttribute::Code(tokens, ..) => field_errors.add(tokens, ATTR_CODE),
+                SnafuAttribute::Description(tokens, ..) => {
+                    field_errors.add(tokens, ATTR_DESCRIPTION)
+                }
+                SnafuAttribute::Help(tokens, ..) => field_errors.add(tokens, ATTR_HELP),
+                SnafuAttribute::Retryable(tokens, ..) => field_errors.add(tokens, ATTR_RETRYABLE),
+                SnafuAttribute::Deprecated(tokens, ..) => field_errors.add(tokens, ATTR_DEPRECATED),
+                SnafuAttribute::ExitCode(tokens, ..) => field_errors.add(tokens, ATTR_EXIT_CODE),
+                SnafuAttribute::FromEnum(tokens, f) => match f {
+                    Some(_) => field_errors.add(tokens, ATTR_FROM_ENUM),
+                    None => field_errors.add(tokens, ATTR_FROM_ENUM_FALSE),
+                },
+                SnafuAttribute::Kind(tokens) => field_errors.add(tokens, ATTR_KIND),
+                SnafuAttribute::Module(tokens, ..) => field_errors.add(tokens, ATTR_MODULE),
+                SnafuAttribute::SendSync(tokens) => field_errors.add(tokens, ATTR_SEND_SYNC),
+                SnafuAttribute::Serialize(tokens) => field_errors.add(tokens, ATTR_SERIALIZE),
+                SnafuAttribute::Clone(tokens) => field_errors.add(tokens, ATTR_CLONE),
+                SnafuAttribute::Debug(tokens) => field_errors.add(tokens, ATTR_DEBUG),
+                SnafuAttribute::Transparent(tokens) => field_errors.add(tokens, ATTR_TRANSPARENT),
+                SnafuAttribute::Whatever(tokens) => field_errors.add(tokens, ATTR_WHATEVER),
                 SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
             }
         }
@@ -716,25 +1494,104 @@ fn field_container(
         errors.extend(errs);
         let (backtrace_attr, errs) = backtrace_attrs.finish_with_location();
         errors.extend(errs);
+        let (location_attr, errs) = location_attrs.finish_with_location();
+        errors.extend(errs);
+        let (default_attr, errs) = default_attrs.finish_with_location();
+        errors.extend(errs);
+        let (visibility_attr, errs) = visibility_attrs.finish_with_location();
+        errors.extend(errs);
+        let (convert_attr, errs) = convert_attrs.finish_with_location();
+        errors.extend(errs);
+        let (getter_attr, errs) = getter_attrs.finish_with_location();
+        errors.extend(errs);
 
-        let source_attr = source_attr.or_else(|| {
-            if field.name == "source" && !source_opt_out {
-                Some((None, syn_field.clone().into_token_stream()))
+        if let Some(((), tokens)) = getter_attr {
+            if field.is_positional() {
+                errors.add(tokens, "`getter` can only be used on a field with a name");
             } else {
-                None
+                getters.push((field.clone(), tokens));
             }
-        });
+        }
+
+        let is_display_only = !source_display_only_tokens.is_empty();
+        let source_attr = source_attr
+            .or_else(|| {
+                source_display_only_tokens
+                    .first()
+                    .map(|tokens| (None, tokens.clone()))
+            })
+            .or_else(|| {
+                if is_implicit_source && !source_opt_out {
+                    Some((None, syn_field.clone().into_token_stream()))
+                } else {
+                    None
+                }
+            });
 
         let backtrace_attr = backtrace_attr.or_else(|| {
             if field.name == "backtrace" && !backtrace_opt_out {
-                Some(((), syn_field.clone().into_token_stream()))
+                Some((None, syn_field.clone().into_token_stream()))
             } else {
                 None
             }
         });
 
-        if let Some((maybe_transformation, location)) = source_attr {
-            let Field { name, ty, .. } = field;
+        let location_attr = location_attr.or_else(|| {
+            if is_implicit_location && !location_opt_out {
+                Some(((), syn_field.clone().into_token_stream()))
+            } else {
+                None
+            }
+        });
+
+        let is_context_field =
+            source_attr.is_none() && backtrace_attr.is_none() && location_attr.is_none();
+        if let (Some((_, tokens)), false) = (&default_attr, is_context_field) {
+            errors.add(tokens, "Only context fields can have a default value");
+        }
+
+        if let Some((visibility, tokens)) = visibility_attr {
+            if is_context_field && default_attr.is_none() {
+                field.visibility = Some(visibility.into_token_stream());
+            } else {
+                errors.add(tokens, "Only context fields can have a visibility");
+            }
+        }
+
+        if let Some((conversion, tokens)) = convert_attr {
+            if is_context_field && default_attr.is_none() {
+                field.conversion = conversion;
+            } else {
+                errors.add(tokens, "Only context fields can choose a conversion");
+            }
+        }
+
+        if let (Some((Some(_), tokens)), Some(_)) = (&backtrace_attr, &source_attr) {
+            errors.add(
+                tokens,
+                "`backtrace(capture_if)` can only be used on a backtrace field",
+            );
+        }
+
+        if let (true, Some(_)) = (is_display_only, &backtrace_attr) {
+            for tokens in &source_display_only_tokens {
+                errors.add(
+                    tokens,
+                    "`source(display_only)` cannot delegate the backtrace to the source",
+                );
+            }
+        }
+
+        if source_attr.is_none() {
+            for tokens in source_bound_tokens {
+                errors.add(tokens, "`source(bound)` can only be used on a source field");
+            }
+        }
+
+        if let Some((maybe_transformation, location)) = source_attr {
+            let Field {
+                name, member, ty, ..
+            } = field;
             let transformation = maybe_transformation
                 .map(|(ty, expr)| Transformation::Transform { ty, expr })
                 .unwrap_or_else(|| Transformation::None { ty });
@@ -742,15 +1599,23 @@ fn field_container(
             source_fields.add(
                 SourceField {
                     name,
+                    member,
                     transformation,
                     // Specifying `backtrace` on a source field is how you request
                     // delegation of the backtrace to the source error type.
                     backtrace_delegate: backtrace_attr.is_some(),
+                    is_arc: source_is_arc,
+                    is_display_only,
+                    bounds: source_bounds,
                 },
                 location,
             );
-        } else if let Some((_, location)) = backtrace_attr {
-            backtrace_fields.add(field, location);
+        } else if let Some((capture_if, location)) = backtrace_attr {
+            backtrace_fields.add((field, capture_if), location);
+        } else if let Some((_, location)) = location_attr {
+            location_fields.add(field, location);
+        } else if let Some((value, _)) = default_attr {
+            default_fields.push(DefaultField { field, value });
         } else {
             user_fields.push(field);
         }
@@ -762,6 +1627,9 @@ fn field_container(
     let (backtrace, errs) = backtrace_fields.finish_with_location();
     errors.extend(errs);
 
+    let (location, errs) = location_fields.finish();
+    errors.extend(errs);
+
     match (&source, &backtrace) {
         (Some(source), Some(backtrace)) if source.0.backtrace_delegate => {
             let source_location = source.1.clone();
@@ -778,18 +1646,162 @@ fn field_container(
         _ => {} // no conflict
     }
 
-    let (display_format, errs) = display_formats.finish();
+    let (display_format, errs) = display_formats.finish_with_location();
     errors.extend(errs);
+    let (display_format, display_format_tokens) = match display_format {
+        Some((display_format, tts)) => (Some(display_format), Some(tts)),
+        None => (None, None),
+    };
 
     let (visibility, errs) = visibilities.finish();
     errors.extend(errs);
 
-    let (is_context, errs) = contexts.finish();
+    let (selector_doc, errs) = selector_docs.finish();
+    errors.extend(errs);
+
+    let (code, errs) = codes.finish();
     errors.extend(errs);
 
+    let (description, errs) = descriptions.finish();
+    errors.extend(errs);
+
+    let (help, errs) = helps.finish();
+    errors.extend(errs);
+
+    let (retryable, errs) = retryables.finish();
+    errors.extend(errs);
+
+    let (deprecated, errs) = deprecations.finish_with_location();
+    errors.extend(errs);
+
+    let (exit_code, errs) = exit_codes.finish();
+    errors.extend(errs);
+
+    let (skip_from_enum, errs) = skip_from_enums.finish();
+    errors.extend(errs);
+
+    let (builder, errs) = builders.finish_with_location();
+    errors.extend(errs);
+
+    let (selector_default, errs) = selector_defaults.finish_with_location();
+    errors.extend(errs);
+
+    let (context, errs) = contexts.finish_with_location();
+    errors.extend(errs);
+
+    let suppress_selector = match &context {
+        Some((Context::Suppress, tts)) => Some(tts.clone()),
+        _ => None,
+    };
+
+    let (is_context, selector_name) = match context {
+        None => (None, None),
+        Some((Context::Suppress, tts)) => (Some((true, tts)), None),
+        Some((Context::Flag(c), tts)) => (Some((c, tts)), None),
+        Some((Context::Suffix(suffix), tts)) => {
+            (Some((true, tts)), Some(SelectorName::Suffix(suffix)))
+        }
+        Some((Context::Name(name), tts)) => (Some((true, tts)), Some(SelectorName::Exact(name))),
+        Some((Context::FailName(..), _)) | Some((Context::Default, _)) => {
+            unreachable!("Collected separately")
+        }
+    };
+
+    let (fail_name, errs) = fail_names.finish_with_location();
+    errors.extend(errs);
+
+    let (is_whatever, errs) = whatevers.finish_with_location();
+    errors.extend(errs);
+
+    let (is_transparent, errs) = transparents.finish_with_location();
+    errors.extend(errs);
+
+    if let Some(((), transparent_tts)) = &is_transparent {
+        let mut errors = errors.scoped(outer_error_location);
+        if let Some(display_tts) = &display_format_tokens {
+            errors.add(transparent_tts, TRANSPARENT_DISPLAY_INCOMPATIBLE);
+            errors.add(display_tts, TRANSPARENT_DISPLAY_INCOMPATIBLE);
+        }
+        if let Some(((), whatever_tts)) = &is_whatever {
+            errors.add(transparent_tts, TRANSPARENT_WHATEVER_INCOMPATIBLE);
+            errors.add(whatever_tts, TRANSPARENT_WHATEVER_INCOMPATIBLE);
+        }
+        if let Some((source, source_tts)) = &source {
+            if source.is_display_only {
+                errors.add(
+                    transparent_tts,
+                    TRANSPARENT_SOURCE_DISPLAY_ONLY_INCOMPATIBLE,
+                );
+                errors.add(source_tts, TRANSPARENT_SOURCE_DISPLAY_ONLY_INCOMPATIBLE);
+            }
+        }
+    }
+
+    // The doc comment is only used when there's no other format, and
+    // a misspelled field would otherwise be reported inside the
+    // generated code.
+    if let (None, None, None, Some(tokens)) = (
+        &display_format,
+        &is_whatever,
+        &is_transparent,
+        &doc_comment_tokens,
+    ) {
+        for argument in format_argument_names(&doc_comment) {
+            if !all_field_names.iter().any(|name| name == argument) {
+                errors.add(
+                    tokens,
+                    format!("field `{}` not found on `{}`", argument, name),
+                );
+            }
+        }
+    }
+
     let source_field = source.map(|(val, _tts)| val);
 
-    let selector_kind = if is_context.unwrap_or(true) {
+    let selector_kind = if let Some(((), whatever_tts)) = is_whatever {
+        // Whatever selectors have no context selector to disable or rename
+        let customized_context = is_context
+            .as_ref()
+            .filter(|(c, _tts)| !c || selector_name.is_some());
+        if let Some((_, context_tts)) = customized_context {
+            let mut errors = errors.scoped(outer_error_location);
+            errors.add(whatever_tts, WHATEVER_CONTEXT_INCOMPATIBLE);
+            errors.add(context_tts, WHATEVER_CONTEXT_INCOMPATIBLE);
+        }
+
+        let (message_fields, other_fields): (Vec<_>, Vec<_>) = user_fields
+            .into_iter()
+            .partition(|field| field.name == "message");
+
+        errors.extend(other_fields.into_iter().map(|Field { original, .. }| {
+            syn::Error::new_spanned(original, "Whatever selectors must not have context fields")
+        }));
+
+        let message_field = message_fields.into_iter().next().ok_or_else(|| {
+            vec![syn::Error::new(
+                variant_span,
+                "Whatever selectors must have a message field",
+            )]
+        })?;
+
+        ContextSelectorKind::Whatever {
+            source_field,
+            message_field,
+        }
+    } else if is_context.map_or(true, |(c, _tts)| c) {
+        if is_transparent.is_some() {
+            errors.extend(user_fields.iter().map(|Field { original, .. }| {
+                syn::Error::new_spanned(
+                    original,
+                    "Transparent selectors must not have context fields",
+                )
+            }));
+
+            if source_field.is_none() {
+                errors.add(&name, "Transparent selectors must have a source field");
+            }
+        }
+
         ContextSelectorKind::Context {
             source_field,
             user_fields,
@@ -812,16 +1824,239 @@ fn field_container(
         ContextSelectorKind::NoContext { source_field }
     };
 
+    let (search_chain, errs) = search_chains.finish_with_location();
+    errors.extend(errs);
+
+    if let Some(((), search_chain_tts)) = &search_chain {
+        if selector_kind.is_whatever() {
+            errors.add(
+                search_chain_tts,
+                "`backtrace(search_chain)` is not supported for whatever errors",
+            );
+        } else if selector_kind.source_field().is_none() {
+            errors.add(
+                search_chain_tts,
+                "`backtrace(search_chain)` requires a source field",
+            );
+        }
+    }
+
+    if let Some(suppress_tts) = &suppress_selector {
+        match &selector_kind {
+            ContextSelectorKind::Context {
+                source_field: None,
+                ..
+            } => {}
+            _ => errors.add(
+                suppress_tts,
+                "`context(suppress)` is only supported for context selectors without a source field",
+            ),
+        }
+
+        let selector_attributes = [
+            builder.as_ref().map(|(_, tts)| ("builder", tts)),
+            selector_default
+                .as_ref()
+                .map(|(_, tts)| ("context(default)", tts)),
+            deprecated.as_ref().map(|(_, tts)| ("deprecated", tts)),
+            fail_name
+                .as_ref()
+                .map(|(_, tts)| ("context(fail_name)", tts)),
+        ];
+        for (attribute, tts) in selector_attributes.iter().flatten() {
+            errors.add(
+                *tts,
+                format!(
+                    "`{}` is not supported when the context selector is suppressed",
+                    attribute
+                ),
+            );
+        }
+    }
+
+    if let Some(((), builder_tts)) = &builder {
+        match &selector_kind {
+            ContextSelectorKind::Context { user_fields, .. } => {
+                if user_fields.iter().any(Field::is_positional) {
+                    errors.add(builder_tts, "Builders are not supported for tuple variants");
+                }
+            }
+            _ => errors.add(
+                builder_tts,
+                "Builders are only supported for context selectors with context",
+            ),
+        }
+    }
+
+    if let Some(((), default_tts)) = &selector_default {
+        match &selector_kind {
+            ContextSelectorKind::Context { .. } => {}
+            _ => errors.add(
+                default_tts,
+                "`context(default)` is only supported for context selectors with context",
+            ),
+        }
+    }
+
+    if let Some((_, deprecated_tts)) = &deprecated {
+        match &selector_kind {
+            ContextSelectorKind::Context { .. } => {}
+            _ => errors.add(
+                deprecated_tts,
+                "`deprecated` is only supported for context selectors with context",
+            ),
+        }
+    }
+
+    if let Some((fail_name, fail_name_tts)) = &fail_name {
+        let has_fail = match &selector_kind {
+            ContextSelectorKind::Context {
+                source_field: None, ..
+            } => true,
+            _ => false,
+        };
+
+        if !has_fail {
+            errors.add(
+                fail_name_tts,
+                "Only context selectors without a source field have a `fail` method",
+            );
+        } else if fail_name == "build" {
+            errors.add(
+                fail_name_tts,
+                "The `fail` method cannot be renamed to `build`",
+            );
+        }
+    }
+
+    let (backtrace_field, backtrace_capture_if) = match backtrace {
+        Some(((field, capture_if), _tts)) => (Some(field), capture_if),
+        None => (None, None),
+    };
+
     Ok(FieldContainer {
         name,
-        backtrace_field: backtrace.map(|(val, _tts)| val),
+        backtrace_field,
+        backtrace_capture_if,
+        backtrace_search_chain: search_chain.is_some(),
+        location_field: location,
+        default_fields,
         selector_kind,
+        selector_name,
+        fail_name: fail_name.map(|(val, _tts)| val),
+        builder: builder.is_some(),
+        selector_default: selector_default.is_some(),
+        suppress_selector: suppress_selector.is_some(),
+        deprecated: deprecated.map(|(note, _tts)| note),
+        selector_doc,
+        code,
+        description,
+        help,
+        retryable,
+        exit_code,
+        is_transparent: is_transparent.is_some(),
+        skip_from_enum: skip_from_enum.is_some(),
+        uncompared_fields,
+        unhashed_fields,
+        members,
+        getters,
         display_format,
         doc_comment,
         visibility,
     })
 }
 
+// The names used by `{name}` or `{name:...}` placeholders in a format
+// string, including named widths and precisions such as `width$`.
+// Escaped braces and positional placeholders are skipped.
+fn format_argument_names(format: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = format;
+
+    while let Some(start) = rest.find(|c| c == '{' || c == '}') {
+        let (brace, after) = rest[start..].split_at(1);
+        if after.starts_with(brace) {
+            rest = &after[1..];
+            continue;
+        }
+        if brace == "}" {
+            rest = after;
+            continue;
+        }
+
+        let end = after.find(|c| c == '}' || c == ':').unwrap_or(after.len());
+        let argument = after[..end].trim();
+        let is_name = argument
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_');
+        if is_name {
+            names.push(argument);
+        }
+
+        let spec_end = after[end..].find('}').map_or(after.len(), |i| end + i);
+        names.extend(crate::parse::spec_count_arguments(&after[end..spec_end]));
+        rest = &after[spec_end..];
+    }
+
+    names
+}
+
+// Only `snafu::Location` and `::snafu::Location` are detected. Other
+// types named `Location` may be unrelated to SNAFU, so fields using
+// them (or an imported `Location`) need `#[snafu(location)]`.
+fn is_location_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            let mut segments = path.segments.iter();
+            match (segments.next(), segments.next(), segments.next()) {
+                (Some(krate), Some(name), None) => {
+                    krate.ident == "snafu"
+                        && krate.arguments.is_empty()
+                        && name.ident == "Location"
+                        && name.arguments.is_empty()
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+// This matches `Arc<T>`, `std::sync::Arc<T>`, etc. by the last
+// segment of the path, returning the `T`.
+fn arc_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Arc" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn has_explicit_source_attribute(field: &syn::Field) -> bool {
+    let attrs = attributes_from_syn(field.attrs.clone()).ok();
+
+    attrs.iter().flatten().any(|attr| match attr {
+        SnafuAttribute::Source(_, ss) => ss.iter().any(|s| match s {
+            Source::Flag(v) => *v,
+            Source::From(..) | Source::Arc | Source::DisplayOnly => true,
+            Source::Bound(..) => false,
+        }),
+        _ => false,
+    })
+}
+
 fn parse_snafu_struct(
     struct_: syn::DataStruct,
     name: syn::Ident,
@@ -856,18 +2091,122 @@ fn parse_snafu_named_struct(
     let attrs = attributes_from_syn(attrs)?;
 
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnNamedStruct);
+    let mut compares = AtMostOne::new("compare", ErrorLocation::OnNamedStruct);
+    let mut hashes = AtMostOne::new("hash", ErrorLocation::OnNamedStruct);
+    let mut send_syncs = AtMostOne::new("send_sync", ErrorLocation::OnNamedStruct);
+    let mut clones = AtMostOne::new("clone", ErrorLocation::OnNamedStruct);
+    let mut debugs = AtMostOne::new("debug", ErrorLocation::OnNamedStruct);
+    let mut compare_false_tokens = Vec::new();
+    let mut serializes = AtMostOne::new("serialize", ErrorLocation::OnNamedStruct);
+    let mut hash_false_tokens = Vec::new();
+    let mut code_tokens = Vec::new();
+    let mut help_tokens = Vec::new();
+    let mut retryable_tokens = Vec::new();
+    let mut exit_code_tokens = Vec::new();
+    let mut from_enum_tokens = Vec::new();
+    let mut suppress_tokens = Vec::new();
 
     let attrs = attrs
         .into_iter()
         .flat_map(|attr| match attr {
+            SnafuAttribute::Context(tokens, Context::Suppress) => {
+                suppress_tokens.push(tokens);
+                None
+            }
             SnafuAttribute::CrateRoot(tokens, root) => {
                 crate_roots.add(root, tokens);
                 None
             }
+            SnafuAttribute::Compare(tokens, c) => {
+                if c {
+                    compares.add((), tokens);
+                } else {
+                    compare_false_tokens.push(tokens);
+                }
+                None
+            }
+            SnafuAttribute::Hash(tokens, h) => {
+                if h {
+                    hashes.add((), tokens);
+                } else {
+                    hash_false_tokens.push(tokens);
+                }
+                None
+            }
+            SnafuAttribute::SendSync(tokens) => {
+                send_syncs.add((), tokens);
+                None
+            }
+            SnafuAttribute::Serialize(tokens) => {
+                serializes.add((), tokens);
+                None
+            }
+            SnafuAttribute::Clone(tokens) => {
+                clones.add((), tokens);
+                None
+            }
+            SnafuAttribute::Debug(tokens) => {
+                debugs.add((), tokens);
+                None
+            }
+            SnafuAttribute::Code(tokens, ..) => {
+                code_tokens.push(tokens);
+                None
+            }
+            SnafuAttribute::Help(tokens, ..) => {
+                help_tokens.push(tokens);
+                None
+            }
+            SnafuAttribute::Retryable(tokens, ..) => {
+                retryable_tokens.push(tokens);
+                None
+            }
+            SnafuAttribute::ExitCode(tokens, ..) => {
+                exit_code_tokens.push(tokens);
+                None
+            }
+            SnafuAttribute::FromEnum(tokens, f) => {
+                from_enum_tokens.push((tokens, f.is_some()));
+                None
+            }
             other => Some(other),
         })
         .collect();
 
+    let mut struct_errors = errors.scoped(ErrorLocation::OnNamedStruct);
+    for tokens in compare_false_tokens {
+        struct_errors.add(tokens, ATTR_COMPARE_FALSE);
+    }
+    for tokens in hash_false_tokens {
+        struct_errors.add(tokens, ATTR_HASH_FALSE);
+    }
+    for tokens in code_tokens {
+        struct_errors.add(tokens, ATTR_CODE);
+    }
+    for tokens in help_tokens {
+        struct_errors.add(tokens, ATTR_HELP);
+    }
+    for tokens in retryable_tokens {
+        struct_errors.add(tokens, ATTR_RETRYABLE);
+    }
+    for tokens in exit_code_tokens {
+        struct_errors.add(tokens, ATTR_EXIT_CODE);
+    }
+    for tokens in suppress_tokens {
+        struct_errors.add(tokens, ATTR_CONTEXT_SUPPRESS);
+    }
+    for (tokens, has_path) in from_enum_tokens {
+        if has_path {
+            struct_errors.add(tokens, ATTR_FROM_ENUM);
+        } else {
+            struct_errors.add(tokens, ATTR_FROM_ENUM_FALSE);
+        }
+    }
+
+    let (send_sync, errs) = send_syncs.finish();
+    errors.extend(errs);
+    let send_sync = send_sync.map(|()| fields.iter().map(|field| field.ty.clone()).collect());
+
     let field_container = field_container(
         name,
         span,
@@ -878,16 +2217,43 @@ fn parse_snafu_named_struct(
         ErrorLocation::InNamedStruct,
     )?;
 
+    // The fields of a struct can already be accessed directly.
+    for (_, tokens) in &field_container.getters {
+        errors
+            .scoped(ErrorLocation::InNamedStruct)
+            .add(tokens, ATTR_GETTER);
+    }
+
     let (maybe_crate_root, errs) = crate_roots.finish();
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let serialize = serialize_requested(serializes, &mut errors);
+
+    let (compare, errs) = compares.finish();
+    errors.extend(errs);
+
+    let (hash, errs) = hashes.finish();
+    errors.extend(errs);
+
+    let (clone, errs) = clones.finish();
+    errors.extend(errs);
+
+    let (debug, errs) = debugs.finish();
+    errors.extend(errs);
+
     errors.finish()?;
 
     Ok(NamedStructInfo {
         crate_root,
         field_container,
         generics,
+        serialize,
+        compare: compare.is_some(),
+        hash: hash.is_some(),
+        send_sync,
+        clone: clone.is_some(),
+        debug: debug.is_some(),
     })
 }
 
@@ -900,6 +2266,14 @@ fn parse_snafu_tuple_struct(
 ) -> MultiSynResult<TupleStructInfo> {
     let mut transformations = AtMostOne::new("source(from)", ErrorLocation::OnTupleStruct);
     let mut crate_roots = AtMostOne::new("crate_root", ErrorLocation::OnTupleStruct);
+    let mut clones = AtMostOne::new("clone", ErrorLocation::OnTupleStruct);
+    let mut debugs = AtMostOne::new("debug", ErrorLocation::OnTupleStruct);
+    let mut serializes = AtMostOne::new("serialize", ErrorLocation::OnTupleStruct);
+    let mut compares = AtMostOne::new("compare", ErrorLocation::OnTupleStruct);
+    let mut hashes = AtMostOne::new("hash", ErrorLocation::OnTupleStruct);
+    let mut send_syncs = AtMostOne::new("send_sync", ErrorLocation::OnTupleStruct);
+    let mut arc_tokens = Vec::new();
+    let mut source_bounds = Vec::new();
 
     let mut errors = SyntaxErrors::default();
     let mut struct_errors = errors.scoped(ErrorLocation::OnTupleStruct);
@@ -907,18 +2281,72 @@ fn parse_snafu_tuple_struct(
     for attr in attributes_from_syn(attrs)? {
         match attr {
             SnafuAttribute::Display(tokens, ..) => struct_errors.add(tokens, ATTR_DISPLAY),
+            SnafuAttribute::DisplayPrefix(tokens, ..) => {
+                struct_errors.add(tokens, ATTR_DISPLAY_PREFIX)
+            }
+            SnafuAttribute::DisplayWithName(tokens) => {
+                struct_errors.add(tokens, ATTR_DISPLAY_WITH_NAME)
+            }
+            SnafuAttribute::VariantName(tokens) => struct_errors.add(tokens, ATTR_VARIANT_NAME),
+            SnafuAttribute::VariantsConst(tokens) => struct_errors.add(tokens, ATTR_VARIANTS_CONST),
+            SnafuAttribute::Doc(tokens, ..) => struct_errors.add(tokens, ATTR_DOC),
             SnafuAttribute::Visibility(tokens, ..) => struct_errors.add(tokens, ATTR_VISIBILITY),
             SnafuAttribute::Source(tokens, ss) => {
                 for s in ss {
                     match s {
                         Source::Flag(..) => struct_errors.add(tokens.clone(), ATTR_SOURCE_BOOL),
                         Source::From(t, e) => transformations.add((t, e), tokens.clone()),
+                        Source::Arc => arc_tokens.push(tokens.clone()),
+                        Source::DisplayOnly => {
+                            struct_errors.add(tokens.clone(), ATTR_SOURCE_DISPLAY_ONLY)
+                        }
+                        Source::Bound(bounds) => source_bounds.extend(bounds),
                     }
                 }
             }
             SnafuAttribute::Backtrace(tokens, ..) => struct_errors.add(tokens, ATTR_BACKTRACE),
+            SnafuAttribute::BacktraceSearchChain(tokens) => {
+                struct_errors.add(tokens, ATTR_BACKTRACE_SEARCH_CHAIN)
+            }
+            SnafuAttribute::Compare(tokens, c) => {
+                if c {
+                    compares.add((), tokens);
+                } else {
+                    struct_errors.add(tokens, ATTR_COMPARE_FALSE);
+                }
+            }
+            SnafuAttribute::Hash(tokens, h) => {
+                if h {
+                    hashes.add((), tokens);
+                } else {
+                    struct_errors.add(tokens, ATTR_HASH_FALSE);
+                }
+            }
+            SnafuAttribute::Location(tokens, ..) => struct_errors.add(tokens, ATTR_LOCATION),
             SnafuAttribute::Context(tokens, ..) => struct_errors.add(tokens, ATTR_CONTEXT),
+            SnafuAttribute::Convert(tokens, ..) => struct_errors.add(tokens, ATTR_CONVERT),
             SnafuAttribute::CrateRoot(tokens, root) => crate_roots.add(root, tokens),
+            SnafuAttribute::Serialize(tokens) => serializes.add((), tokens),
+            SnafuAttribute::Builder(tokens) => struct_errors.add(tokens, ATTR_BUILDER),
+            SnafuAttribute::Default(tokens, ..) => struct_errors.add(tokens, ATTR_DEFAULT),
+            SnafuAttribute::Code(tokens, ..) => struct_errors.add(tokens, ATTR_CODE),
+            SnafuAttribute::Description(tokens, ..) => struct_errors.add(tokens, ATTR_DESCRIPTION),
+            SnafuAttribute::Help(tokens, ..) => struct_errors.add(tokens, ATTR_HELP),
+            SnafuAttribute::Retryable(tokens, ..) => struct_errors.add(tokens, ATTR_RETRYABLE),
+            SnafuAttribute::Deprecated(tokens, ..) => struct_errors.add(tokens, ATTR_DEPRECATED),
+            SnafuAttribute::ExitCode(tokens, ..) => struct_errors.add(tokens, ATTR_EXIT_CODE),
+            SnafuAttribute::FromEnum(tokens, f) => match f {
+                Some(_) => struct_errors.add(tokens, ATTR_FROM_ENUM),
+                None => struct_errors.add(tokens, ATTR_FROM_ENUM_FALSE),
+            },
+            SnafuAttribute::Getter(tokens) => struct_errors.add(tokens, ATTR_GETTER),
+            SnafuAttribute::Kind(tokens) => struct_errors.add(tokens, ATTR_KIND),
+            SnafuAttribute::Module(tokens, ..) => struct_errors.add(tokens, ATTR_MODULE),
+            SnafuAttribute::SendSync(tokens) => send_syncs.add((), tokens),
+            SnafuAttribute::Clone(tokens) => clones.add((), tokens),
+            SnafuAttribute::Debug(tokens) => debugs.add((), tokens),
+            SnafuAttribute::Transparent(tokens) => struct_errors.add(tokens, ATTR_TRANSPARENT),
+            SnafuAttribute::Whatever(tokens) => struct_errors.add(tokens, ATTR_WHATEVER),
             SnafuAttribute::DocComment(..) => { /* Just a regular doc comment. */ }
         }
     }
@@ -938,6 +2366,24 @@ fn parse_snafu_tuple_struct(
         return Err(vec![one_field_error(span)]);
     }
 
+    let (send_sync, errs) = send_syncs.finish();
+    errors.extend(errs);
+    let send_sync = send_sync.map(|()| vec![inner.value().ty.clone()]);
+
+    // The wrapped type is only known once the field has been found.
+    for tokens in arc_tokens {
+        match arc_inner_type(&inner.value().ty) {
+            Some(ty) => {
+                let wrap = syn::parse_quote! { ::std::sync::Arc::new };
+                transformations.add((ty.clone(), wrap), tokens);
+            }
+            None => errors.add(
+                tokens,
+                "`source(arc)` can only be used on a tuple struct wrapping an `Arc`",
+            ),
+        }
+    }
+
     let (maybe_transformation, errs) = transformations.finish();
     let transformation = maybe_transformation
         .map(|(ty, expr)| Transformation::Transform { ty, expr })
@@ -950,6 +2396,20 @@ fn parse_snafu_tuple_struct(
     let crate_root = maybe_crate_root.unwrap_or_else(default_crate_root);
     errors.extend(errs);
 
+    let serialize = serialize_requested(serializes, &mut errors);
+
+    let (compare, errs) = compares.finish();
+    errors.extend(errs);
+
+    let (hash, errs) = hashes.finish();
+    errors.extend(errs);
+
+    let (clone, errs) = clones.finish();
+    errors.extend(errs);
+
+    let (debug, errs) = debugs.finish();
+    errors.extend(errs);
+
     errors.finish()?;
 
     Ok(TupleStructInfo {
@@ -957,12 +2417,31 @@ fn parse_snafu_tuple_struct(
         name,
         generics,
         transformation,
+        serialize,
+        source_bounds,
+        compare: compare.is_some(),
+        hash: hash.is_some(),
+        send_sync,
+        clone: clone.is_some(),
+        debug: debug.is_some(),
     })
 }
 
 enum Source {
     Flag(bool),
     From(syn::Type, syn::Expr),
+    Arc,
+    DisplayOnly,
+    Bound(Vec<syn::WherePredicate>),
+}
+
+enum Context {
+    Flag(bool),
+    Suffix(syn::Ident),
+    Name(syn::Ident),
+    FailName(syn::Ident),
+    Default,
+    Suppress,
 }
 
 /// A SnafuAttribute represents one SNAFU-specific attribute inside of `#[snafu(...)]`.  For
@@ -973,12 +2452,40 @@ enum Source {
 /// with the data.  The location can be used to give accurate error messages in case there was a
 /// problem with the use of the attribute.
 enum SnafuAttribute {
-    Display(proc_macro2::TokenStream, UserInput),
+    Display(proc_macro2::TokenStream, DisplayFormat),
+    DisplayPrefix(proc_macro2::TokenStream, syn::LitStr),
+    DisplayWithName(proc_macro2::TokenStream),
+    VariantName(proc_macro2::TokenStream),
+    VariantsConst(proc_macro2::TokenStream),
+    Doc(proc_macro2::TokenStream, String),
     Visibility(proc_macro2::TokenStream, UserInput),
     Source(proc_macro2::TokenStream, Vec<Source>),
-    Backtrace(proc_macro2::TokenStream, bool),
-    Context(proc_macro2::TokenStream, bool),
+    Backtrace(proc_macro2::TokenStream, bool, Option<syn::Expr>),
+    BacktraceSearchChain(proc_macro2::TokenStream),
+    Compare(proc_macro2::TokenStream, bool),
+    Hash(proc_macro2::TokenStream, bool),
+    Location(proc_macro2::TokenStream, bool),
+    Context(proc_macro2::TokenStream, Context),
+    Convert(proc_macro2::TokenStream, Conversion),
     CrateRoot(proc_macro2::TokenStream, UserInput),
+    Default(proc_macro2::TokenStream, Option<syn::Expr>),
+    Builder(proc_macro2::TokenStream),
+    Clone(proc_macro2::TokenStream),
+    Debug(proc_macro2::TokenStream),
+    Serialize(proc_macro2::TokenStream),
+    Code(proc_macro2::TokenStream, syn::LitStr),
+    Description(proc_macro2::TokenStream, syn::LitStr),
+    Help(proc_macro2::TokenStream, syn::LitStr),
+    Retryable(proc_macro2::TokenStream, bool),
+    Deprecated(proc_macro2::TokenStream, Option<syn::LitStr>),
+    ExitCode(proc_macro2::TokenStream, i32),
+    FromEnum(proc_macro2::TokenStream, Option<syn::Path>),
+    Getter(proc_macro2::TokenStream),
+    Kind(proc_macro2::TokenStream),
+    Module(proc_macro2::TokenStream, Option<syn::Ident>),
+    SendSync(proc_macro2::TokenStream),
+    Transparent(proc_macro2::TokenStream),
+    Whatever(proc_macro2::TokenStream),
     DocComment(proc_macro2::TokenStream, String),
 }
 
@@ -986,6 +2493,47 @@ fn default_crate_root() -> UserInput {
     Box::new(quote! { ::snafu })
 }
 
+/// The generated `Serialize` implementation uses the copy of serde
+/// re-exported by SNAFU, which only exists when its `serde` feature
+/// flag is enabled.
+fn serialize_requested(
+    serializes: AtMostOne<(), proc_macro2::TokenStream>,
+    errors: &mut SyntaxErrors,
+) -> bool {
+    let (serialize, errs) = serializes.finish_with_location();
+    errors.extend(errs);
+
+    match serialize {
+        Some(((), tokens)) if !cfg!(feature = "serde") => {
+            errors.add(tokens, "`serialize` requires the `serde` feature flag");
+            false
+        }
+        Some(_) => true,
+        None => false,
+    }
+}
+
+/// Converts a name such as `ConfigError` to `config_error`.
+fn snake_case_ident(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.to_string();
+    let name = name.trim_start_matches("r#");
+    let mut snake = String::with_capacity(name.len());
+    let mut previous_was_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if previous_was_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            previous_was_lower = false;
+        } else {
+            snake.push(c);
+            previous_was_lower = c.is_lowercase() || c.is_numeric();
+        }
+    }
+    format_ident!("{}", snake, span = ident.span())
+}
+
 fn private_visibility() -> UserInput {
     Box::new(quote! {})
 }
@@ -1055,7 +2603,7 @@ trait GenericAwareNames {
     fn provided_generics_without_defaults(&self) -> Vec<proc_macro2::TokenStream> {
         self.provided_generic_lifetimes()
             .into_iter()
-            .chain(self.provided_generic_types_without_defaults().into_iter())
+            .chain(self.provided_generic_types_without_defaults())
             .collect()
     }
 
@@ -1086,6 +2634,16 @@ trait GenericAwareNames {
             .collect()
     }
 
+    fn provided_type_param_names(&self) -> Vec<proc_macro2::TokenStream> {
+        self.generics()
+            .type_params()
+            .map(|t| {
+                let ident = &t.ident;
+                quote! { #ident }
+            })
+            .collect()
+    }
+
     fn provided_where_clauses(&self) -> Vec<proc_macro2::TokenStream> {
         self.generics()
             .where_clause
@@ -1101,12 +2659,50 @@ impl EnumInfo {
         let display_impl = DisplayImpl(&self);
         let error_impl = ErrorImpl(&self);
         let error_compat_impl = ErrorCompatImpl(&self);
+        let partial_eq_impl = PartialEqImpl(&self);
+        let hash_impl = HashImpl(&self);
+        let clone_impl = CloneImpl(&self);
+        let debug_impl = DebugImpl(&self);
+        let serialize_impl = SerializeImpl(&self);
+        let kind_impl = KindImpl(&self);
+        let code_impl = CodeImpl(&self);
+        let help_impl = HelpImpl(&self);
+        let retryable_impl = RetryableImpl(&self);
+        let variant_name_impl = VariantNameImpl(&self);
+        let variants_const_impl = VariantsConstImpl(&self);
+        let exit_code_impl = ExitCodeImpl(&self);
+        let getter_impl = GetterImpl(&self);
+        let from_enum_impl = FromEnumImpl(&self);
+        let send_sync_impl = self.send_sync.as_ref().map(|field_types| {
+            let send_sync = shared::SendSync {
+                field_types,
+                original_generics: &self.provided_generics_without_defaults(),
+                parameterized_error_name: &self.parameterized_name(),
+                where_clauses: &self.provided_where_clauses(),
+            };
+            quote! { #send_sync }
+        });
 
         quote! {
             #context_selectors
             #display_impl
             #error_impl
             #error_compat_impl
+            #serialize_impl
+            #partial_eq_impl
+            #hash_impl
+            #clone_impl
+            #debug_impl
+            #kind_impl
+            #code_impl
+            #help_impl
+            #retryable_impl
+            #variant_name_impl
+            #variants_const_impl
+            #exit_code_impl
+            #getter_impl
+            #from_enum_impl
+            #send_sync_impl
         }
     }
 }
@@ -1131,13 +2727,28 @@ impl<'a> quote::ToTokens for ContextSelectors<'a> {
             .iter()
             .map(|variant| ContextSelector(self.0, variant));
 
-        stream.extend({
-            quote! {
-                #(#context_selectors)*
-            }
-        })
-    }
-}
+        let context_selectors = quote! {
+            #(#context_selectors)*
+        };
+
+        stream.extend(match &self.0.module {
+            Some(module) => {
+                let visibility = &self.0.visibility;
+                let module_doc = format!("SNAFU context selectors for the `{}` error", self.0.name);
+                quote! {
+                    #[doc = #module_doc]
+                    #visibility mod #module {
+                        #[allow(unused_imports)]
+                        use super::*;
+
+                        #context_selectors
+                    }
+                }
+            }
+            None => context_selectors,
+        })
+    }
+}
 
 struct ContextSelector<'a>(&'a EnumInfo, &'a FieldContainer);
 
@@ -1159,74 +2770,666 @@ impl<'a> quote::ToTokens for ContextSelector<'a> {
             .as_ref()
             .unwrap_or(&self.0.default_visibility);
 
-        let selector_doc_string = format!(
-            "SNAFU context selector for the `{}::{}` variant",
-            enum_name, variant_name,
-        );
+        let constructor_name = if self.1.suppress_selector {
+            Some(snake_case_ident(variant_name))
+        } else {
+            None
+        };
+
+        let selector_doc_string = self.1.selector_doc.clone().unwrap_or_else(|| {
+            if constructor_name.is_some() {
+                format!("Create the `{}::{}` error", enum_name, variant_name)
+            } else {
+                format!(
+                    "SNAFU context selector for the `{}::{}` variant",
+                    enum_name, variant_name,
+                )
+            }
+        });
+
+        let default_suffix = self
+            .0
+            .default_suffix
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let selector_name = self
+            .1
+            .selector_name(&variant_name.to_string(), &default_suffix);
+        let fail_name = self.1.fail_name();
+
+        let context_selector = ContextSelector {
+            backtrace_field: self.1.backtrace_field.as_ref(),
+            backtrace_capture_if: self.1.backtrace_capture_if.as_ref(),
+            location_field: self.1.location_field.as_ref(),
+            default_fields: &self.1.default_fields,
+            crate_root: &self.0.crate_root,
+            error_constructor_name: &quote! { #enum_name::#variant_name },
+            fail_name: &fail_name,
+            builder: self.1.builder,
+            constructor_name: constructor_name.as_ref(),
+            selector_default: self.1.selector_default,
+            deprecated: self.1.deprecated.as_ref().map(Option::as_ref),
+            original_generics_without_defaults: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            selector_doc_string: &selector_doc_string,
+            selector_kind,
+            selector_name: &selector_name,
+            user_fields: selector_kind.user_fields(),
+            visibility: Some(&visibility),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #context_selector });
+    }
+}
+
+struct DisplayImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for DisplayImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Display, DisplayMatchArm};
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let FieldContainer {
+                    backtrace_field,
+                    location_field,
+                    default_fields,
+                    display_format,
+                    doc_comment,
+                    members,
+                    name: variant_name,
+                    selector_kind,
+                    is_transparent,
+                    ..
+                } = variant;
+
+                let arm = DisplayMatchArm {
+                    crate_root: &self.0.crate_root,
+                    backtrace_field: backtrace_field.as_ref(),
+                    location_field: location_field.as_ref(),
+                    default_fields,
+                    default_name: &variant_name,
+                    display_prefix: self.0.display_prefix.as_ref(),
+                    display_with_name: self.0.display_with_name,
+                    display_format: display_format.as_ref(),
+                    members,
+                    is_transparent: *is_transparent,
+                    doc_comment,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                    selector_kind,
+                };
+
+                quote! { #arm }
+            })
+            .collect();
+
+        let display = Display {
+            arms: &arms,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let display_impl = quote! { #display };
+
+        stream.extend(display_impl)
+    }
+}
+
+struct PartialEqImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for PartialEqImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{PartialEq, PartialEqMatchArm};
+
+        if !self.0.compare {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let arm = PartialEqMatchArm {
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let partial_eq = PartialEq {
+            arms: &arms,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            type_params: &self.0.provided_type_param_names(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #partial_eq })
+    }
+}
+
+struct HashImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for HashImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Hash, HashMatchArm};
+
+        if !self.0.hash {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let variant_name = &variant.name;
+                let arm = HashMatchArm {
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                    discriminant: Some(index),
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let hash = Hash {
+            arms: &arms,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            type_params: &self.0.provided_type_param_names(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #hash })
+    }
+}
+
+struct DebugImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for DebugImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Debug, DebugMatchArm};
+
+        if !self.0.debug {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let arm = DebugMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let debug = Debug {
+            arms: &arms,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            type_params: &self.0.provided_type_param_names(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #debug })
+    }
+}
+
+struct CloneImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CloneImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Clone, CloneMatchArm};
+
+        if !self.0.clone {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let arm = CloneMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let clone = Clone {
+            arms: &arms,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            type_params: &self.0.provided_type_param_names(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #clone })
+    }
+}
+
+struct KindImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for KindImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.kind {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let kind_name = format_ident!("{}Kind", enum_name);
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let kind_doc = format!("The kind of a [`{}`], without any of its data", enum_name);
+
+        let variant_names: Vec<_> = self.0.variants.iter().map(|v| &v.name).collect();
+        let variant_docs = variant_names
+            .iter()
+            .map(|name| format!("The kind of [`{}::{}`]", enum_name, name));
+
+        stream.extend(quote! {
+            #[doc = #kind_doc]
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+            #visibility enum #kind_name {
+                #(
+                    #[doc = #variant_docs]
+                    #variant_names,
+                )*
+            }
+
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "The kind of this error"]
+                #visibility fn kind(&self) -> #kind_name {
+                    match *self {
+                        #(
+                            #enum_name::#variant_names { .. } => #kind_name::#variant_names,
+                        )*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct CodeImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CodeImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if self.0.variants.iter().all(|v| v.code.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let code = match &variant.code {
+                Some(code) => quote! { ::core::option::Option::Some(#code) },
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! { #enum_name::#variant_name { .. } => #code, }
+        });
+
+        stream.extend(quote! {
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "The error code of this error, if it has one"]
+                #visibility fn code(&self) -> ::core::option::Option<&'static str> {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct HelpImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for HelpImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if self.0.variants.iter().all(|v| v.help.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let help = match &variant.help {
+                Some(help) => quote! { ::core::option::Option::Some(#help) },
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! { #enum_name::#variant_name { .. } => #help, }
+        });
+
+        stream.extend(quote! {
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "Advice on how to resolve this error, if it has any"]
+                #visibility fn help(&self) -> ::core::option::Option<&'static str> {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct RetryableImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for RetryableImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if self.0.variants.iter().all(|v| v.retryable.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            let retryable = variant.retryable.unwrap_or(false);
+            quote! { #enum_name::#variant_name { .. } => #retryable, }
+        });
+
+        stream.extend(quote! {
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "Whether the operation that caused this error may succeed if it is retried"]
+                #visibility fn is_retryable(&self) -> bool {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct VariantNameImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for VariantNameImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.variant_name {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            quote! { #enum_name::#variant_name { .. } => stringify!(#variant_name), }
+        });
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "The name of this error's variant"]
+                #[allow(dead_code)]
+                #visibility fn variant_name(&self) -> &'static str {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct VariantsConstImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for VariantsConstImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if !self.0.variants_const {
+            return;
+        }
+
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let variant_names = self.0.variants.iter().map(|variant| &variant.name);
+
+        stream.extend(quote! {
+            #[allow(single_use_lifetimes)]
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "The names of every variant of this error, in declaration order"]
+                #visibility const VARIANTS: &'static [&'static str] = &[#(stringify!(#variant_names)),*];
+            }
+        })
+    }
+}
+
+struct ExitCodeImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for ExitCodeImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        if self.0.variants.iter().all(|v| v.exit_code.is_none()) {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let arms = self.0.variants.iter().map(|variant| {
+            let variant_name = &variant.name;
+            // Matches `EXIT_FAILURE` on common platforms
+            let exit_code = variant.exit_code.unwrap_or(1);
+            quote! { #enum_name::#variant_name { .. } => #exit_code, }
+        });
+
+        stream.extend(quote! {
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #[doc = "The status a process should exit with because of this error"]
+                #visibility fn exit_code(&self) -> i32 {
+                    match *self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct GetterImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for GetterImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        // Each getter, in the order it first appears, with the
+        // variants that have the field.
+        let mut getters: Vec<(&Field, Vec<&syn::Ident>)> = Vec::new();
+        for variant in &self.0.variants {
+            for (field, _) in &variant.getters {
+                match getters.iter_mut().find(|(f, _)| f.name == field.name) {
+                    Some((_, variants)) => variants.push(&variant.name),
+                    None => getters.push((field, vec![&variant.name])),
+                }
+            }
+        }
+
+        if getters.is_empty() {
+            return;
+        }
 
-        let context_selector = ContextSelector {
-            backtrace_field: self.1.backtrace_field.as_ref(),
-            crate_root: &self.0.crate_root,
-            error_constructor_name: &quote! { #enum_name::#variant_name },
-            original_generics_without_defaults: &self.0.provided_generics_without_defaults(),
-            parameterized_error_name: &self.0.parameterized_name(),
-            selector_doc_string: &selector_doc_string,
-            selector_kind: &selector_kind,
-            selector_name: variant_name,
-            user_fields: &selector_kind.user_fields(),
-            visibility: Some(&visibility),
-            where_clauses: &self.0.provided_where_clauses(),
-        };
+        let enum_name = &self.0.name;
+        let visibility = &self.0.visibility;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        let methods = getters.iter().map(|(field, variant_names)| {
+            let name = &field.name;
+            let ty = &field.ty;
+            let doc = format!(
+                "The `{}` field, if this variant has one",
+                name.to_string().trim_start_matches("r#")
+            );
 
-        stream.extend(quote! { #context_selector });
+            quote! {
+                #[doc = #doc]
+                #[allow(unreachable_patterns)]
+                #visibility fn #name(&self) -> ::core::option::Option<&#ty> {
+                    match *self {
+                        #(
+                            #enum_name::#variant_names { ref #name, .. } => {
+                                ::core::option::Option::Some(#name)
+                            }
+                        )*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        });
+
+        stream.extend(quote! {
+            impl<#(#original_generics),*> #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                #(#methods)*
+            }
+        })
     }
 }
 
-struct DisplayImpl<'a>(&'a EnumInfo);
+struct FromEnumImpl<'a>(&'a EnumInfo);
 
-impl<'a> quote::ToTokens for DisplayImpl<'a> {
+impl<'a> quote::ToTokens for FromEnumImpl<'a> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{Display, DisplayMatchArm};
+        use quote::quote_spanned;
+        use syn::spanned::Spanned;
 
-        let enum_name = &self.0.name;
+        let from_path = match &self.0.from_enum {
+            Some(from_path) => from_path,
+            None => return,
+        };
 
-        let arms: Vec<_> = self
+        // Generic arguments aren't allowed in a pattern without a
+        // turbofish, so leave them for the compiler to infer.
+        let mut pattern_path = from_path.clone();
+        for segment in &mut pattern_path.segments {
+            segment.arguments = syn::PathArguments::None;
+        }
+
+        let enum_name = &self.0.name;
+        let original_generics = self.0.provided_generics_without_defaults();
+        let parameterized_error_name = self.0.parameterized_name();
+        let where_clauses = self.0.provided_where_clauses();
+
+        // Every field is listed without `..`, so a variant with a
+        // different shape, or one that has no counterpart, fails to
+        // compile.
+        let arms = self
             .0
             .variants
             .iter()
+            .filter(|variant| !variant.skip_from_enum)
             .map(|variant| {
-                let FieldContainer {
-                    backtrace_field,
-                    display_format,
-                    doc_comment,
-                    name: variant_name,
-                    selector_kind,
-                    ..
-                } = variant;
-
-                let arm = DisplayMatchArm {
-                    backtrace_field: backtrace_field.as_ref(),
-                    default_name: &variant_name,
-                    display_format: display_format.as_ref().map(|f| &**f),
-                    doc_comment,
-                    pattern_ident: &quote! { #enum_name::#variant_name },
-                    selector_kind,
-                };
+                let variant_name = &variant.name;
+                let members = &variant.members;
+                let names: Vec<_> = (0..members.len())
+                    .map(|i| format_ident!("__snafu_{}", i))
+                    .collect();
 
-                quote! { #arm }
-            })
-            .collect();
+                quote! {
+                    #pattern_path::#variant_name { #(#members: #names),* } => {
+                        #enum_name::#variant_name {
+                            #(#members: ::core::convert::Into::into(#names)),*
+                        }
+                    }
+                }
+            });
 
-        let display = Display {
-            arms: &arms,
-            original_generics: &self.0.provided_generics_without_defaults(),
-            parameterized_error_name: &self.0.parameterized_name(),
-            where_clauses: &self.0.provided_where_clauses(),
+        // Point at the attribute when a variant isn't covered.
+        let body = quote_spanned! { from_path.span()=>
+            match other {
+                #(#arms)*
+            }
         };
 
-        let display_impl = quote! { #display };
-
-        stream.extend(display_impl)
+        stream.extend(quote! {
+            impl<#(#original_generics),*> ::core::convert::From<#from_path> for #parameterized_error_name
+            where
+                #(#where_clauses),*
+            {
+                fn from(other: #from_path) -> Self {
+                    #body
+                }
+            }
+        })
     }
 }
 
@@ -1245,8 +3448,12 @@ impl<'a> quote::ToTokens for ErrorImpl<'a> {
                 let variant_name = &field_container.name;
                 let pattern_ident = &quote! { #enum_name::#variant_name };
 
+                let description = match &field_container.description {
+                    Some(description) => quote! { #description },
+                    None => quote! { stringify!(#pattern_ident) },
+                };
                 let error_description_match_arm = quote! {
-                    #pattern_ident { .. } => stringify!(#pattern_ident),
+                    #pattern_ident { .. } => #description,
                 };
 
                 let error_source_match_arm = ErrorSourceMatchArm {
@@ -1277,9 +3484,13 @@ struct ErrorCompatImpl<'a>(&'a EnumInfo);
 
 impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatLocationMatchArm,
+            ErrorCompatStripBacktraceMatchArm,
+        };
 
-        let variants_to_backtrace: Vec<_> = self
+        let mut variants_to_strip_backtrace = Vec::new();
+        let (variants_to_backtrace, variants_to_location): (Vec<_>, Vec<_>) = self
             .0
             .variants
             .iter()
@@ -1287,21 +3498,39 @@ impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
                 let crate_root = &self.0.crate_root;
                 let enum_name = &self.0.name;
                 let variant_name = &field_container.name;
+                let pattern_ident = &quote! { #enum_name::#variant_name };
 
-                let match_arm = ErrorCompatBacktraceMatchArm {
+                let backtrace_match_arm = ErrorCompatBacktraceMatchArm {
                     field_container,
                     crate_root,
-                    pattern_ident: &quote! { #enum_name::#variant_name },
+                    pattern_ident,
                 };
 
-                quote! { #match_arm }
+                let location_match_arm = ErrorCompatLocationMatchArm {
+                    field_container,
+                    pattern_ident,
+                };
+
+                let strip_backtrace_match_arm = ErrorCompatStripBacktraceMatchArm {
+                    field_container,
+                    crate_root,
+                    pattern_ident,
+                };
+                variants_to_strip_backtrace.push(quote! { #strip_backtrace_match_arm });
+
+                (
+                    quote! { #backtrace_match_arm },
+                    quote! { #location_match_arm },
+                )
             })
-            .collect();
+            .unzip();
 
         let error_compat_impl = ErrorCompat {
             crate_root: &self.0.crate_root,
             parameterized_error_name: &self.0.parameterized_name(),
             backtrace_arms: &variants_to_backtrace,
+            location_arms: &variants_to_location,
+            strip_backtrace_arms: &variants_to_strip_backtrace,
             original_generics: &self.0.provided_generics_without_defaults(),
             where_clauses: &self.0.provided_where_clauses(),
         };
@@ -1312,15 +3541,52 @@ impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
     }
 }
 
+struct SerializeImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for SerializeImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Serialize, SerializeMatchArm};
+
+        if !self.0.serialize {
+            return;
+        }
+
+        let enum_name = &self.0.name;
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let arm = SerializeMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container: variant,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                    type_name: enum_name,
+                };
+                quote! { #arm }
+            })
+            .collect();
+
+        let serialize = Serialize {
+            arms: &arms,
+            crate_root: &self.0.crate_root,
+            original_generics: &self.0.provided_generics_without_defaults(),
+            parameterized_error_name: &self.0.parameterized_name(),
+            type_params: &self.0.provided_type_param_names(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        stream.extend(quote! { #serialize })
+    }
+}
+
 impl NamedStructInfo {
     fn selector_name(&self) -> syn::Ident {
         let selector_name = self.field_container.name.to_string();
         let selector_name = selector_name.trim_end_matches("Error");
-        format_ident!(
-            "{}Context",
-            selector_name,
-            span = self.field_container.name.span()
-        )
+        self.field_container.selector_name(selector_name, "Context")
     }
 
     fn generate_snafu(self) -> proc_macro2::TokenStream {
@@ -1336,9 +3602,13 @@ impl NamedStructInfo {
                     name,
                     selector_kind,
                     backtrace_field,
+                    location_field,
+                    default_fields,
                     display_format,
                     doc_comment,
+                    members,
                     visibility,
+                    ..
                 },
             ..
         } = &self;
@@ -1350,12 +3620,16 @@ impl NamedStructInfo {
 
         let pattern_ident = &quote! { Self };
 
+        let description = match &field_container.description {
+            Some(description) => quote! { #description },
+            None => quote! { stringify!(#name) },
+        };
         let error_description_match_arm = quote! {
-            #pattern_ident { .. } => stringify!(#name),
+            #pattern_ident { .. } => #description,
         };
 
         let error_source_match_arm = ErrorSourceMatchArm {
-            field_container: &field_container,
+            field_container,
             pattern_ident,
         };
         let error_source_match_arm = quote! { #error_source_match_arm };
@@ -1370,19 +3644,37 @@ impl NamedStructInfo {
         };
         let error_impl = quote! { #error_impl };
 
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatLocationMatchArm,
+            ErrorCompatStripBacktraceMatchArm,
+        };
+
+        let backtrace_match_arm = ErrorCompatBacktraceMatchArm {
+            field_container,
+            crate_root: &crate_root,
+            pattern_ident: &quote! { Self },
+        };
+        let backtrace_match_arm = quote! { #backtrace_match_arm };
+
+        let location_match_arm = ErrorCompatLocationMatchArm {
+            field_container,
+            pattern_ident: &quote! { Self },
+        };
+        let location_match_arm = quote! { #location_match_arm };
 
-        let match_arm = ErrorCompatBacktraceMatchArm {
+        let strip_backtrace_match_arm = ErrorCompatStripBacktraceMatchArm {
             field_container,
             crate_root: &crate_root,
             pattern_ident: &quote! { Self },
         };
-        let match_arm = quote! { #match_arm };
+        let strip_backtrace_match_arm = quote! { #strip_backtrace_match_arm };
 
         let error_compat_impl = ErrorCompat {
             crate_root: &crate_root,
             parameterized_error_name: &parameterized_struct_name,
-            backtrace_arms: &[match_arm],
+            backtrace_arms: &[backtrace_match_arm],
+            location_arms: &[location_match_arm],
+            strip_backtrace_arms: &[strip_backtrace_match_arm],
             original_generics: &original_generics,
             where_clauses: &where_clauses,
         };
@@ -1390,12 +3682,19 @@ impl NamedStructInfo {
         use crate::shared::{Display, DisplayMatchArm};
 
         let arm = DisplayMatchArm {
+            crate_root: &crate_root,
             backtrace_field: backtrace_field.as_ref(),
+            location_field: location_field.as_ref(),
+            default_fields,
             default_name: &name,
-            display_format: display_format.as_ref().map(|f| &**f),
-            doc_comment: &doc_comment,
+            display_prefix: None,
+            display_with_name: false,
+            display_format: display_format.as_ref(),
+            members,
+            is_transparent: field_container.is_transparent,
+            doc_comment,
             pattern_ident: &quote! { Self },
-            selector_kind: &selector_kind,
+            selector_kind,
         };
         let arm = quote! { #arm };
 
@@ -1408,27 +3707,165 @@ impl NamedStructInfo {
 
         use crate::shared::ContextSelector;
 
-        let selector_doc_string = format!("SNAFU context selector for the `{}` error", name);
+        let selector_doc_string = field_container
+            .selector_doc
+            .clone()
+            .unwrap_or_else(|| format!("SNAFU context selector for the `{}` error", name));
 
         let context_selector = ContextSelector {
             backtrace_field: backtrace_field.as_ref(),
+            backtrace_capture_if: field_container.backtrace_capture_if.as_ref(),
+            location_field: location_field.as_ref(),
+            default_fields,
             crate_root: &crate_root,
             error_constructor_name: &name,
+            fail_name: &field_container.fail_name(),
+            builder: field_container.builder,
+            constructor_name: None,
+            selector_default: field_container.selector_default,
+            deprecated: field_container.deprecated.as_ref().map(Option::as_ref),
             original_generics_without_defaults: &original_generics,
             parameterized_error_name: &parameterized_struct_name,
             selector_doc_string: &selector_doc_string,
-            selector_kind: &selector_kind,
+            selector_kind,
             selector_name: &selector_name,
-            user_fields: &user_fields,
+            user_fields,
             visibility: visibility.as_ref().map(|x| &**x),
             where_clauses: &where_clauses,
         };
 
+        let partial_eq_impl = if self.compare {
+            use crate::shared::{PartialEq, PartialEqMatchArm};
+
+            let arm = PartialEqMatchArm {
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let partial_eq = PartialEq {
+                arms: &[arm],
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                type_params: &self.provided_type_param_names(),
+                where_clauses: &where_clauses,
+            };
+            quote! { #partial_eq }
+        } else {
+            quote! {}
+        };
+
+        let hash_impl = if self.hash {
+            use crate::shared::{Hash, HashMatchArm};
+
+            let arm = HashMatchArm {
+                field_container,
+                pattern_ident: &quote! { Self },
+                discriminant: None,
+            };
+            let arm = quote! { #arm };
+
+            let hash = Hash {
+                arms: &[arm],
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                type_params: &self.provided_type_param_names(),
+                where_clauses: &where_clauses,
+            };
+            quote! { #hash }
+        } else {
+            quote! {}
+        };
+
+        let clone_impl = if self.clone {
+            use crate::shared::{Clone, CloneMatchArm};
+
+            let arm = CloneMatchArm {
+                crate_root: &crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let clone = Clone {
+                arms: &[arm],
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                type_params: &self.provided_type_param_names(),
+                where_clauses: &where_clauses,
+            };
+            quote! { #clone }
+        } else {
+            quote! {}
+        };
+
+        let debug_impl = if self.debug {
+            use crate::shared::{Debug, DebugMatchArm};
+
+            let arm = DebugMatchArm {
+                crate_root: &crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let debug = Debug {
+                arms: &[arm],
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                type_params: &self.provided_type_param_names(),
+                where_clauses: &where_clauses,
+            };
+            quote! { #debug }
+        } else {
+            quote! {}
+        };
+
+        let serialize_impl = if self.serialize {
+            use crate::shared::{Serialize, SerializeMatchArm};
+
+            let arm = SerializeMatchArm {
+                crate_root: &crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+                type_name: &field_container.name,
+            };
+            let arm = quote! { #arm };
+
+            let serialize = Serialize {
+                arms: &[arm],
+                crate_root: &crate_root,
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                type_params: &self.provided_type_param_names(),
+                where_clauses: &where_clauses,
+            };
+            quote! { #serialize }
+        } else {
+            quote! {}
+        };
+
+        let send_sync_impl = self.send_sync.as_ref().map(|field_types| {
+            let send_sync = shared::SendSync {
+                field_types,
+                original_generics: &original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+            quote! { #send_sync }
+        });
+
         quote! {
             #error_impl
             #error_compat_impl
             #display_impl
             #context_selector
+            #serialize_impl
+            #partial_eq_impl
+            #hash_impl
+            #clone_impl
+            #debug_impl
+            #send_sync_impl
         }
     }
 }
@@ -1447,11 +3884,30 @@ impl TupleStructInfo {
     fn generate_snafu(self) -> proc_macro2::TokenStream {
         let parameterized_struct_name = self.parameterized_name();
 
+        let type_params = self.provided_type_param_names();
+
+        let send_sync_impl = self.send_sync.as_ref().map(|field_types| {
+            let send_sync = shared::SendSync {
+                field_types,
+                original_generics: &self.provided_generics_without_defaults(),
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &self.provided_where_clauses(),
+            };
+            quote! { #send_sync }
+        });
+
         let TupleStructInfo {
             crate_root,
             generics,
             name,
             transformation,
+            source_bounds,
+            compare,
+            hash,
+            clone,
+            debug,
+            serialize,
+            ..
         } = self;
 
         let inner_type = transformation.ty();
@@ -1487,6 +3943,18 @@ impl TupleStructInfo {
             }
         };
 
+        let location_fn = quote! {
+            fn location(&self) -> ::core::option::Option<&#crate_root::Location> {
+                #crate_root::ErrorCompat::location(&self.0)
+            }
+        };
+
+        let strip_backtrace_fn = quote! {
+            fn strip_backtrace(&mut self) {
+                #crate_root::ErrorCompat::strip_backtrace(&mut self.0)
+            }
+        };
+
         let std_backtrace_fn = if cfg!(feature = "unstable-backtraces-impl-std") {
             quote! {
                 fn backtrace(&self) -> ::core::option::Option<&std::backtrace::Backtrace> {
@@ -1517,6 +3985,8 @@ impl TupleStructInfo {
                 #(#where_clauses),*
             {
                 #backtrace_fn
+                #location_fn
+                #strip_backtrace_fn
             }
         };
 
@@ -1535,19 +4005,124 @@ impl TupleStructInfo {
         let from_impl = quote! {
             impl#generics ::core::convert::From<#inner_type> for #parameterized_struct_name
             where
-                #(#where_clauses),*
+                #(#where_clauses,)*
+                #(#source_bounds,)*
             {
+                #[inline]
                 fn from(other: #inner_type) -> Self {
                     #name((#transformation)(other))
                 }
             }
         };
 
+        let partial_eq_impl = if compare {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl#generics ::core::cmp::PartialEq for #parameterized_struct_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::cmp::PartialEq,)*
+                {
+                    fn eq(&self, other: &Self) -> bool {
+                        self.0 == other.0
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let hash_impl = if hash {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl#generics ::core::hash::Hash for #parameterized_struct_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::hash::Hash,)*
+                {
+                    fn hash<__SnafuHasher>(&self, state: &mut __SnafuHasher)
+                    where
+                        __SnafuHasher: ::core::hash::Hasher,
+                    {
+                        ::core::hash::Hash::hash(&self.0, state)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let clone_impl = if clone {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl#generics ::core::clone::Clone for #parameterized_struct_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::clone::Clone,)*
+                {
+                    fn clone(&self) -> Self {
+                        #name(::core::clone::Clone::clone(&self.0))
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let debug_impl = if debug {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl#generics ::core::fmt::Debug for #parameterized_struct_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::fmt::Debug,)*
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        f.debug_tuple(stringify!(#name)).field(&self.0).finish()
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let serialize_impl = if serialize {
+            quote! {
+                #[allow(single_use_lifetimes)]
+                impl#generics #crate_root::serde::Serialize for #parameterized_struct_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: #crate_root::serde::Serialize,)*
+                {
+                    fn serialize<__SnafuSerializer>(
+                        &self,
+                        serializer: __SnafuSerializer,
+                    ) -> ::core::result::Result<
+                        <__SnafuSerializer as #crate_root::serde::Serializer>::Ok,
+                        <__SnafuSerializer as #crate_root::serde::Serializer>::Error,
+                    >
+                    where
+                        __SnafuSerializer: #crate_root::serde::Serializer,
+                    {
+                        #crate_root::serde::Serialize::serialize(&self.0, serializer)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         quote! {
             #error_impl
             #error_compat_impl
             #display_impl
             #from_impl
+            #serialize_impl
+            #partial_eq_impl
+            #hash_impl
+            #clone_impl
+            #debug_impl
+            #send_sync_impl
         }
     }
 }
@@ -1562,20 +4137,6 @@ impl GenericAwareNames for TupleStructInfo {
     }
 }
 
-trait Transpose<T, E> {
-    fn my_transpose(self) -> Result<Option<T>, E>;
-}
-
-impl<T, E> Transpose<T, E> for Option<Result<T, E>> {
-    fn my_transpose(self) -> Result<Option<T>, E> {
-        match self {
-            Some(Ok(v)) => Ok(Some(v)),
-            Some(Err(e)) => Err(e),
-            None => Ok(None),
-        }
-    }
-}
-
 mod sponge {
     use std::iter::FromIterator;
 
@@ -1645,3 +4206,83 @@ mod sponge {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: &str) -> String {
+        let input = syn::parse_str(input).expect("Could not parse the test input");
+        let info = parse_snafu_information(input).expect("Could not derive Snafu");
+
+        let tokens = match info {
+            SnafuInfo::Enum(e) => e.generate_snafu(),
+            SnafuInfo::NamedStruct(s) => s.generate_snafu(),
+            SnafuInfo::TupleStruct(s) => s.generate_snafu(),
+        };
+        tokens.to_string()
+    }
+
+    // Checks the attributes between each definition of the named
+    // method and the end of the previous item.
+    fn assert_inlined(expansion: &str, method: &str) {
+        let definition = format!("fn {} ", method);
+        let mut found = false;
+
+        for (start, _) in expansion.match_indices(&definition) {
+            let before = &expansion[..start];
+            let item_start = before.rfind(|c| c == '{' || c == '}' || c == ';');
+            let attributes = &before[item_start.map_or(0, |i| i + 1)..];
+            assert!(
+                attributes.contains("# [inline]"),
+                "`{}` is not marked #[inline] in {}",
+                method,
+                expansion,
+            );
+            found = true;
+        }
+
+        assert!(found, "`{}` was not generated in {}", method, expansion);
+    }
+
+    #[test]
+    fn enum_conversion_methods_are_inlined() {
+        let expansion = expand(
+            r#"
+            enum Error {
+                Leaf { id: u32 },
+                Unit,
+                WithSource { source: InnerError, location: snafu::Location },
+                #[snafu(context(false))]
+                Converted { source: OtherError },
+            }
+            "#,
+        );
+
+        for method in &[
+            "build",
+            "fail",
+            "into_error",
+            "into_error_with_location",
+            "from",
+        ] {
+            assert_inlined(&expansion, method);
+        }
+    }
+
+    #[test]
+    fn named_struct_conversion_methods_are_inlined() {
+        let expansion = expand("struct Error { user_id: u32 }");
+        assert_inlined(&expansion, "build");
+        assert_inlined(&expansion, "fail");
+
+        let expansion = expand("struct Error { source: InnerError }");
+        assert_inlined(&expansion, "into_error");
+    }
+
+    #[test]
+    fn tuple_struct_conversions_are_inlined() {
+        let expansion = expand("struct Error(InnerError);");
+        assert_inlined(&expansion, "from");
+    }
+}
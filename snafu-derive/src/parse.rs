@@ -5,21 +5,59 @@ use syn::{
     parenthesized,
     parse::{Parse, ParseBuffer, ParseStream, Result},
     punctuated::Punctuated,
-    token, Expr, LitBool, LitStr, Path, Type,
+    token, Expr, Ident, LitBool, LitInt, LitStr, Path, Type, WherePredicate,
 };
 
 mod kw {
     use syn::custom_keyword;
 
     custom_keyword!(backtrace);
+    custom_keyword!(builder);
+    custom_keyword!(clone);
+    custom_keyword!(code);
+    custom_keyword!(compare);
     custom_keyword!(context);
+    custom_keyword!(convert);
     custom_keyword!(crate_root);
+    custom_keyword!(debug);
+    custom_keyword!(default);
+    custom_keyword!(deprecated);
+    custom_keyword!(description);
     custom_keyword!(display);
+    custom_keyword!(display_prefix);
+    custom_keyword!(display_with_name);
+    custom_keyword!(doc);
+    custom_keyword!(exit_code);
+    custom_keyword!(from_enum);
+    custom_keyword!(getter);
+    custom_keyword!(hash);
+    custom_keyword!(help);
+    custom_keyword!(kind);
+    custom_keyword!(location);
+    custom_keyword!(module);
+    custom_keyword!(retryable);
+    custom_keyword!(send_sync);
+    custom_keyword!(serialize);
     custom_keyword!(source);
+    custom_keyword!(transparent);
+    custom_keyword!(variant_name);
+    custom_keyword!(variants_const);
     custom_keyword!(visibility);
+    custom_keyword!(whatever);
 
+    custom_keyword!(arc);
+    custom_keyword!(bound);
+    custom_keyword!(capture_if);
     custom_keyword!(delegate); // deprecated
+    custom_keyword!(display_only);
+    custom_keyword!(fail_name);
     custom_keyword!(from);
+    custom_keyword!(into);
+    custom_keyword!(name);
+    custom_keyword!(search_chain);
+    custom_keyword!(suffix);
+    custom_keyword!(suppress);
+    custom_keyword!(to_owned);
 }
 
 pub(crate) fn attributes_from_syn(
@@ -57,11 +95,38 @@ pub(crate) fn attributes_from_syn(
 
 enum Attribute {
     Backtrace(Backtrace),
+    Builder(Builder),
+    Clone(Clone),
+    Code(Code),
+    Compare(Compare),
     Context(Context),
+    Convert(Convert),
     CrateRoot(CrateRoot),
+    Debug(Debug),
+    Default(Default),
+    Deprecated(Deprecated),
+    Description(Description),
     Display(Display),
+    DisplayPrefix(DisplayPrefix),
+    DisplayWithName(DisplayWithName),
+    Doc(Doc),
+    ExitCode(ExitCode),
+    FromEnum(FromEnum),
+    Getter(Getter),
+    Hash(Hash),
+    Help(Help),
+    Kind(Kind),
+    Location(Location),
+    Module(Module),
+    Retryable(Retryable),
+    SendSync(SendSync),
+    Serialize(Serialize),
     Source(Source),
+    Transparent(Transparent),
+    VariantName(VariantName),
+    VariantsConst(VariantsConst),
     Visibility(Visibility),
+    Whatever(Whatever),
 }
 
 impl From<Attribute> for SnafuAttribute {
@@ -69,202 +134,1395 @@ impl From<Attribute> for SnafuAttribute {
         use self::Attribute::*;
 
         match other {
-            Backtrace(b) => SnafuAttribute::Backtrace(b.to_token_stream(), b.into_bool()),
-            Context(c) => SnafuAttribute::Context(c.to_token_stream(), c.into_bool()),
+            Backtrace(b) => {
+                let tokens = b.to_token_stream();
+                match b.into_components() {
+                    Some((enabled, capture_if)) => {
+                        SnafuAttribute::Backtrace(tokens, enabled, capture_if)
+                    }
+                    None => SnafuAttribute::BacktraceSearchChain(tokens),
+                }
+            }
+            Builder(b) => SnafuAttribute::Builder(b.to_token_stream()),
+            Clone(c) => SnafuAttribute::Clone(c.to_token_stream()),
+            Code(c) => SnafuAttribute::Code(c.to_token_stream(), c.into_value()),
+            Compare(c) => SnafuAttribute::Compare(c.to_token_stream(), c.into_bool()),
+            Context(c) => SnafuAttribute::Context(c.to_token_stream(), c.into_value()),
+            Convert(c) => SnafuAttribute::Convert(c.to_token_stream(), c.into_value()),
             CrateRoot(cr) => SnafuAttribute::CrateRoot(cr.to_token_stream(), cr.into_arbitrary()),
-            Display(d) => SnafuAttribute::Display(d.to_token_stream(), d.into_arbitrary()),
+            Debug(d) => SnafuAttribute::Debug(d.to_token_stream()),
+            Default(d) => SnafuAttribute::Default(d.to_token_stream(), d.into_option()),
+            Deprecated(d) => SnafuAttribute::Deprecated(d.to_token_stream(), d.into_option()),
+            Description(d) => SnafuAttribute::Description(d.to_token_stream(), d.into_value()),
+            Display(d) => SnafuAttribute::Display(d.to_token_stream(), d.into_value()),
+            DisplayPrefix(d) => SnafuAttribute::DisplayPrefix(d.to_token_stream(), d.into_value()),
+            DisplayWithName(d) => SnafuAttribute::DisplayWithName(d.to_token_stream()),
+            Doc(d) => SnafuAttribute::Doc(d.to_token_stream(), d.into_value()),
+            ExitCode(e) => SnafuAttribute::ExitCode(e.to_token_stream(), e.into_value()),
+            FromEnum(f) => SnafuAttribute::FromEnum(f.to_token_stream(), f.into_option()),
+            Getter(g) => SnafuAttribute::Getter(g.to_token_stream()),
+            Hash(h) => SnafuAttribute::Hash(h.to_token_stream(), h.into_bool()),
+            Help(h) => SnafuAttribute::Help(h.to_token_stream(), h.into_value()),
+            Kind(k) => SnafuAttribute::Kind(k.to_token_stream()),
+            Location(l) => SnafuAttribute::Location(l.to_token_stream(), l.into_bool()),
+            Module(m) => SnafuAttribute::Module(m.to_token_stream(), m.into_option()),
+            Retryable(r) => SnafuAttribute::Retryable(r.to_token_stream(), r.into_bool()),
+            SendSync(s) => SnafuAttribute::SendSync(s.to_token_stream()),
+            Serialize(s) => SnafuAttribute::Serialize(s.to_token_stream()),
             Source(s) => SnafuAttribute::Source(s.to_token_stream(), s.into_components()),
+            Transparent(t) => SnafuAttribute::Transparent(t.to_token_stream()),
+            VariantName(v) => SnafuAttribute::VariantName(v.to_token_stream()),
+            VariantsConst(v) => SnafuAttribute::VariantsConst(v.to_token_stream()),
             Visibility(v) => SnafuAttribute::Visibility(v.to_token_stream(), v.into_arbitrary()),
+            Whatever(o) => SnafuAttribute::Whatever(o.to_token_stream()),
+        }
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::backtrace) {
+            input.parse().map(Attribute::Backtrace)
+        } else if lookahead.peek(kw::builder) {
+            input.parse().map(Attribute::Builder)
+        } else if lookahead.peek(kw::clone) {
+            input.parse().map(Attribute::Clone)
+        } else if lookahead.peek(kw::code) {
+            input.parse().map(Attribute::Code)
+        } else if lookahead.peek(kw::compare) {
+            input.parse().map(Attribute::Compare)
+        } else if lookahead.peek(kw::context) {
+            input.parse().map(Attribute::Context)
+        } else if lookahead.peek(kw::convert) {
+            input.parse().map(Attribute::Convert)
+        } else if lookahead.peek(kw::crate_root) {
+            input.parse().map(Attribute::CrateRoot)
+        } else if lookahead.peek(kw::debug) {
+            input.parse().map(Attribute::Debug)
+        } else if lookahead.peek(kw::default) {
+            input.parse().map(Attribute::Default)
+        } else if lookahead.peek(kw::deprecated) {
+            input.parse().map(Attribute::Deprecated)
+        } else if lookahead.peek(kw::description) {
+            input.parse().map(Attribute::Description)
+        } else if lookahead.peek(kw::display) {
+            input.parse().map(Attribute::Display)
+        } else if lookahead.peek(kw::display_prefix) {
+            input.parse().map(Attribute::DisplayPrefix)
+        } else if lookahead.peek(kw::display_with_name) {
+            input.parse().map(Attribute::DisplayWithName)
+        } else if lookahead.peek(kw::doc) {
+            input.parse().map(Attribute::Doc)
+        } else if lookahead.peek(kw::exit_code) {
+            input.parse().map(Attribute::ExitCode)
+        } else if lookahead.peek(kw::from_enum) {
+            input.parse().map(Attribute::FromEnum)
+        } else if lookahead.peek(kw::getter) {
+            input.parse().map(Attribute::Getter)
+        } else if lookahead.peek(kw::hash) {
+            input.parse().map(Attribute::Hash)
+        } else if lookahead.peek(kw::help) {
+            input.parse().map(Attribute::Help)
+        } else if lookahead.peek(kw::kind) {
+            input.parse().map(Attribute::Kind)
+        } else if lookahead.peek(kw::location) {
+            input.parse().map(Attribute::Location)
+        } else if lookahead.peek(kw::module) {
+            input.parse().map(Attribute::Module)
+        } else if lookahead.peek(kw::retryable) {
+            input.parse().map(Attribute::Retryable)
+        } else if lookahead.peek(kw::send_sync) {
+            input.parse().map(Attribute::SendSync)
+        } else if lookahead.peek(kw::serialize) {
+            input.parse().map(Attribute::Serialize)
+        } else if lookahead.peek(kw::source) {
+            input.parse().map(Attribute::Source)
+        } else if lookahead.peek(kw::transparent) {
+            input.parse().map(Attribute::Transparent)
+        } else if lookahead.peek(kw::variant_name) {
+            input.parse().map(Attribute::VariantName)
+        } else if lookahead.peek(kw::variants_const) {
+            input.parse().map(Attribute::VariantsConst)
+        } else if lookahead.peek(kw::visibility) {
+            input.parse().map(Attribute::Visibility)
+        } else if lookahead.peek(kw::whatever) {
+            input.parse().map(Attribute::Whatever)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+struct Backtrace {
+    backtrace_token: kw::backtrace,
+    arg: MaybeArg<BacktraceArg>,
+}
+
+impl Backtrace {
+    // `None` when searching the source chain instead of marking a field
+    fn into_components(self) -> Option<(bool, Option<Expr>)> {
+        match self.arg.into_option() {
+            None => Some((true, None)),
+            Some(BacktraceArg::Bool(value)) => Some((value.value, None)),
+            Some(BacktraceArg::CaptureIf { expr, .. }) => Some((true, Some(expr))),
+            Some(BacktraceArg::SearchChain { .. }) => None,
+        }
+    }
+}
+
+impl Parse for Backtrace {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            backtrace_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Backtrace {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.backtrace_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+enum BacktraceArg {
+    Bool(LitBool),
+    CaptureIf {
+        capture_if_token: kw::capture_if,
+        arg: ValueArg<LitStr>,
+        expr: Expr,
+    },
+    SearchChain {
+        search_chain_token: kw::search_chain,
+    },
+}
+
+impl Parse for BacktraceArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // TODO: Remove this with a semver-incompatible release
+        if input.peek(kw::delegate) {
+            return Err(input.error(
+                "`backtrace(delegate)` has been removed; use `backtrace` on a source field",
+            ));
+        }
+
+        if input.peek(kw::capture_if) {
+            let capture_if_token = input.parse()?;
+            let arg: ValueArg<LitStr> = input.parse()?;
+            let expr = arg
+                .value()
+                .parse()
+                .map_err(|e| syn::Error::new(arg.value().span(), e))?;
+            Ok(BacktraceArg::CaptureIf {
+                capture_if_token,
+                arg,
+                expr,
+            })
+        } else if input.peek(kw::search_chain) {
+            Ok(BacktraceArg::SearchChain {
+                search_chain_token: input.parse()?,
+            })
+        } else {
+            Ok(BacktraceArg::Bool(input.parse()?))
+        }
+    }
+}
+
+impl ToTokens for BacktraceArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            BacktraceArg::Bool(value) => value.to_tokens(tokens),
+            BacktraceArg::CaptureIf {
+                capture_if_token,
+                arg,
+                ..
+            } => {
+                capture_if_token.to_tokens(tokens);
+                arg.to_tokens(tokens);
+            }
+            BacktraceArg::SearchChain { search_chain_token } => {
+                search_chain_token.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+struct Builder {
+    builder_token: kw::builder,
+}
+
+impl Parse for Builder {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            builder_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Builder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.builder_token.to_tokens(tokens);
+    }
+}
+
+struct Clone {
+    clone_token: kw::clone,
+}
+
+impl Parse for Clone {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            clone_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Clone {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.clone_token.to_tokens(tokens);
+    }
+}
+
+struct Code {
+    code_token: kw::code,
+    arg: ValueArg<LitStr>,
+}
+
+impl Code {
+    fn into_value(self) -> LitStr {
+        self.arg.into_value()
+    }
+}
+
+impl Parse for Code {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            code_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Code {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.code_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Description {
+    description_token: kw::description,
+    arg: ValueArg<LitStr>,
+}
+
+impl Description {
+    fn into_value(self) -> LitStr {
+        self.arg.into_value()
+    }
+}
+
+impl Parse for Description {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            description_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Description {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.description_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct ExitCode {
+    exit_code_token: kw::exit_code,
+    arg: ValueArg<LitInt>,
+    value: i32,
+}
+
+impl ExitCode {
+    fn into_value(self) -> i32 {
+        self.value
+    }
+}
+
+impl Parse for ExitCode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let exit_code_token = input.parse()?;
+        let arg: ValueArg<LitInt> = input.parse()?;
+        let value = arg.value().base10_parse()?;
+
+        Ok(Self {
+            exit_code_token,
+            arg,
+            value,
+        })
+    }
+}
+
+impl ToTokens for ExitCode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.exit_code_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Compare {
+    compare_token: kw::compare,
+    arg: MaybeArg<LitBool>,
+}
+
+impl Compare {
+    fn into_bool(self) -> bool {
+        self.arg.into_option().map_or(true, |a| a.value)
+    }
+}
+
+impl Parse for Compare {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            compare_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Compare {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.compare_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Context {
+    context_token: kw::context,
+    arg: MaybeArg<ContextArg>,
+}
+
+impl Context {
+    fn into_value(self) -> super::Context {
+        match self.arg.into_option() {
+            None => super::Context::Flag(true),
+            Some(ContextArg::Flag { value }) => super::Context::Flag(value.value),
+            Some(ContextArg::Suffix { arg, .. }) => super::Context::Suffix(arg.into_value()),
+            Some(ContextArg::Name { arg, .. }) => super::Context::Name(arg.into_value()),
+            Some(ContextArg::FailName { arg, .. }) => super::Context::FailName(arg.into_value()),
+            Some(ContextArg::Default { .. }) => super::Context::Default,
+            Some(ContextArg::Suppress { .. }) => super::Context::Suppress,
+        }
+    }
+}
+
+impl Parse for Context {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            context_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Context {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.context_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+enum ContextArg {
+    Flag {
+        value: LitBool,
+    },
+    Suffix {
+        suffix_token: kw::suffix,
+        arg: CompatArg<Ident>,
+    },
+    Name {
+        name_token: kw::name,
+        arg: CompatArg<Ident>,
+    },
+    FailName {
+        fail_name_token: kw::fail_name,
+        arg: CompatArg<Ident>,
+    },
+    Default {
+        default_token: kw::default,
+    },
+    Suppress {
+        suppress_token: kw::suppress,
+    },
+}
+
+impl Parse for ContextArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(LitBool) {
+            Ok(ContextArg::Flag {
+                value: input.parse()?,
+            })
+        } else if lookahead.peek(kw::suffix) {
+            Ok(ContextArg::Suffix {
+                suffix_token: input.parse()?,
+                arg: input.parse()?,
+            })
+        } else if lookahead.peek(kw::name) {
+            Ok(ContextArg::Name {
+                name_token: input.parse()?,
+                arg: input.parse()?,
+            })
+        } else if lookahead.peek(kw::fail_name) {
+            Ok(ContextArg::FailName {
+                fail_name_token: input.parse()?,
+                arg: input.parse()?,
+            })
+        } else if lookahead.peek(kw::default) {
+            Ok(ContextArg::Default {
+                default_token: input.parse()?,
+            })
+        } else if lookahead.peek(kw::suppress) {
+            Ok(ContextArg::Suppress {
+                suppress_token: input.parse()?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for ContextArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            ContextArg::Flag { value } => {
+                value.to_tokens(tokens);
+            }
+            ContextArg::Suffix { suffix_token, arg } => {
+                suffix_token.to_tokens(tokens);
+                arg.to_tokens(tokens);
+            }
+            ContextArg::Name { name_token, arg } => {
+                name_token.to_tokens(tokens);
+                arg.to_tokens(tokens);
+            }
+            ContextArg::FailName {
+                fail_name_token,
+                arg,
+            } => {
+                fail_name_token.to_tokens(tokens);
+                arg.to_tokens(tokens);
+            }
+            ContextArg::Default { default_token } => {
+                default_token.to_tokens(tokens);
+            }
+            ContextArg::Suppress { suppress_token } => {
+                suppress_token.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+struct Convert {
+    convert_token: kw::convert,
+    paren_token: token::Paren,
+    arg: ConvertArg,
+}
+
+impl Convert {
+    fn into_value(self) -> super::Conversion {
+        match self.arg {
+            ConvertArg::Into(_) => super::Conversion::Into,
+            ConvertArg::ToOwned(_) => super::Conversion::ToOwned,
+            ConvertArg::Clone(_) => super::Conversion::Clone,
         }
     }
 }
 
-impl Parse for Attribute {
+impl Parse for Convert {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            convert_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            arg: content.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Convert {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.convert_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.arg.to_tokens(tokens);
+        });
+    }
+}
+
+enum ConvertArg {
+    Into(kw::into),
+    ToOwned(kw::to_owned),
+    Clone(kw::clone),
+}
+
+impl Parse for ConvertArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::into) {
+            input.parse().map(ConvertArg::Into)
+        } else if lookahead.peek(kw::to_owned) {
+            input.parse().map(ConvertArg::ToOwned)
+        } else if lookahead.peek(kw::clone) {
+            input.parse().map(ConvertArg::Clone)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for ConvertArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            ConvertArg::Into(token) => token.to_tokens(tokens),
+            ConvertArg::ToOwned(token) => token.to_tokens(tokens),
+            ConvertArg::Clone(token) => token.to_tokens(tokens),
+        }
+    }
+}
+
+struct CrateRoot {
+    crate_root_token: kw::crate_root,
+    arg: CompatArg<Path>,
+}
+
+impl CrateRoot {
+    // TODO: Remove boxed trait object
+    fn into_arbitrary(self) -> Box<dyn ToTokens> {
+        Box::new(self.arg.into_value())
+    }
+}
+
+impl Parse for CrateRoot {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            crate_root_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for CrateRoot {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.crate_root_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Debug {
+    debug_token: kw::debug,
+}
+
+impl Parse for Debug {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            debug_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Debug {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.debug_token.to_tokens(tokens);
+    }
+}
+
+struct Default {
+    default_token: kw::default,
+    arg: Option<ValueArg<Expr>>,
+}
+
+impl Default {
+    fn into_option(self) -> Option<Expr> {
+        self.arg.map(ValueArg::into_value)
+    }
+}
+
+impl Parse for Default {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let default_token = input.parse()?;
+        let arg = if input.peek(token::Paren) || input.peek(token::Eq) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { default_token, arg })
+    }
+}
+
+impl ToTokens for Default {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.default_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Deprecated {
+    deprecated_token: kw::deprecated,
+    arg: Option<ValueArg<LitStr>>,
+}
+
+impl Deprecated {
+    fn into_option(self) -> Option<LitStr> {
+        self.arg.map(ValueArg::into_value)
+    }
+}
+
+impl Parse for Deprecated {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let deprecated_token = input.parse()?;
+        let arg = if input.peek(token::Paren) || input.peek(token::Eq) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            deprecated_token,
+            arg,
+        })
+    }
+}
+
+impl ToTokens for Deprecated {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.deprecated_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Display {
+    display_token: kw::display,
+    args: CompatArg<Punctuated<Expr, token::Comma>>,
+    expanded_args: Punctuated<Expr, token::Comma>,
+    with: Option<syn::Path>,
+    runtime: Option<(Expr, Vec<Expr>)>,
+}
+
+impl Display {
+    fn into_value(self) -> super::DisplayFormat {
+        if let Some(path) = self.with {
+            return super::DisplayFormat::With(path);
+        }
+
+        match self.runtime {
+            Some((format, arguments)) => super::DisplayFormat::Runtime { format, arguments },
+            // TODO: Remove boxed trait object
+            None => super::DisplayFormat::Arguments(Box::new(self.expanded_args)),
+        }
+    }
+}
+
+impl Parse for Display {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let display_token = input.parse()?;
+        let args = CompatArg::parse_with(input, Punctuated::parse_terminated)?;
+        let with = display_with_function(args.value())?;
+        let runtime = display_runtime_format(args.value())?;
+        let expanded_args = expand_inline_arguments(args.value())?;
+
+        Ok(Self {
+            display_token,
+            args,
+            expanded_args,
+            with,
+            runtime,
+        })
+    }
+}
+
+/// Recognizes `with = "path::to::function"`, which delegates the
+/// `Display` implementation to a user-written function.
+fn display_with_function(args: &Punctuated<Expr, token::Comma>) -> Result<Option<syn::Path>> {
+    let mut args = args.iter();
+    let (first, rest) = (args.next(), args.next());
+
+    let assign = match (first, rest) {
+        (Some(Expr::Assign(assign)), None) => assign,
+        _ => return Ok(None),
+    };
+
+    let is_with = match &*assign.left {
+        Expr::Path(path) => path.path.is_ident("with"),
+        _ => false,
+    };
+    if !is_with {
+        return Ok(None);
+    }
+
+    match &*assign.right {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(path),
+            ..
+        }) => path.parse().map(Some),
+        other => Err(syn::Error::new_spanned(
+            other,
+            "expected a string containing the path to a function",
+        )),
+    }
+}
+
+/// Recognizes `fmt = EXPR, args...`, where the format string is an
+/// expression, such as a constant, instead of a literal.
+fn display_runtime_format(
+    args: &Punctuated<Expr, token::Comma>,
+) -> Result<Option<(Expr, Vec<Expr>)>> {
+    let mut args = args.iter();
+
+    let format = match args.next() {
+        Some(Expr::Assign(assign)) => match &*assign.left {
+            Expr::Path(path) if path.path.is_ident("fmt") => (*assign.right).clone(),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    let arguments = args
+        .map(|arg| match arg {
+            Expr::Assign(_) => Err(syn::Error::new_spanned(
+                arg,
+                "only positional arguments are supported with `fmt`",
+            )),
+            _ => Ok(arg.clone()),
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Some((format, arguments)))
+}
+
+/// Rewrites a format string containing inline arguments, such as
+/// `"{filename.display()}"`, into a format string that only refers
+/// to named arguments, appending an argument for each of them.
+fn expand_inline_arguments(
+    args: &Punctuated<Expr, token::Comma>,
+) -> Result<Punctuated<Expr, token::Comma>> {
+    let format = match args.first() {
+        Some(Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(format),
+            ..
+        })) => format,
+        _ => return Ok(args.clone()),
+    };
+
+    let mut named: Vec<String> = args
+        .iter()
+        .skip(1)
+        .filter_map(|arg| match arg {
+            Expr::Assign(syn::ExprAssign { left, .. }) => match &**left {
+                Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let span = format.span();
+    let value = format.value();
+    let mut rewritten = String::with_capacity(value.len());
+    let mut additional: Vec<Expr> = Vec::new();
+    let mut rest = &value[..];
+
+    while let Some(start) = rest.find(|c| c == '{' || c == '}') {
+        rewritten.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            rewritten.push_str(&rest[..2]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        if rest.starts_with('}') {
+            rewritten.push('}');
+            rest = &rest[1..];
+            continue;
+        }
+
+        let end = match closing_brace(rest) {
+            Some(end) => end,
+            None => {
+                rewritten.push_str(rest);
+                rest = "";
+                break;
+            }
+        };
+        let placeholder = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let split = format_spec_start(placeholder).unwrap_or(placeholder.len());
+        let (argument, spec) = placeholder.split_at(split);
+        let argument = argument.trim();
+
+        // Widths and precisions such as `{:>width$}` may also name
+        // an argument, which older compilers cannot capture.
+        for count in spec_count_arguments(spec) {
+            if !named.iter().any(|n| n == count) {
+                let ident = Ident::new(count, span);
+                additional.push(syn::parse_quote! { #ident = #ident });
+                named.push(count.to_string());
+            }
+        }
+
+        if argument.is_empty() || argument.bytes().all(|b| b.is_ascii_digit()) {
+            rewritten.push('{');
+            rewritten.push_str(placeholder);
+            rewritten.push('}');
+            continue;
+        }
+
+        let name = match syn::parse_str::<Ident>(argument) {
+            Ok(ident) => {
+                if !named.contains(&argument.to_string()) {
+                    let ident = Ident::new(&ident.to_string(), span);
+                    additional.push(syn::parse_quote! { #ident = #ident });
+                    named.push(argument.to_string());
+                }
+                argument.to_string()
+            }
+            Err(_) => {
+                let invalid = |_| syn::Error::new(span, "Invalid expression in format string");
+                let expr: TokenStream = syn::parse_str(argument).map_err(invalid)?;
+                let expr: Expr = syn::parse2(respan(expr, span)).map_err(invalid)?;
+                let ident = quote::format_ident!("__snafu_display_{}", additional.len());
+                additional.push(syn::parse_quote! { #ident = #expr });
+                ident.to_string()
+            }
+        };
+
+        rewritten.push('{');
+        rewritten.push_str(&name);
+        rewritten.push_str(spec);
+        rewritten.push('}');
+    }
+    rewritten.push_str(rest);
+
+    if additional.is_empty() {
+        return Ok(args.clone());
+    }
+
+    let rewritten = LitStr::new(&rewritten, span);
+    let mut expanded = Punctuated::new();
+    expanded.push(syn::parse_quote! { #rewritten });
+    expanded.extend(args.iter().skip(1).cloned());
+    expanded.extend(additional);
+    Ok(expanded)
+}
+
+/// Finds the brace that closes the placeholder starting at the
+/// beginning of `s`, skipping over any nested delimiters.
+fn closing_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Finds the colon that separates an argument from its format
+/// specification, ignoring path separators and nested delimiters.
+fn format_spec_start(placeholder: &str) -> Option<usize> {
+    let bytes = placeholder.as_bytes();
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 => {
+                let prev = i.checked_sub(1).map(|p| bytes[p]);
+                let next = bytes.get(i + 1).cloned();
+                if prev != Some(b':') && next != Some(b':') {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the named arguments used as a width or precision in a
+/// format specification, such as `width` in `:>width$`.
+pub(crate) fn spec_count_arguments(spec: &str) -> impl Iterator<Item = &str> {
+    spec.match_indices('$').filter_map(move |(end, _)| {
+        let start = spec[..end]
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+        let name = &spec[start..end];
+        let is_named = name
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_');
+        if is_named {
+            Some(name)
+        } else {
+            None
+        }
+    })
+}
+
+fn respan(tokens: TokenStream, span: proc_macro2::Span) -> TokenStream {
+    use proc_macro2::{Group, TokenTree};
+
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut new = Group::new(group.delimiter(), respan(group.stream(), span));
+                new.set_span(span);
+                token = TokenTree::Group(new);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+impl ToTokens for Display {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_token.to_tokens(tokens);
+        self.args.to_tokens(tokens);
+    }
+}
+
+struct DisplayPrefix {
+    display_prefix_token: kw::display_prefix,
+    arg: ValueArg<LitStr>,
+}
+
+impl DisplayPrefix {
+    fn into_value(self) -> LitStr {
+        self.arg.into_value()
+    }
+}
+
+impl Parse for DisplayPrefix {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            display_prefix_token: input.parse()?,
+            arg: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for DisplayPrefix {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_prefix_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct DisplayWithName {
+    display_with_name_token: kw::display_with_name,
+}
+
+impl Parse for DisplayWithName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            display_with_name_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for DisplayWithName {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_with_name_token.to_tokens(tokens);
+    }
+}
+
+struct VariantName {
+    variant_name_token: kw::variant_name,
+}
+
+impl Parse for VariantName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            variant_name_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for VariantName {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.variant_name_token.to_tokens(tokens);
+    }
+}
+
+struct VariantsConst {
+    variants_const_token: kw::variants_const,
+}
+
+impl Parse for VariantsConst {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            variants_const_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for VariantsConst {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.variants_const_token.to_tokens(tokens);
+    }
+}
+
+struct Doc {
+    doc_token: kw::doc,
+    paren_token: token::Paren,
+    str: LitStr,
+}
+
+impl Doc {
+    fn into_value(self) -> String {
+        self.str.value()
+    }
+}
+
+impl Parse for Doc {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            doc_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            str: content.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Doc {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.doc_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.str.to_tokens(tokens);
+        });
+    }
+}
+
+struct DocComment {
+    eq_token: token::Eq,
+    str: LitStr,
+}
+
+impl DocComment {
+    fn into_value(self) -> String {
+        self.str.value()
+    }
+}
+
+impl From<DocComment> for SnafuAttribute {
+    fn from(other: DocComment) -> Self {
+        SnafuAttribute::DocComment(other.to_token_stream(), other.into_value())
+    }
+}
+
+impl Parse for DocComment {
     fn parse(input: ParseStream) -> Result<Self> {
-        let lookahead = input.lookahead1();
-        if lookahead.peek(kw::backtrace) {
-            input.parse().map(Attribute::Backtrace)
-        } else if lookahead.peek(kw::context) {
-            input.parse().map(Attribute::Context)
-        } else if lookahead.peek(kw::crate_root) {
-            input.parse().map(Attribute::CrateRoot)
-        } else if lookahead.peek(kw::display) {
-            input.parse().map(Attribute::Display)
-        } else if lookahead.peek(kw::source) {
-            input.parse().map(Attribute::Source)
-        } else if lookahead.peek(kw::visibility) {
-            input.parse().map(Attribute::Visibility)
-        } else {
-            Err(lookahead.error())
-        }
+        Ok(Self {
+            eq_token: input.parse()?,
+            str: input.parse()?,
+        })
     }
 }
 
-struct Backtrace {
-    backtrace_token: kw::backtrace,
-    arg: MaybeArg<BacktraceArg>,
+impl ToTokens for DocComment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.eq_token.to_tokens(tokens);
+        self.str.to_tokens(tokens);
+    }
 }
 
-impl Backtrace {
-    fn into_bool(self) -> bool {
-        self.arg.into_option().map_or(true, |a| a.value.value)
+struct FromEnum {
+    from_enum_token: kw::from_enum,
+    paren_token: token::Paren,
+    arg: FromEnumArg,
+}
+
+impl FromEnum {
+    fn into_option(self) -> Option<Path> {
+        match self.arg {
+            FromEnumArg::Opt(_) => None,
+            FromEnumArg::Path(path) => Some(path),
+        }
     }
 }
 
-impl Parse for Backtrace {
+impl Parse for FromEnum {
     fn parse(input: ParseStream) -> Result<Self> {
+        let content;
         Ok(Self {
-            backtrace_token: input.parse()?,
-            arg: input.parse()?,
+            from_enum_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            arg: content.parse()?,
         })
     }
 }
 
-impl ToTokens for Backtrace {
+impl ToTokens for FromEnum {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.backtrace_token.to_tokens(tokens);
-        self.arg.to_tokens(tokens);
+        self.from_enum_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.arg.to_tokens(tokens);
+        });
     }
 }
 
-struct BacktraceArg {
-    value: LitBool,
+enum FromEnumArg {
+    Opt(LitBool),
+    Path(Path),
 }
 
-impl Parse for BacktraceArg {
+impl Parse for FromEnumArg {
     fn parse(input: ParseStream) -> Result<Self> {
-        // TODO: Remove this with a semver-incompatible release
-        if input.peek(kw::delegate) {
-            return Err(input.error(
-                "`backtrace(delegate)` has been removed; use `backtrace` on a source field",
-            ));
+        if input.peek(LitBool) {
+            let value: LitBool = input.parse()?;
+            if value.value {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "expected the path to an enum or `false`",
+                ));
+            }
+            Ok(FromEnumArg::Opt(value))
+        } else {
+            Ok(FromEnumArg::Path(input.parse()?))
+        }
+    }
+}
+
+impl ToTokens for FromEnumArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            FromEnumArg::Opt(value) => value.to_tokens(tokens),
+            FromEnumArg::Path(path) => path.to_tokens(tokens),
         }
+    }
+}
+
+struct Getter {
+    getter_token: kw::getter,
+}
 
+impl Parse for Getter {
+    fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            value: input.parse()?,
+            getter_token: input.parse()?,
         })
     }
 }
 
-impl ToTokens for BacktraceArg {
+impl ToTokens for Getter {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.value.to_tokens(tokens);
+        self.getter_token.to_tokens(tokens);
     }
 }
 
-struct Context {
-    context_token: kw::context,
+struct Hash {
+    hash_token: kw::hash,
     arg: MaybeArg<LitBool>,
 }
 
-impl Context {
+impl Hash {
     fn into_bool(self) -> bool {
         self.arg.into_option().map_or(true, |a| a.value)
     }
 }
 
-impl Parse for Context {
+impl Parse for Hash {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            context_token: input.parse()?,
+            hash_token: input.parse()?,
             arg: input.parse()?,
         })
     }
 }
 
-impl ToTokens for Context {
+impl ToTokens for Hash {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.context_token.to_tokens(tokens);
+        self.hash_token.to_tokens(tokens);
         self.arg.to_tokens(tokens);
     }
 }
 
-struct CrateRoot {
-    crate_root_token: kw::crate_root,
-    arg: CompatArg<Path>,
+struct Help {
+    help_token: kw::help,
+    arg: ValueArg<LitStr>,
 }
 
-impl CrateRoot {
-    // TODO: Remove boxed trait object
-    fn into_arbitrary(self) -> Box<dyn ToTokens> {
-        Box::new(self.arg.into_value())
+impl Help {
+    fn into_value(self) -> LitStr {
+        self.arg.into_value()
     }
 }
 
-impl Parse for CrateRoot {
+impl Parse for Help {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            crate_root_token: input.parse()?,
+            help_token: input.parse()?,
             arg: input.parse()?,
         })
     }
 }
 
-impl ToTokens for CrateRoot {
+impl ToTokens for Help {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.crate_root_token.to_tokens(tokens);
+        self.help_token.to_tokens(tokens);
         self.arg.to_tokens(tokens);
     }
 }
 
-struct Display {
-    display_token: kw::display,
-    args: CompatArg<Punctuated<Expr, token::Comma>>,
+struct Module {
+    module_token: kw::module,
+    arg: Option<CompatArg<Ident>>,
 }
 
-impl Display {
-    // TODO: Remove boxed trait object
-    fn into_arbitrary(self) -> Box<dyn ToTokens> {
-        Box::new(self.args.into_value())
+impl Module {
+    fn into_option(self) -> Option<Ident> {
+        self.arg.map(CompatArg::into_value)
     }
 }
 
-impl Parse for Display {
+impl Parse for Module {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let module_token = input.parse()?;
+        let arg = if input.peek(token::Paren) || input.peek(token::Eq) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { module_token, arg })
+    }
+}
+
+impl ToTokens for Module {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.module_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Kind {
+    kind_token: kw::kind,
+}
+
+impl Parse for Kind {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            display_token: input.parse()?,
-            args: CompatArg::parse_with(&input, Punctuated::parse_terminated)?,
+            kind_token: input.parse()?,
         })
     }
 }
 
-impl ToTokens for Display {
+impl ToTokens for Kind {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.display_token.to_tokens(tokens);
-        self.args.to_tokens(tokens);
+        self.kind_token.to_tokens(tokens);
     }
 }
 
-struct DocComment {
-    eq_token: token::Eq,
-    str: LitStr,
+struct Location {
+    location_token: kw::location,
+    arg: MaybeArg<LitBool>,
 }
 
-impl DocComment {
-    fn into_value(self) -> String {
-        self.str.value()
+impl Location {
+    fn into_bool(self) -> bool {
+        self.arg.into_option().map_or(true, |a| a.value)
     }
 }
 
-impl From<DocComment> for SnafuAttribute {
-    fn from(other: DocComment) -> Self {
-        SnafuAttribute::DocComment(other.to_token_stream(), other.into_value())
+impl Parse for Location {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            location_token: input.parse()?,
+            arg: input.parse()?,
+        })
     }
 }
 
-impl Parse for DocComment {
+impl ToTokens for Location {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.location_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct Retryable {
+    retryable_token: kw::retryable,
+    arg: MaybeArg<LitBool>,
+}
+
+impl Retryable {
+    fn into_bool(self) -> bool {
+        self.arg.into_option().map_or(true, |a| a.value)
+    }
+}
+
+impl Parse for Retryable {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            eq_token: input.parse()?,
-            str: input.parse()?,
+            retryable_token: input.parse()?,
+            arg: input.parse()?,
         })
     }
 }
 
-impl ToTokens for DocComment {
+impl ToTokens for Retryable {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.eq_token.to_tokens(tokens);
-        self.str.to_tokens(tokens);
+        self.retryable_token.to_tokens(tokens);
+        self.arg.to_tokens(tokens);
+    }
+}
+
+struct SendSync {
+    send_sync_token: kw::send_sync,
+}
+
+impl Parse for SendSync {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            send_sync_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for SendSync {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.send_sync_token.to_tokens(tokens);
+    }
+}
+
+struct Serialize {
+    serialize_token: kw::serialize,
+}
+
+impl Parse for Serialize {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            serialize_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Serialize {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.serialize_token.to_tokens(tokens);
     }
 }
 
@@ -281,6 +1539,11 @@ impl Source {
                 .into_iter()
                 .map(|sa| match sa {
                     SourceArg::Flag { value } => super::Source::Flag(value.value),
+                    SourceArg::Arc { .. } => super::Source::Arc,
+                    SourceArg::DisplayOnly { .. } => super::Source::DisplayOnly,
+                    SourceArg::Bound { predicates, .. } => {
+                        super::Source::Bound(predicates.into_iter().collect())
+                    }
                     SourceArg::From { r#type, expr, .. } => super::Source::From(r#type, expr),
                 })
                 .collect(),
@@ -292,7 +1555,7 @@ impl Parse for Source {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
             source_token: input.parse()?,
-            args: MaybeArg::parse_with(&input, Punctuated::parse_terminated)?,
+            args: MaybeArg::parse_with(input, Punctuated::parse_terminated)?,
         })
     }
 }
@@ -308,6 +1571,17 @@ enum SourceArg {
     Flag {
         value: LitBool,
     },
+    Arc {
+        arc_token: kw::arc,
+    },
+    Bound {
+        bound_token: kw::bound,
+        arg: ValueArg<LitStr>,
+        predicates: Punctuated<WherePredicate, token::Comma>,
+    },
+    DisplayOnly {
+        display_only_token: kw::display_only,
+    },
     From {
         from_token: kw::from,
         paren_token: token::Paren,
@@ -324,6 +1598,26 @@ impl Parse for SourceArg {
             Ok(SourceArg::Flag {
                 value: input.parse()?,
             })
+        } else if lookahead.peek(kw::arc) {
+            Ok(SourceArg::Arc {
+                arc_token: input.parse()?,
+            })
+        } else if lookahead.peek(kw::bound) {
+            let bound_token = input.parse()?;
+            let arg: ValueArg<LitStr> = input.parse()?;
+            let predicates = arg
+                .value()
+                .parse_with(Punctuated::parse_terminated)
+                .map_err(|e| syn::Error::new(arg.value().span(), e))?;
+            Ok(SourceArg::Bound {
+                bound_token,
+                arg,
+                predicates,
+            })
+        } else if lookahead.peek(kw::display_only) {
+            Ok(SourceArg::DisplayOnly {
+                display_only_token: input.parse()?,
+            })
         } else if lookahead.peek(kw::from) {
             let content;
             Ok(SourceArg::From {
@@ -345,6 +1639,18 @@ impl ToTokens for SourceArg {
             SourceArg::Flag { value } => {
                 value.to_tokens(tokens);
             }
+            SourceArg::Arc { arc_token } => {
+                arc_token.to_tokens(tokens);
+            }
+            SourceArg::Bound {
+                bound_token, arg, ..
+            } => {
+                bound_token.to_tokens(tokens);
+                arg.to_tokens(tokens);
+            }
+            SourceArg::DisplayOnly { display_only_token } => {
+                display_only_token.to_tokens(tokens);
+            }
             SourceArg::From {
                 from_token,
                 paren_token,
@@ -363,6 +1669,24 @@ impl ToTokens for SourceArg {
     }
 }
 
+struct Transparent {
+    transparent_token: kw::transparent,
+}
+
+impl Parse for Transparent {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            transparent_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Transparent {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.transparent_token.to_tokens(tokens);
+    }
+}
+
 struct Visibility {
     visibility_token: kw::visibility,
     visibility: MaybeCompatArg<syn::Visibility>,
@@ -394,6 +1718,24 @@ impl ToTokens for Visibility {
     }
 }
 
+struct Whatever {
+    whatever_token: kw::whatever,
+}
+
+impl Parse for Whatever {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            whatever_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Whatever {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.whatever_token.to_tokens(tokens);
+    }
+}
+
 enum MaybeArg<T> {
     None,
     Some {
@@ -461,6 +1803,13 @@ enum CompatArg<T> {
 }
 
 impl<T> CompatArg<T> {
+    fn value(&self) -> &T {
+        match self {
+            CompatArg::Compat { content, .. } => content,
+            CompatArg::Pretty { content, .. } => content,
+        }
+    }
+
     fn into_value(self) -> T {
         match self {
             CompatArg::Compat { content, .. } => content,
@@ -604,3 +1953,59 @@ impl<T: ToTokens> ToTokens for MaybeCompatArg<T> {
         }
     }
 }
+
+/// A value written either as `name(value)` or `name = value`.
+enum ValueArg<T> {
+    Paren { paren_token: token::Paren, value: T },
+    Eq { eq_token: token::Eq, value: T },
+}
+
+impl<T> ValueArg<T> {
+    fn value(&self) -> &T {
+        match self {
+            ValueArg::Paren { value, .. } => value,
+            ValueArg::Eq { value, .. } => value,
+        }
+    }
+
+    fn into_value(self) -> T {
+        match self {
+            ValueArg::Paren { value, .. } => value,
+            ValueArg::Eq { value, .. } => value,
+        }
+    }
+}
+
+impl<T: Parse> Parse for ValueArg<T> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(token::Paren) {
+            let content;
+            Ok(ValueArg::Paren {
+                paren_token: parenthesized!(content in input),
+                value: content.parse()?,
+            })
+        } else if lookahead.peek(token::Eq) {
+            Ok(ValueArg::Eq {
+                eq_token: input.parse()?,
+                value: input.parse()?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl<T: ToTokens> ToTokens for ValueArg<T> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            ValueArg::Paren { paren_token, value } => {
+                paren_token.surround(tokens, |tokens| value.to_tokens(tokens));
+            }
+            ValueArg::Eq { eq_token, value } => {
+                eq_token.to_tokens(tokens);
+                value.to_tokens(tokens);
+            }
+        }
+    }
+}
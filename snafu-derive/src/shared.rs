@@ -1,18 +1,35 @@
+pub(crate) use self::clone::{Clone, CloneMatchArm};
 pub(crate) use self::context_selector::ContextSelector;
+pub(crate) use self::debug::{Debug, DebugMatchArm};
 pub(crate) use self::display::{Display, DisplayMatchArm};
 pub(crate) use self::error::{Error, ErrorSourceMatchArm};
-pub(crate) use self::error_compat::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+pub(crate) use self::error_compat::{
+    ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatLocationMatchArm,
+    ErrorCompatStripBacktraceMatchArm,
+};
+pub(crate) use self::hash::{Hash, HashMatchArm};
+pub(crate) use self::partial_eq::{PartialEq, PartialEqMatchArm};
+pub(crate) use self::send_sync::SendSync;
+pub(crate) use self::serialize::{Serialize, SerializeMatchArm};
 
 pub mod context_selector {
-    use crate::{ContextSelectorKind, Field};
+    use crate::{ContextSelectorKind, Conversion, DefaultField, Field};
     use proc_macro2::TokenStream;
     use quote::{format_ident, quote, ToTokens};
 
     #[derive(Copy, Clone)]
     pub(crate) struct ContextSelector<'a> {
         pub backtrace_field: Option<&'a Field>,
+        pub backtrace_capture_if: Option<&'a syn::Expr>,
+        pub location_field: Option<&'a Field>,
+        pub default_fields: &'a [DefaultField],
         pub crate_root: &'a dyn ToTokens,
         pub error_constructor_name: &'a dyn ToTokens,
+        pub fail_name: &'a proc_macro2::Ident,
+        pub builder: bool,
+        pub constructor_name: Option<&'a proc_macro2::Ident>,
+        pub selector_default: bool,
+        pub deprecated: Option<Option<&'a syn::LitStr>>,
         pub original_generics_without_defaults: &'a [TokenStream],
         pub parameterized_error_name: &'a dyn ToTokens,
         pub selector_doc_string: &'a str,
@@ -28,6 +45,7 @@ pub mod context_selector {
             use self::ContextSelectorKind::*;
 
             let context_selector = match self.selector_kind {
+                Context { .. } if self.constructor_name.is_some() => self.generate_constructor(),
                 Context { source_field, .. } => {
                     let context_selector_type = self.generate_type();
                     let context_selector_impl = match source_field {
@@ -36,14 +54,30 @@ pub mod context_selector {
                     };
                     let context_selector_into_error_impl =
                         self.generate_into_error(source_field.as_ref());
+                    let context_selector_builder = if self.builder {
+                        Some(self.generate_builder())
+                    } else {
+                        None
+                    };
+                    let context_selector_default = if self.selector_default {
+                        Some(self.generate_default())
+                    } else {
+                        None
+                    };
 
                     quote! {
                         #context_selector_type
                         #context_selector_impl
                         #context_selector_into_error_impl
+                        #context_selector_builder
+                        #context_selector_default
                     }
                 }
                 NoContext { source_field } => self.generate_from_source(source_field),
+                Whatever {
+                    source_field,
+                    message_field,
+                } => self.generate_whatever(source_field.as_ref(), message_field),
             };
 
             stream.extend(context_selector)
@@ -64,6 +98,30 @@ pub mod context_selector {
                 .collect()
         }
 
+        // Selectors for tuple variants are tuple structs themselves,
+        // holding only the user fields in order.
+        fn is_positional(&self) -> bool {
+            self.user_fields.iter().any(Field::is_positional)
+        }
+
+        fn selector_members(&self) -> Vec<syn::Member> {
+            if self.is_positional() {
+                (0..self.user_fields.len())
+                    .map(|index| syn::Member::Unnamed(syn::Index::from(index)))
+                    .collect()
+            } else {
+                self.user_fields
+                    .iter()
+                    .map(|field| field.member().clone())
+                    .collect()
+            }
+        }
+
+        // The selector's own impls use it, which should not warn.
+        fn allow_deprecated(&self) -> Option<TokenStream> {
+            self.deprecated.map(|_| quote! { #[allow(deprecated)] })
+        }
+
         fn parameterized_selector_name(&self) -> TokenStream {
             let selector_name = self.selector_name;
             let user_generics = self.user_field_generics();
@@ -75,23 +133,22 @@ pub mod context_selector {
             let user_field_generics = self.user_field_generics();
             let where_clauses = self.where_clauses;
 
-            let target_types = user_fields
-                .iter()
-                .map(|Field { ty, .. }| quote! { ::core::convert::Into<#ty>});
-
             user_field_generics
                 .into_iter()
-                .zip(target_types)
-                .map(|(gen, bound)| quote! { #gen: #bound })
+                .zip(user_fields)
+                .map(|(gen, field)| conversion_bounds(&gen, field))
                 .chain(where_clauses.iter().cloned())
                 .collect()
         }
 
         fn transfer_user_fields(&self) -> Vec<TokenStream> {
-            self.user_field_names()
-                .into_iter()
-                .map(|name| {
-                    quote! { #name: ::core::convert::Into::into(self.#name) }
+            self.user_fields
+                .iter()
+                .zip(self.selector_members())
+                .map(|(field, selector_member)| {
+                    let member = field.member();
+                    let value = convert_value(field, quote! { self.#selector_member });
+                    quote! { #member: #value }
                 })
                 .collect()
         }
@@ -99,11 +156,46 @@ pub mod context_selector {
         fn construct_backtrace_field(&self) -> Option<TokenStream> {
             self.backtrace_field.map(|field| {
                 let crate_root = self.crate_root;
-                let name = &field.name;
-                quote! { #name: #crate_root::GenerateBacktrace::generate(), }
+                let member = field.member();
+                match self.backtrace_capture_if {
+                    Some(capture_if) => quote! {
+                        #member: if #capture_if {
+                            #crate_root::GenerateBacktrace::generate()
+                        } else {
+                            ::core::option::Option::None
+                        },
+                    },
+                    None => quote! { #member: #crate_root::GenerateBacktrace::generate(), },
+                }
+            })
+        }
+
+        fn construct_location_field(&self) -> Option<TokenStream> {
+            self.location_field.map(|field| {
+                let crate_root = self.crate_root;
+                let member = field.member();
+                if cfg!(feature = "rust_1_46") {
+                    quote! { #member: #crate_root::Location::caller(), }
+                } else {
+                    quote! { #member: #crate_root::location!(), }
+                }
             })
         }
 
+        fn construct_default_fields(&self) -> TokenStream {
+            let fields = self
+                .default_fields
+                .iter()
+                .map(|DefaultField { field, value }| {
+                    let member = field.member();
+                    match value {
+                        Some(value) => quote! { #member: #value, },
+                        None => quote! { #member: ::core::default::Default::default(), },
+                    }
+                });
+            quote! { #(#fields)* }
+        }
+
         fn generate_type(self) -> TokenStream {
             let visibility = self.visibility;
             let parameterized_selector_name = self.parameterized_selector_name();
@@ -111,28 +203,204 @@ pub mod context_selector {
             let user_field_names = self.user_field_names();
             let selector_doc_string = self.selector_doc_string;
 
+            // Each field is as visible as the selector unless it has
+            // its own `#[snafu(visibility)]`.
+            let field_visibilities: Vec<_> = self
+                .user_fields
+                .iter()
+                .map(|field| match field.visibility() {
+                    Some(v) => Some(v as &dyn ToTokens),
+                    None => visibility,
+                })
+                .collect();
+
             let body = if user_field_names.is_empty() {
                 quote! { ; }
+            } else if self.is_positional() {
+                quote! {
+                    (
+                        #(
+                            #[allow(missing_docs)]
+                            #field_visibilities #user_field_generics
+                        ),*
+                    );
+                }
             } else {
                 quote! {
                     {
                         #(
                             #[allow(missing_docs)]
-                            #visibility #user_field_names: #user_field_generics
+                            #field_visibilities #user_field_names: #user_field_generics
                         ),*
                     }
                 }
             };
 
+            let deprecated = self.deprecated.map(|note| match note {
+                Some(note) => quote! { #[deprecated(note = #note)] },
+                None => quote! { #[deprecated] },
+            });
+            let allow_deprecated = self.allow_deprecated();
+
             quote! {
                 #[derive(Debug, Copy, Clone)]
                 #[doc = #selector_doc_string]
+                #deprecated
+                #allow_deprecated
                 #visibility struct #parameterized_selector_name #body
             }
         }
 
+        fn generate_builder(self) -> TokenStream {
+            let visibility = self.visibility;
+            let selector_name = self.selector_name;
+            let user_field_generics = self.user_field_generics();
+            let user_field_names = self.user_field_names();
+
+            let optional_types: Vec<_> = self
+                .user_fields
+                .iter()
+                .map(|Field { ty, .. }| option_inner_type(ty))
+                .collect();
+
+            // `new` accepts every required field, leaving the optional
+            // fields as `None`.
+            let new_generics = user_field_generics
+                .iter()
+                .zip(&optional_types)
+                .filter(|(_, optional)| optional.is_none())
+                .map(|(generic, _)| generic);
+            let new_arguments = user_field_names
+                .iter()
+                .zip(&user_field_generics)
+                .zip(&optional_types)
+                .filter(|(_, optional)| optional.is_none())
+                .map(|((name, generic), _)| quote! { #name: #generic });
+            let new_selector_types = self
+                .user_fields
+                .iter()
+                .zip(&user_field_generics)
+                .zip(&optional_types)
+                .map(|((field, generic), optional)| match optional {
+                    Some(_) => {
+                        let ty = &field.ty;
+                        quote! { #ty }
+                    }
+                    None => quote! { #generic },
+                });
+            let new_values = user_field_names.iter().zip(&optional_types).map(
+                |(name, optional)| match optional {
+                    Some(_) => quote! { #name: ::core::option::Option::None },
+                    None => quote! { #name },
+                },
+            );
+
+            let setters = self
+                .user_fields
+                .iter()
+                .enumerate()
+                .filter_map(|(index, field)| optional_types[index].map(|inner| (index, field, inner)))
+                .map(|(index, field, inner)| {
+                    let name = &field.name;
+                    let setter_name = format_ident!(
+                        "with_{}",
+                        name.to_string().trim_start_matches("r#"),
+                        span = name.span()
+                    );
+                    let setter_doc = format!("Set the optional `{}` field", name);
+
+                    let result_types = self
+                        .user_fields
+                        .iter()
+                        .zip(&user_field_generics)
+                        .enumerate()
+                        .map(|(i, (field, generic))| {
+                            if i == index {
+                                let ty = &field.ty;
+                                quote! { #ty }
+                            } else {
+                                quote! { #generic }
+                            }
+                        });
+                    let values = user_field_names.iter().enumerate().map(|(i, other)| {
+                        if i == index {
+                            quote! { #other: ::core::option::Option::Some(#name) }
+                        } else {
+                            quote! { #other: self.#other }
+                        }
+                    });
+
+                    quote! {
+                        #[doc = #setter_doc]
+                        #visibility fn #setter_name(self, #name: #inner) -> #selector_name<#(#result_types,)*> {
+                            #selector_name {
+                                #(#values,)*
+                            }
+                        }
+                    }
+                });
+
+            let allow_deprecated = self.allow_deprecated();
+
+            quote! {
+                #allow_deprecated
+                impl<#(#new_generics,)*> #selector_name<#(#new_selector_types,)*> {
+                    #[doc = "Create the context selector with each optional field set to `None`"]
+                    #visibility fn new(#(#new_arguments),*) -> Self {
+                        #selector_name {
+                            #(#new_values,)*
+                        }
+                    }
+                }
+
+                #allow_deprecated
+                impl<#(#user_field_generics,)*> #selector_name<#(#user_field_generics,)*> {
+                    #(#setters)*
+                }
+            }
+        }
+
+        // The selector's generic placeholders are fixed to the field
+        // types so that struct update syntax has a single type to
+        // infer. Only the error's generics used by those types can
+        // be declared, as the others would be unconstrained.
+        fn generate_default(self) -> TokenStream {
+            let selector_name = self.selector_name;
+            let field_types: Vec<_> = self.user_fields.iter().map(|f| &f.ty).collect();
+            let members = self.selector_members();
+            let field_type_tokens: Vec<_> = field_types.iter().map(|ty| quote! { #ty }).collect();
+
+            let generics = self
+                .original_generics_without_defaults
+                .iter()
+                .filter(|generic| {
+                    generic_parameter_name(generic).map_or(false, |name| {
+                        field_type_tokens
+                            .iter()
+                            .any(|ty| mentions_generic(ty.clone(), &name))
+                    })
+                });
+
+            let allow_deprecated = self.allow_deprecated();
+
+            quote! {
+                #allow_deprecated
+                impl<#(#generics),*> ::core::default::Default for #selector_name<#(#field_types,)*>
+                where
+                    #(#field_types: ::core::default::Default),*
+                {
+                    fn default() -> Self {
+                        #selector_name {
+                            #(#members: ::core::default::Default::default(),)*
+                        }
+                    }
+                }
+            }
+        }
+
         fn generate_leaf(self) -> TokenStream {
             let error_constructor_name = self.error_constructor_name;
+            let fail_name = self.fail_name;
             let original_generics_without_defaults = self.original_generics_without_defaults;
             let parameterized_error_name = self.parameterized_error_name;
             let parameterized_selector_name = self.parameterized_selector_name();
@@ -141,23 +409,34 @@ pub mod context_selector {
             let extended_where_clauses = self.extended_where_clauses();
             let transfer_user_fields = self.transfer_user_fields();
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_location_field = self.construct_location_field();
+            let construct_default_fields = self.construct_default_fields();
+            let track_caller = track_caller();
+            let allow_deprecated = self.allow_deprecated();
 
             quote! {
+                #allow_deprecated
                 impl<#(#user_field_generics,)*> #parameterized_selector_name {
                     #[doc = "Consume the selector and return the associated error"]
                     #[must_use]
+                    #[inline]
+                    #track_caller
                     #visibility fn build<#(#original_generics_without_defaults,)*>(self) -> #parameterized_error_name
                     where
                         #(#extended_where_clauses),*
                     {
                         #error_constructor_name {
                             #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
                             #(#transfer_user_fields,)*
                         }
                     }
 
                     #[doc = "Consume the selector and return a `Result` with the associated error"]
-                    #visibility fn fail<#(#original_generics_without_defaults,)* __T>(self) -> ::core::result::Result<__T, #parameterized_error_name>
+                    #[inline]
+                    #track_caller
+                    #visibility fn #fail_name<#(#original_generics_without_defaults,)* __T>(self) -> ::core::result::Result<__T, #parameterized_error_name>
                     where
                         #(#extended_where_clauses),*
                     {
@@ -167,6 +446,50 @@ pub mod context_selector {
             }
         }
 
+        // A suppressed selector is replaced by an inherent function on
+        // the error that accepts the context fields as arguments.
+        fn generate_constructor(self) -> TokenStream {
+            let constructor_name = self.constructor_name;
+            let error_constructor_name = self.error_constructor_name;
+            let original_generics_without_defaults = self.original_generics_without_defaults;
+            let parameterized_error_name = self.parameterized_error_name;
+            let user_field_generics = self.user_field_generics();
+            let user_field_names = self.user_field_names();
+            let visibility = self.visibility;
+            let selector_doc_string = self.selector_doc_string;
+            let extended_where_clauses = self.extended_where_clauses();
+            let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_location_field = self.construct_location_field();
+            let construct_default_fields = self.construct_default_fields();
+            let track_caller = track_caller();
+
+            let transfer_user_fields = self.user_fields.iter().map(|field| {
+                let member = field.member();
+                let value = convert_value(field, field.name().to_token_stream());
+                quote! { #member: #value }
+            });
+
+            quote! {
+                impl<#(#original_generics_without_defaults,)*> #parameterized_error_name {
+                    #[doc = #selector_doc_string]
+                    #[must_use]
+                    #[inline]
+                    #track_caller
+                    #visibility fn #constructor_name<#(#user_field_generics,)*>(#(#user_field_names: #user_field_generics),*) -> Self
+                    where
+                        #(#extended_where_clauses),*
+                    {
+                        #error_constructor_name {
+                            #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
+                            #(#transfer_user_fields,)*
+                        }
+                    }
+                }
+            }
+        }
+
         fn generate_into_error(self, source_field: Option<&crate::SourceField>) -> TokenStream {
             let crate_root = self.crate_root;
             let error_constructor_name = self.error_constructor_name;
@@ -177,6 +500,9 @@ pub mod context_selector {
             let extended_where_clauses = self.extended_where_clauses();
             let transfer_user_fields = self.transfer_user_fields();
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_location_field = self.construct_location_field();
+            let construct_default_fields = self.construct_default_fields();
+            let track_caller = track_caller();
 
             let (source_ty, transfer_source_field) = match source_field {
                 Some(source_field) => {
@@ -185,22 +511,49 @@ pub mod context_selector {
                 }
                 None => (quote! { #crate_root::NoneError }, quote! {}),
             };
+            let source_bounds = source_field.map_or(&[][..], |f| &f.bounds);
+
+            let into_error_with_location = self.location_field.map(|field| {
+                let member = field.member();
+                quote! {
+                    #[inline]
+                    fn into_error_with_location(self, error: Self::Source, location: #crate_root::Location) -> #parameterized_error_name {
+                        #error_constructor_name {
+                            #transfer_source_field
+                            #construct_backtrace_field
+                            #member: location,
+                            #construct_default_fields
+                            #(#transfer_user_fields),*
+                        }
+                    }
+                }
+            });
+
+            let allow_deprecated = self.allow_deprecated();
 
             quote! {
+                #allow_deprecated
                 impl<#(#original_generics_without_defaults,)* #(#user_field_generics,)*> #crate_root::IntoError<#parameterized_error_name> for #parameterized_selector_name
                 where
                     #parameterized_error_name: #crate_root::Error + #crate_root::ErrorCompat,
-                    #(#extended_where_clauses),*
+                    #(#extended_where_clauses,)*
+                    #(#source_bounds,)*
                 {
                     type Source = #source_ty;
 
+                    #[inline]
+                    #track_caller
                     fn into_error(self, error: Self::Source) -> #parameterized_error_name {
                         #error_constructor_name {
                             #transfer_source_field
                             #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
                             #(#transfer_user_fields),*
                         }
                     }
+
+                    #into_error_with_location
                 }
             }
         }
@@ -209,21 +562,94 @@ pub mod context_selector {
             let parameterized_error_name = self.parameterized_error_name;
             let error_constructor_name = self.error_constructor_name;
             let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_location_field = self.construct_location_field();
+            let construct_default_fields = self.construct_default_fields();
+            let track_caller = track_caller();
             let original_generics_without_defaults = self.original_generics_without_defaults;
             let user_field_generics = self.user_field_generics();
             let where_clauses = self.where_clauses;
 
             let (source_field_type, transfer_source_field) = build_source_info(source_field);
+            let source_bounds = &source_field.bounds;
 
             quote! {
                 impl<#(#original_generics_without_defaults,)* #(#user_field_generics,)*> ::core::convert::From<#source_field_type> for #parameterized_error_name
                 where
-                    #(#where_clauses),*
+                    #(#where_clauses,)*
+                    #(#source_bounds,)*
                 {
+                    #[inline]
+                    #track_caller
                     fn from(error: #source_field_type) -> Self {
                         #error_constructor_name {
                             #transfer_source_field
                             #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
+                        }
+                    }
+                }
+            }
+        }
+
+        fn generate_whatever(
+            self,
+            source_field: Option<&crate::SourceField>,
+            message_field: &Field,
+        ) -> TokenStream {
+            let crate_root = self.crate_root;
+            let parameterized_error_name = self.parameterized_error_name;
+            let error_constructor_name = self.error_constructor_name;
+            let construct_backtrace_field = self.construct_backtrace_field();
+            let construct_location_field = self.construct_location_field();
+            let construct_default_fields = self.construct_default_fields();
+            let track_caller = track_caller();
+            let original_generics_without_defaults = self.original_generics_without_defaults;
+            let where_clauses = self.where_clauses;
+
+            let message_field_member = message_field.member();
+
+            let source_bounds = source_field.map_or(&[][..], |f| &f.bounds);
+            let (source_ty, empty_source_field, transfer_source_field) = match source_field {
+                Some(source_field) => {
+                    let (ty, transfer) = build_source_info(source_field);
+                    let member = source_field.member();
+                    (
+                        quote! { #ty },
+                        quote! { #member: ::core::option::Option::None, },
+                        transfer,
+                    )
+                }
+                None => (quote! { #crate_root::NoneError }, quote! {}, quote! {}),
+            };
+
+            quote! {
+                impl<#(#original_generics_without_defaults,)*> #crate_root::FromString for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#source_bounds,)*
+                {
+                    type Source = #source_ty;
+
+                    #track_caller
+                    fn without_source(message: ::std::string::String) -> Self {
+                        #error_constructor_name {
+                            #empty_source_field
+                            #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
+                            #message_field_member: ::core::convert::Into::into(message),
+                        }
+                    }
+
+                    #track_caller
+                    fn with_source(error: Self::Source, message: ::std::string::String) -> Self {
+                        #error_constructor_name {
+                            #transfer_source_field
+                            #construct_backtrace_field
+                            #construct_location_field
+                            #construct_default_fields
+                            #message_field_member: ::core::convert::Into::into(message),
                         }
                     }
                 }
@@ -231,21 +657,243 @@ pub mod context_selector {
         }
     }
 
+    // Allows a `Location` field to record where the error was created
+    // instead of where the error type was defined.
+    fn track_caller() -> TokenStream {
+        if cfg!(feature = "rust_1_46") {
+            quote! { #[track_caller] }
+        } else {
+            quote! {}
+        }
+    }
+
     // Assumes that the error is in a variable called "error"
     fn build_source_info(source_field: &crate::SourceField) -> (&syn::Type, TokenStream) {
-        let source_field_name = source_field.name();
+        let source_field_member = source_field.member();
         let source_field_type = source_field.transformation.ty();
         let source_transformation = source_field.transformation.transformation();
 
         (
             source_field_type,
-            quote! { #source_field_name: (#source_transformation)(error), },
+            quote! { #source_field_member: (#source_transformation)(error), },
         )
     }
+
+    /// The name of a generic parameter, such as `T` in `T: Debug` or
+    /// `'a` in `'a`.
+    fn generic_parameter_name(generic: &TokenStream) -> Option<String> {
+        use proc_macro2::TokenTree;
+
+        let mut tokens = generic.clone().into_iter();
+        match tokens.next()? {
+            TokenTree::Punct(p) if p.as_char() == '\'' => match tokens.next()? {
+                TokenTree::Ident(i) => Some(format!("'{}", i)),
+                _ => None,
+            },
+            TokenTree::Ident(i) if i == "const" => match tokens.next()? {
+                TokenTree::Ident(i) => Some(i.to_string()),
+                _ => None,
+            },
+            TokenTree::Ident(i) => Some(i.to_string()),
+            _ => None,
+        }
+    }
+
+    fn mentions_generic(tokens: TokenStream, name: &str) -> bool {
+        use proc_macro2::TokenTree;
+
+        let mut previous_was_quote = false;
+        tokens.into_iter().any(|token| {
+            let found = match &token {
+                TokenTree::Group(g) => mentions_generic(g.stream(), name),
+                TokenTree::Ident(i) if previous_was_quote => format!("'{}", i) == name,
+                TokenTree::Ident(i) => i == name,
+                _ => false,
+            };
+            previous_was_quote = match &token {
+                TokenTree::Punct(p) => p.as_char() == '\'',
+                _ => false,
+            };
+            found
+        })
+    }
+
+    /// The `T` in a field of type `Option<T>`, which the builder
+    /// treats as optional.
+    fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+        let path = match ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+            _ => return None,
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != "Option" {
+            return None;
+        }
+        match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                match args.args.first() {
+                    Some(syn::GenericArgument::Type(ty)) => Some(ty),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn conversion_bounds(gen: &proc_macro2::Ident, field: &Field) -> TokenStream {
+        let ty = &field.ty;
+        match field.conversion {
+            Conversion::Into => quote! { #gen: ::core::convert::Into<#ty> },
+            Conversion::ToOwned => quote! {
+                #gen: ::core::ops::Deref,
+                <#gen as ::core::ops::Deref>::Target: ::std::borrow::ToOwned<Owned = #ty>
+            },
+            Conversion::Clone => quote! {
+                #gen: ::core::ops::Deref<Target = #ty>,
+                #ty: ::core::clone::Clone
+            },
+        }
+    }
+
+    fn convert_value(field: &Field, value: TokenStream) -> TokenStream {
+        match field.conversion {
+            Conversion::Into => quote! { ::core::convert::Into::into(#value) },
+            Conversion::ToOwned => quote! { ::std::borrow::ToOwned::to_owned(&*#value) },
+            Conversion::Clone => quote! { ::core::clone::Clone::clone(&*#value) },
+        }
+    }
+}
+
+pub mod debug {
+    use crate::{Field, FieldContainer};
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, ToTokens};
+
+    pub(crate) struct Debug<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) type_params: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Debug<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                original_generics,
+                parameterized_error_name,
+                type_params,
+                where_clauses,
+            } = *self;
+
+            let debug_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::fmt::Debug for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::fmt::Debug,)*
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match *self {
+                            #(#arms,)*
+                        }
+                    }
+                }
+            };
+
+            stream.extend(debug_impl);
+        }
+    }
+
+    pub(crate) struct DebugMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for DebugMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        name,
+                        backtrace_field,
+                        members,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_debug_{}", i))
+                .collect();
+
+            let is_tuple = members.iter().any(|member| match member {
+                syn::Member::Named(_) => false,
+                syn::Member::Unnamed(_) => true,
+            });
+            let backtrace_member = backtrace_field.as_ref().map(Field::member);
+
+            let fields = members.iter().zip(&names).map(|(member, name)| {
+                let add_field = |value: TokenStream| match member {
+                    syn::Member::Named(ident) => {
+                        let label = unraw(ident);
+                        quote! { __snafu_debug.field(#label, #value); }
+                    }
+                    syn::Member::Unnamed(_) => quote! { __snafu_debug.field(#value); },
+                };
+
+                if Some(member) == backtrace_member {
+                    // The backtrace is only shown in full by `{:#?}`.
+                    let full = add_field(quote! { #name });
+                    let opaque = add_field(quote! { &format_args!("Backtrace(<captured>)") });
+                    quote! {
+                        if __snafu_alternate
+                            || #crate_root::GenerateBacktrace::as_backtrace(#name).is_none()
+                        {
+                            #full
+                        } else {
+                            #opaque
+                        }
+                    }
+                } else {
+                    add_field(quote! { #name })
+                }
+            });
+
+            let alternate = backtrace_member.map(|_| {
+                quote! { let __snafu_alternate = f.alternate(); }
+            });
+
+            let label = unraw(name);
+            let builder = if is_tuple {
+                quote! { f.debug_tuple(#label) }
+            } else {
+                quote! { f.debug_struct(#label) }
+            };
+
+            let match_arm = quote! {
+                #pattern_ident { #(#members: ref #names,)* .. } => {
+                    #alternate
+                    let mut __snafu_debug = #builder;
+                    #(#fields)*
+                    __snafu_debug.finish()
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    fn unraw(ident: &proc_macro2::Ident) -> String {
+        let ident = ident.to_string();
+        ident.trim_start_matches("r#").to_owned()
+    }
 }
 
 pub mod display {
-    use crate::{Field, SourceField};
     use proc_macro2::TokenStream;
     use quote::{quote, ToTokens};
 
@@ -295,9 +943,16 @@ pub mod display {
     }
 
     pub(crate) struct DisplayMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
         pub(crate) backtrace_field: Option<&'a crate::Field>,
+        pub(crate) location_field: Option<&'a crate::Field>,
+        pub(crate) default_fields: &'a [crate::DefaultField],
         pub(crate) default_name: &'a dyn ToTokens,
-        pub(crate) display_format: Option<&'a dyn ToTokens>,
+        pub(crate) display_prefix: Option<&'a syn::LitStr>,
+        pub(crate) display_with_name: bool,
+        pub(crate) display_format: Option<&'a crate::DisplayFormat>,
+        pub(crate) members: &'a [syn::Member],
+        pub(crate) is_transparent: bool,
         pub(crate) doc_comment: &'a str,
         pub(crate) pattern_ident: &'a dyn ToTokens,
         pub(crate) selector_kind: &'a crate::ContextSelectorKind,
@@ -306,9 +961,16 @@ pub mod display {
     impl ToTokens for DisplayMatchArm<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
+                crate_root,
                 backtrace_field,
+                location_field,
+                default_fields,
                 default_name,
+                display_prefix,
+                display_with_name,
                 display_format,
+                members,
+                is_transparent,
                 doc_comment,
                 pattern_ident,
                 selector_kind,
@@ -316,11 +978,38 @@ pub mod display {
 
             let user_fields = selector_kind.user_fields();
             let source_field = selector_kind.source_field();
+            let message_field = selector_kind.message_field();
+
+            let field_names: Vec<_> = user_fields
+                .iter()
+                .chain(backtrace_field)
+                .chain(location_field)
+                .chain(message_field)
+                .chain(default_fields.iter().map(|f| &f.field))
+                .map(crate::Field::name)
+                .chain(source_field.map(crate::SourceField::name))
+                .collect();
 
             let format = match (display_format, source_field) {
-                (Some(v), _) => quote! { #v },
+                (_, Some(f)) if is_transparent => {
+                    let field_name = &f.name;
+                    quote! { "{}", #field_name }
+                }
+                (Some(crate::DisplayFormat::Arguments(v)), _) => quote! { #v },
+                (Some(crate::DisplayFormat::With(_)), _)
+                | (Some(crate::DisplayFormat::Runtime { .. }), _) => TokenStream::new(),
+                (None, _) if message_field.is_some() => {
+                    let field_name = message_field.map(crate::Field::name);
+                    quote! { "{}", #field_name }
+                }
                 (None, _) if !doc_comment.is_empty() => {
-                    quote! { #doc_comment }
+                    // Only the fields mentioned in the doc comment may be
+                    // passed as named arguments; unused ones are an error.
+                    let used = crate::format_argument_names(doc_comment);
+                    let interpolated = field_names
+                        .iter()
+                        .filter(|name| used.iter().any(|used| **name == *used));
+                    quote! { #doc_comment #(, #interpolated = #interpolated)* }
                 }
                 (None, Some(f)) => {
                     let field_name = &f.name;
@@ -329,17 +1018,64 @@ pub mod display {
                 (None, None) => quote! { stringify!(#default_name)},
             };
 
-            let field_names = user_fields
+            let bound_fields: Vec<_> = user_fields
                 .iter()
                 .chain(backtrace_field)
-                .map(Field::name)
-                .chain(source_field.map(SourceField::name));
+                .chain(location_field)
+                .chain(message_field)
+                .chain(default_fields.iter().map(|f| &f.field))
+                .map(|f| (f.member(), f.name()))
+                .chain(source_field.map(|f| (f.member(), f.name())))
+                .collect();
+
+            let field_bindings = bound_fields
+                .iter()
+                .map(|(member, name)| quote! { #member: ref #name });
+
+            let field_names = quote! { #(#field_bindings),* };
+
+            // A display function receives the fields in the order they
+            // were declared, followed by the formatter.
+            let write_message = match display_format {
+                Some(crate::DisplayFormat::With(function)) if !is_transparent => {
+                    let arguments = members.iter().filter_map(|member| {
+                        bound_fields
+                            .iter()
+                            .find(|(m, _)| *m == member)
+                            .map(|(_, name)| name)
+                    });
+                    quote! { #function(#(#arguments,)* #FORMATTER_ARG) }
+                }
+                Some(crate::DisplayFormat::Runtime { format, arguments }) if !is_transparent => {
+                    quote! {
+                        #crate_root::write_runtime_format(
+                            #FORMATTER_ARG,
+                            #format,
+                            &[#(&#arguments as &dyn ::core::fmt::Display),*],
+                        )
+                    }
+                }
+                _ => quote! { write!(#FORMATTER_ARG, #format) },
+            };
 
-            let field_names = quote! { #(ref #field_names),* };
+            // Transparent errors are displayed exactly as their
+            // source is, so they do not get the prefix.
+            let write_prefix = display_prefix.filter(|_| !is_transparent).map(|prefix| {
+                quote! { ::core::fmt::Write::write_str(#FORMATTER_ARG, #prefix)?; }
+            });
+            let write_name = if display_with_name && !is_transparent {
+                Some(quote! {
+                    ::core::fmt::Write::write_str(#FORMATTER_ARG, concat!("[", stringify!(#default_name), "] "))?;
+                })
+            } else {
+                None
+            };
 
             let match_arm = quote! {
                 #pattern_ident { #field_names } => {
-                    write!(#FORMATTER_ARG, #format)
+                    #write_name
+                    #write_prefix
+                    #write_message
                 }
             };
 
@@ -436,20 +1172,62 @@ pub mod error {
     impl ToTokens for ErrorSourceMatchArm<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
-                field_container: FieldContainer { selector_kind, .. },
-                pattern_ident,
+                field_container:
+                    FieldContainer {
+                        selector_kind,
+                        is_transparent,
+                        ..
+                    },
+                pattern_ident,
             } = *self;
 
             let source_field = selector_kind.source_field();
 
             let arm = match source_field {
+                // The value is only shown by `Display`, as it
+                // doesn't implement `Error`.
+                Some(source_field) if source_field.is_display_only => {
+                    quote! {
+                        #pattern_ident { .. } => { ::core::option::Option::None }
+                    }
+                }
+                // The source's message is already used as the display,
+                // so skip straight to its own source.
+                Some(source_field) if *is_transparent => {
+                    let SourceField {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = source_field;
+                    let source = source_field.deref_arc();
+                    quote! {
+                        #pattern_ident { #field_member: ref #field_name, .. } => {
+                            #source.as_error_source().source()
+                        }
+                    }
+                }
+                Some(source_field) if selector_kind.is_whatever() => {
+                    let SourceField {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = source_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref #field_name, .. } => {
+                            #field_name.as_ref().map(|e| e.as_error_source())
+                        }
+                    }
+                }
                 Some(source_field) => {
                     let SourceField {
-                        name: field_name, ..
+                        name: field_name,
+                        member: field_member,
+                        ..
                     } = source_field;
+                    let source = source_field.deref_arc();
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => {
-                            ::core::option::Option::Some(#field_name.as_error_source())
+                        #pattern_ident { #field_member: ref #field_name, .. } => {
+                            ::core::option::Option::Some(#source.as_error_source())
                         }
                     }
                 }
@@ -474,6 +1252,8 @@ pub mod error_compat {
         pub(crate) crate_root: &'a dyn ToTokens,
         pub(crate) parameterized_error_name: &'a dyn ToTokens,
         pub(crate) backtrace_arms: &'a [TokenStream],
+        pub(crate) location_arms: &'a [TokenStream],
+        pub(crate) strip_backtrace_arms: &'a [TokenStream],
         pub(crate) original_generics: &'a [TokenStream],
         pub(crate) where_clauses: &'a [TokenStream],
     }
@@ -484,6 +1264,8 @@ pub mod error_compat {
                 crate_root,
                 parameterized_error_name,
                 backtrace_arms,
+                location_arms,
+                strip_backtrace_arms,
                 original_generics,
                 where_clauses,
             } = *self;
@@ -496,6 +1278,22 @@ pub mod error_compat {
                 }
             };
 
+            let location_fn = quote! {
+                fn location(&self) -> ::core::option::Option<&#crate_root::Location> {
+                    match *self {
+                        #(#location_arms),*
+                    }
+                }
+            };
+
+            let strip_backtrace_fn = quote! {
+                fn strip_backtrace(&mut self) {
+                    match *self {
+                        #(#strip_backtrace_arms),*
+                    }
+                }
+            };
+
             let error_compat_impl = quote! {
                 #[allow(single_use_lifetimes)]
                 impl<#(#original_generics),*> #crate_root::ErrorCompat for #parameterized_error_name
@@ -503,6 +1301,8 @@ pub mod error_compat {
                     #(#where_clauses),*
                 {
                     #backtrace_fn
+                    #location_fn
+                    #strip_backtrace_fn
                 }
             };
 
@@ -523,6 +1323,7 @@ pub mod error_compat {
                 field_container:
                     FieldContainer {
                         backtrace_field,
+                        backtrace_search_chain,
                         selector_kind,
                         ..
                     },
@@ -532,18 +1333,49 @@ pub mod error_compat {
             let match_arm = match (selector_kind.source_field(), backtrace_field) {
                 (Some(source_field), _) if source_field.backtrace_delegate => {
                     let SourceField {
-                        name: field_name, ..
+                        name: field_name,
+                        member: field_member,
+                        ..
                     } = source_field;
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => { #crate_root::ErrorCompat::backtrace(#field_name) }
+                        #pattern_ident { #field_member: ref #field_name, .. } => { #crate_root::ErrorCompat::backtrace(#field_name) }
+                    }
+                }
+                // Our own backtrace is preferred, falling back to the
+                // source's when ours was not captured.
+                (Some(source_field), Some(backtrace_field)) if *backtrace_search_chain => {
+                    let source_name = &source_field.name;
+                    let source_member = &source_field.member;
+                    let Field {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = backtrace_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref #field_name, #source_member: ref #source_name, .. } => {
+                            #crate_root::GenerateBacktrace::as_backtrace(#field_name)
+                                .or_else(|| #crate_root::ErrorCompat::backtrace(#source_name))
+                        }
+                    }
+                }
+                (Some(source_field), None) if *backtrace_search_chain => {
+                    let SourceField {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = source_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref #field_name, .. } => { #crate_root::ErrorCompat::backtrace(#field_name) }
                     }
                 }
                 (_, Some(backtrace_field)) => {
                     let Field {
-                        name: field_name, ..
+                        name: field_name,
+                        member: field_member,
+                        ..
                     } = backtrace_field;
                     quote! {
-                        #pattern_ident { ref #field_name, .. } => { #crate_root::GenerateBacktrace::as_backtrace(#field_name) }
+                        #pattern_ident { #field_member: ref #field_name, .. } => { #crate_root::GenerateBacktrace::as_backtrace(#field_name) }
                     }
                 }
                 _ => {
@@ -556,4 +1388,655 @@ pub mod error_compat {
             stream.extend(match_arm);
         }
     }
+
+    pub(crate) struct ErrorCompatStripBacktraceMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatStripBacktraceMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        backtrace_field,
+                        selector_kind,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let match_arm = match (selector_kind.source_field(), backtrace_field) {
+                (Some(source_field), _) if source_field.backtrace_delegate => {
+                    let SourceField {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = source_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref mut #field_name, .. } => { #crate_root::ErrorCompat::strip_backtrace(#field_name) }
+                    }
+                }
+                (_, Some(backtrace_field)) => {
+                    let Field {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = backtrace_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref mut #field_name, .. } => { *#field_name = #crate_root::GenerateBacktrace::disabled(); }
+                    }
+                }
+                _ => {
+                    quote! {
+                        #pattern_ident { .. } => {}
+                    }
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    pub(crate) struct ErrorCompatLocationMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatLocationMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_container: FieldContainer { location_field, .. },
+                pattern_ident,
+            } = *self;
+
+            let match_arm = match location_field {
+                Some(location_field) => {
+                    let Field {
+                        name: field_name,
+                        member: field_member,
+                        ..
+                    } = location_field;
+                    quote! {
+                        #pattern_ident { #field_member: ref #field_name, .. } => { ::core::option::Option::Some(#field_name) }
+                    }
+                }
+                None => {
+                    quote! {
+                        #pattern_ident { .. } => { ::core::option::Option::None }
+                    }
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod clone {
+    use crate::{FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, quote_spanned, ToTokens};
+    use syn::spanned::Spanned;
+
+    pub(crate) struct Clone<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) type_params: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Clone<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                original_generics,
+                parameterized_error_name,
+                type_params,
+                where_clauses,
+            } = *self;
+
+            let clone_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::clone::Clone for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::clone::Clone,)*
+                {
+                    fn clone(&self) -> Self {
+                        match *self {
+                            #(#arms,)*
+                        }
+                    }
+                }
+            };
+
+            stream.extend(clone_impl);
+        }
+    }
+
+    pub(crate) struct CloneMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for CloneMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        selector_kind,
+                        backtrace_field,
+                        location_field,
+                        default_fields,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let members: Vec<_> = selector_kind
+                .user_fields()
+                .iter()
+                .chain(selector_kind.message_field())
+                .chain(default_fields.iter().map(|f| &f.field))
+                .chain(location_field)
+                .map(crate::Field::member)
+                .chain(selector_kind.source_field().map(SourceField::member))
+                .collect();
+
+            let names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_clone_{}", i))
+                .collect();
+
+            // Report fields that are not `Clone` where they are defined.
+            let clones = members.iter().zip(&names).map(|(member, name)| {
+                quote_spanned! { member.span()=> ::core::clone::Clone::clone(#name) }
+            });
+
+            // Backtraces cannot be cloned, so the copy captures its own.
+            let backtrace_field = backtrace_field.as_ref().map(|field| {
+                let member = field.member();
+                quote! { #member: #crate_root::GenerateBacktrace::generate(), }
+            });
+
+            let match_arm = quote! {
+                #pattern_ident { #(#members: ref #names,)* .. } => #pattern_ident {
+                    #(#members: #clones,)*
+                    #backtrace_field
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod partial_eq {
+    use crate::{FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, ToTokens};
+
+    pub(crate) struct PartialEq<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) type_params: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for PartialEq<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                original_generics,
+                parameterized_error_name,
+                type_params,
+                where_clauses,
+            } = *self;
+
+            let partial_eq_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::cmp::PartialEq for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::cmp::PartialEq,)*
+                {
+                    fn eq(&self, other: &Self) -> bool {
+                        #[allow(unreachable_patterns)]
+                        match (self, other) {
+                            #(#arms,)*
+                            _ => false,
+                        }
+                    }
+                }
+            };
+
+            stream.extend(partial_eq_impl);
+        }
+    }
+
+    pub(crate) struct PartialEqMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for PartialEqMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_container:
+                    FieldContainer {
+                        selector_kind,
+                        default_fields,
+                        uncompared_fields,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            // Backtraces and locations differ between otherwise
+            // identical errors, so they never take part.
+            let members: Vec<_> = selector_kind
+                .user_fields()
+                .iter()
+                .chain(selector_kind.message_field())
+                .chain(default_fields.iter().map(|f| &f.field))
+                .map(crate::Field::member)
+                .chain(selector_kind.source_field().map(SourceField::member))
+                .filter(|member| !uncompared_fields.contains(member))
+                .collect();
+
+            let self_names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_self_{}", i))
+                .collect();
+            let other_names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_other_{}", i))
+                .collect();
+
+            let match_arm = quote! {
+                (
+                    #pattern_ident { #(#members: #self_names,)* .. },
+                    #pattern_ident { #(#members: #other_names,)* .. },
+                ) => true #(&& #self_names == #other_names)*
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod hash {
+    use crate::{FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, ToTokens};
+
+    pub(crate) struct Hash<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) type_params: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Hash<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                original_generics,
+                parameterized_error_name,
+                type_params,
+                where_clauses,
+            } = *self;
+
+            let hash_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> ::core::hash::Hash for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: ::core::hash::Hash,)*
+                {
+                    fn hash<__SnafuHasher>(&self, state: &mut __SnafuHasher)
+                    where
+                        __SnafuHasher: ::core::hash::Hasher,
+                    {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #(#arms,)*
+                            _ => {}
+                        }
+                    }
+                }
+            };
+
+            stream.extend(hash_impl);
+        }
+    }
+
+    pub(crate) struct HashMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+        /// Distinguishes the variants of an enum from each other.
+        pub(crate) discriminant: Option<usize>,
+    }
+
+    impl ToTokens for HashMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_container:
+                    FieldContainer {
+                        selector_kind,
+                        default_fields,
+                        uncompared_fields,
+                        unhashed_fields,
+                        ..
+                    },
+                pattern_ident,
+                discriminant,
+            } = *self;
+
+            // Fields that are not compared must not be hashed either,
+            // otherwise equal errors could have different hashes.
+            let members: Vec<_> = selector_kind
+                .user_fields()
+                .iter()
+                .chain(selector_kind.message_field())
+                .chain(default_fields.iter().map(|f| &f.field))
+                .map(crate::Field::member)
+                .chain(selector_kind.source_field().map(SourceField::member))
+                .filter(|member| !uncompared_fields.contains(member))
+                .filter(|member| !unhashed_fields.contains(member))
+                .collect();
+
+            let names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_{}", i))
+                .collect();
+
+            let discriminant = discriminant.map(|discriminant| {
+                quote! { ::core::hash::Hash::hash(&#discriminant, state); }
+            });
+
+            let match_arm = quote! {
+                #pattern_ident { #(#members: #names,)* .. } => {
+                    #discriminant
+                    #(::core::hash::Hash::hash(#names, state);)*
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod serialize {
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, quote_spanned, ToTokens};
+    use syn::spanned::Spanned;
+
+    pub(crate) struct Serialize<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) type_params: &'a [TokenStream],
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Serialize<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                crate_root,
+                original_generics,
+                parameterized_error_name,
+                type_params,
+                where_clauses,
+            } = *self;
+
+            let serialize_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #crate_root::serde::Serialize for #parameterized_error_name
+                where
+                    #(#where_clauses,)*
+                    #(#type_params: #crate_root::serde::Serialize,)*
+                    Self: #crate_root::Error,
+                {
+                    fn serialize<__SnafuSerializer>(
+                        &self,
+                        serializer: __SnafuSerializer,
+                    ) -> ::core::result::Result<
+                        <__SnafuSerializer as #crate_root::serde::Serializer>::Ok,
+                        <__SnafuSerializer as #crate_root::serde::Serializer>::Error,
+                    >
+                    where
+                        __SnafuSerializer: #crate_root::serde::Serializer,
+                    {
+                        match *self {
+                            #(#arms,)*
+                        }
+                    }
+                }
+            };
+
+            stream.extend(serialize_impl);
+        }
+    }
+
+    pub(crate) struct SerializeMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+        /// The name of the enum or struct.
+        pub(crate) type_name: &'a syn::Ident,
+    }
+
+    impl ToTokens for SerializeMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        name: variant_name,
+                        selector_kind,
+                        location_field,
+                        default_fields,
+                        members: all_members,
+                        ..
+                    },
+                pattern_ident,
+                type_name,
+            } = *self;
+
+            // The source is serialized through `Error::source` and the
+            // backtrace is left out entirely.
+            let serialized: Vec<_> = selector_kind
+                .user_fields()
+                .iter()
+                .chain(selector_kind.message_field())
+                .chain(default_fields.iter().map(|f| &f.field))
+                .chain(location_field)
+                .collect();
+
+            let fields: Vec<_> = all_members
+                .iter()
+                .flat_map(|member| serialized.iter().find(|f| f.member() == member))
+                .collect();
+            let members: Vec<_> = fields.iter().map(|f| f.member()).collect();
+
+            let names: Vec<_> = (0..members.len())
+                .map(|i| format_ident!("__snafu_serialize_{}", i))
+                .collect();
+
+            let keys = members.iter().map(|member| match member {
+                syn::Member::Named(ident) => unraw(ident),
+                syn::Member::Unnamed(index) => index.index.to_string(),
+            });
+
+            let type_name = unraw(type_name);
+            let variant_name = unraw(variant_name);
+
+            let serializer = quote! { #crate_root::serde::Serializer };
+            let serialize_struct = quote! { #crate_root::serde::ser::SerializeStruct };
+
+            // The fields are gathered into a local type so that they
+            // can be serialized as a single nested value. It cannot
+            // name the error's generics, so each field gets its own.
+            let nested_fields = if members.is_empty() {
+                quote! {
+                    struct __SnafuFields;
+
+                    impl #crate_root::serde::Serialize for __SnafuFields {
+                        fn serialize<__SnafuSerializer>(
+                            &self,
+                            serializer: __SnafuSerializer,
+                        ) -> ::core::result::Result<
+                            <__SnafuSerializer as #serializer>::Ok,
+                            <__SnafuSerializer as #serializer>::Error,
+                        >
+                        where
+                            __SnafuSerializer: #serializer,
+                        {
+                            let fields = #serializer::serialize_struct(serializer, #variant_name, 0)?;
+                            #serialize_struct::end(fields)
+                        }
+                    }
+
+                    let fields = __SnafuFields;
+                }
+            } else {
+                let len = members.len();
+                let types: Vec<_> = (0..members.len())
+                    .map(|i| format_ident!("__SnafuField{}", i))
+                    .collect();
+                let indices = (0..members.len()).map(syn::Index::from);
+
+                // Report fields that are not `Serialize` where they are defined.
+                let assertions = fields.iter().map(|field| {
+                    let ty = &field.ty;
+                    quote_spanned! { ty.span()=> __snafu_assert_serialize::<#ty>(); }
+                });
+
+                quote! {
+                    fn __snafu_assert_serialize<T>()
+                    where
+                        T: ?::core::marker::Sized + #crate_root::serde::Serialize,
+                    {
+                    }
+
+                    #(#assertions)*
+
+                    struct __SnafuFields<'__snafu, #(#types: ?::core::marker::Sized),*>(
+                        #(&'__snafu #types),*
+                    );
+
+                    impl<'__snafu, #(#types),*> #crate_root::serde::Serialize
+                        for __SnafuFields<'__snafu, #(#types),*>
+                    where
+                        #(#types: ?::core::marker::Sized + #crate_root::serde::Serialize,)*
+                    {
+                        fn serialize<__SnafuSerializer>(
+                            &self,
+                            serializer: __SnafuSerializer,
+                        ) -> ::core::result::Result<
+                            <__SnafuSerializer as #serializer>::Ok,
+                            <__SnafuSerializer as #serializer>::Error,
+                        >
+                        where
+                            __SnafuSerializer: #serializer,
+                        {
+                            let mut fields = #serializer::serialize_struct(serializer, #variant_name, #len)?;
+                            #(#serialize_struct::serialize_field(&mut fields, #keys, self.#indices)?;)*
+                            #serialize_struct::end(fields)
+                        }
+                    }
+
+                    let fields = __SnafuFields(#(#names),*);
+                }
+            };
+
+            let match_arm = quote! {
+                #pattern_ident { #(#members: ref #names,)* .. } => {
+                    #nested_fields
+
+                    let mut error = #serializer::serialize_struct(serializer, #type_name, 4)?;
+                    #serialize_struct::serialize_field(&mut error, "variant", #variant_name)?;
+                    #serialize_struct::serialize_field(
+                        &mut error,
+                        "message",
+                        &#crate_root::SerializeDisplay(self),
+                    )?;
+                    #serialize_struct::serialize_field(
+                        &mut error,
+                        "source",
+                        &#crate_root::Error::source(self).map(#crate_root::SerializeDisplay),
+                    )?;
+                    #serialize_struct::serialize_field(&mut error, "fields", &fields)?;
+                    #serialize_struct::end(error)
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    fn unraw(ident: &syn::Ident) -> String {
+        ident.to_string().trim_start_matches("r#").to_owned()
+    }
+}
+
+pub mod send_sync {
+    use proc_macro2::TokenStream;
+    use quote::{quote, quote_spanned, ToTokens};
+    use syn::spanned::Spanned;
+
+    pub(crate) struct SendSync<'a> {
+        pub(crate) field_types: &'a [syn::Type],
+        pub(crate) original_generics: &'a [TokenStream],
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for SendSync<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_types,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            // Each assertion is spanned to its field so that the
+            // compiler points at the type that is not thread-safe.
+            let assertions = field_types.iter().map(|ty| {
+                quote_spanned! { ty.span()=>
+                    __snafu_assert_send_sync::<#ty>();
+                }
+            });
+
+            let send_sync_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#(#original_generics),*> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    #[allow(dead_code)]
+                    fn __snafu_assert_send_sync() {
+                        fn __snafu_assert_send_sync<T>()
+                        where
+                            T: ?::core::marker::Sized + ::core::marker::Send + ::core::marker::Sync,
+                        {
+                        }
+
+                        #(#assertions)*
+                    }
+                }
+            };
+
+            stream.extend(send_sync_impl);
+        }
+    }
 }
@@ -12,6 +12,10 @@ impl crate::GenerateBacktrace for Backtrace {
         Backtrace(())
     }
 
+    fn disabled() -> Self {
+        Backtrace(())
+    }
+
     fn as_backtrace(&self) -> Option<&Backtrace> {
         Some(self)
     }
@@ -1,31 +1,276 @@
-use backtrace;
-use std::{fmt, path};
+use std::{
+    env, fmt,
+    path::{self, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, Once, PoisonError,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// A backtrace starting from the beginning of the thread.
 ///
 /// Backtrace functionality is currently **enabled**. Please review
 /// [the feature flags](crate::guide::feature_flags) to disable it.
-#[derive(Debug)]
-pub struct Backtrace(backtrace::Backtrace);
+///
+/// The stack frames are captured when the error is created, but
+/// symbols are only resolved the first time the backtrace is
+/// formatted. Setting the `SNAFU_BACKTRACE` environment variable to
+/// `0` skips capturing entirely.
+///
+/// When displayed, the leading frames that belong to SNAFU itself,
+/// such as those of the generated context selectors, are omitted so
+/// that the first frame shown is the code that created the error.
+/// Setting `SNAFU_BACKTRACE` to `full` shows every frame.
+///
+/// The name of the current thread and the time of capture are also
+/// recorded and included when the backtrace is displayed.
+pub struct Backtrace {
+    inner: Option<Mutex<backtrace::Backtrace>>,
+    thread_name: Option<String>,
+    captured_at: SystemTime,
+}
+
+impl Backtrace {
+    /// The name of the thread that created the backtrace, if it had
+    /// one.
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread_name.as_ref().map(String::as_str)
+    }
+
+    /// The time at which the backtrace was created.
+    pub fn captured_at(&self) -> SystemTime {
+        self.captured_at
+    }
+
+    /// The stack frames of the backtrace, starting with the most
+    /// recent call.
+    ///
+    /// Symbols are resolved as needed and copied out, allowing you to
+    /// write your own formatting. A disabled backtrace has no frames.
+    pub fn frames(&self) -> impl Iterator<Item = BacktraceFrame> {
+        let frames: Vec<_> = match self.resolved() {
+            Some(inner) => inner.frames().iter().map(BacktraceFrame::new).collect(),
+            None => Vec::new(),
+        };
+        frames.into_iter()
+    }
+
+    fn resolved(&self) -> Option<MutexGuard<'_, backtrace::Backtrace>> {
+        self.inner.as_ref().map(|inner| {
+            let mut inner = inner.lock().unwrap_or_else(PoisonError::into_inner);
+            inner.resolve();
+            inner
+        })
+    }
+}
+
+/// A single stack frame of a [`Backtrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    /// The symbols of the frame. There may be more than one when
+    /// functions have been inlined, or none if they could not be
+    /// resolved.
+    pub symbols: Vec<BacktraceSymbol>,
+}
+
+impl BacktraceFrame {
+    fn new(frame: &backtrace::BacktraceFrame) -> Self {
+        BacktraceFrame {
+            symbols: frame.symbols().iter().map(BacktraceSymbol::new).collect(),
+        }
+    }
+}
+
+/// A resolved symbol of a [`BacktraceFrame`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceSymbol {
+    /// The demangled name of the function.
+    pub name: Option<String>,
+    /// The file containing the function.
+    pub filename: Option<PathBuf>,
+    /// The line number within `filename`.
+    pub lineno: Option<u32>,
+}
+
+impl BacktraceSymbol {
+    fn new(symbol: &backtrace::BacktraceSymbol) -> Self {
+        BacktraceSymbol {
+            name: symbol.name().map(|n| n.to_string()),
+            filename: symbol.filename().map(Into::into),
+            lineno: symbol.lineno(),
+        }
+    }
+}
+
+static SETTINGS: Once = Once::new();
+static CAPTURE_ENABLED: AtomicBool = AtomicBool::new(true);
+static SHOW_ALL_FRAMES: AtomicBool = AtomicBool::new(false);
+
+/// Reads `SNAFU_BACKTRACE`.
+///
+/// This value will be tested only once per program execution;
+/// changing the environment variable after it has been checked will
+/// have no effect.
+fn load_settings() {
+    SETTINGS.call_once(|| {
+        let value = env::var_os("SNAFU_BACKTRACE");
+        let value = value.as_ref().and_then(|v| v.to_str());
+        CAPTURE_ENABLED.store(value != Some("0"), Ordering::SeqCst);
+        SHOW_ALL_FRAMES.store(value == Some("full"), Ordering::SeqCst);
+    });
+}
+
+/// Backtraces are captured unless `SNAFU_BACKTRACE` is set to `0`.
+fn capture_enabled() -> bool {
+    load_settings();
+    CAPTURE_ENABLED.load(Ordering::SeqCst)
+}
+
+/// SNAFU's own frames are displayed when `SNAFU_BACKTRACE` is set to
+/// `full`.
+fn show_all_frames() -> bool {
+    load_settings();
+    SHOW_ALL_FRAMES.load(Ordering::SeqCst)
+}
+
+/// The number of leading frames that belong to SNAFU: capturing the
+/// backtrace, the trait implementations that add context, and the
+/// `build` and `fail` methods of the context selectors. The
+/// `backtrace` crate already omits its own frames.
+fn machinery_frames(frames: &[backtrace::BacktraceFrame]) -> usize {
+    enum Stage {
+        // Only the backtrace has been captured.
+        Capturing,
+        // SNAFU's trait implementations, such as `ResultExt::context`
+        // and the `IntoError` implementations of the selectors.
+        Context,
+        // The `build` method of the selector with this path.
+        Built(String),
+        // The method of that selector that called `build`.
+        Failed,
+    }
+
+    fn is_generate(name: &str) -> bool {
+        name.ends_with(" as snafu::GenerateBacktrace>::generate")
+    }
+
+    fn is_snafu(name: &str) -> bool {
+        name.starts_with("snafu::") || name.starts_with("<snafu::") || name.contains(" as snafu::")
+    }
+
+    // Selectors are defined by the user, so their `build` method is
+    // only recognized when it directly captured the backtrace, and
+    // `fail` only when it belongs to the same selector.
+    fn is_method_of(name: &str, selector: &str) -> bool {
+        name.starts_with(selector) && name[selector.len()..].rfind("::") == Some(0)
+    }
+
+    // Inlined functions share a frame, so each symbol is checked in
+    // turn.
+    let mut stage = Stage::Capturing;
+    let mut count = 0;
+
+    for frame in frames {
+        let symbols = frame.symbols();
+        if symbols.is_empty() {
+            break;
+        }
+
+        for symbol in symbols {
+            // The alternate format omits the hash suffix
+            let name = match symbol.name() {
+                Some(name) => format!("{:#}", name),
+                None => return count,
+            };
+
+            stage = match stage {
+                Stage::Capturing if is_generate(&name) => Stage::Capturing,
+                Stage::Capturing | Stage::Context if is_snafu(&name) => Stage::Context,
+                Stage::Capturing if name.ends_with("::build") => {
+                    let selector = &name[..name.len() - "::build".len()];
+                    Stage::Built(selector.to_string())
+                }
+                Stage::Built(ref selector) if is_method_of(&name, selector) => Stage::Failed,
+                _ => return count,
+            };
+        }
+
+        count += 1;
+    }
+
+    count
+}
 
 impl crate::GenerateBacktrace for Backtrace {
     // Inlining in an attempt to remove this function from the backtrace
     #[inline(always)]
     fn generate() -> Self {
-        Backtrace(backtrace::Backtrace::new())
+        let inner = if capture_enabled() {
+            Some(Mutex::new(backtrace::Backtrace::new_unresolved()))
+        } else {
+            None
+        };
+
+        Backtrace {
+            inner,
+            thread_name: thread::current().name().map(Into::into),
+            captured_at: SystemTime::now(),
+        }
+    }
+
+    fn disabled() -> Self {
+        Backtrace {
+            inner: None,
+            thread_name: None,
+            captured_at: UNIX_EPOCH,
+        }
     }
 
     fn as_backtrace(&self) -> Option<&Backtrace> {
-        Some(self)
+        self.inner.as_ref().map(|_| self)
+    }
+}
+
+impl fmt::Debug for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.resolved() {
+            Some(inner) => fmt::Debug::fmt(&*inner, f),
+            None => write!(f, "disabled backtrace"),
+        }
     }
 }
 
 impl fmt::Display for Backtrace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let frames = self.0.frames();
+        let inner = match self.resolved() {
+            Some(inner) => inner,
+            None => return write!(f, "disabled backtrace"),
+        };
+
+        let thread_name = self.thread_name().unwrap_or("<unnamed>");
+        let since_epoch = self
+            .captured_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "Backtrace of thread '{}' captured at {}.{:09} seconds since the Unix epoch",
+            thread_name,
+            since_epoch.as_secs(),
+            since_epoch.subsec_nanos(),
+        )?;
+
+        let frames = inner.frames();
         let width = (frames.len() as f32).log10().floor() as usize + 1;
+        let skip = if show_all_frames() {
+            0
+        } else {
+            machinery_frames(frames)
+        };
 
-        for (index, frame) in frames.iter().enumerate() {
+        for (index, frame) in frames.iter().enumerate().skip(skip) {
             let mut symbols = frame.symbols().iter().map(SymbolDisplay);
 
             if let Some(symbol) = symbols.next() {
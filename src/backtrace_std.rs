@@ -0,0 +1,67 @@
+use std::{backtrace, fmt};
+
+/// A backtrace starting from the beginning of the thread.
+///
+/// Backtrace functionality is currently **enabled** using the
+/// standard library's [`std::backtrace::Backtrace`]. Please review
+/// [the feature flags](crate::guide::feature_flags) to disable it.
+///
+/// Whether a backtrace is captured is controlled by the
+/// `RUST_LIB_BACKTRACE` and `RUST_BACKTRACE` environment variables,
+/// as described by [`std::backtrace::Backtrace::capture`].
+pub struct Backtrace(backtrace::Backtrace);
+
+impl Backtrace {
+    /// Whether the backtrace was captured, disabled, or is not
+    /// supported on this platform.
+    pub fn status(&self) -> backtrace::BacktraceStatus {
+        self.0.status()
+    }
+
+    /// Returns a reference to the standard library's backtrace.
+    pub fn as_std(&self) -> &backtrace::Backtrace {
+        &self.0
+    }
+
+    /// Returns the standard library's backtrace.
+    pub fn into_std(self) -> backtrace::Backtrace {
+        self.0
+    }
+}
+
+impl From<backtrace::Backtrace> for Backtrace {
+    fn from(backtrace: backtrace::Backtrace) -> Self {
+        Backtrace(backtrace)
+    }
+}
+
+impl crate::GenerateBacktrace for Backtrace {
+    // Inlining in an attempt to remove this function from the backtrace
+    #[inline(always)]
+    fn generate() -> Self {
+        Backtrace(backtrace::Backtrace::capture())
+    }
+
+    fn disabled() -> Self {
+        Backtrace(backtrace::Backtrace::disabled())
+    }
+
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        match self.status() {
+            backtrace::BacktraceStatus::Captured => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
@@ -16,8 +16,9 @@ use crate::{Snafu, ResultExt};
 ///
 /// Context selectors for error variants without a `source`, such
 /// as `Leaf`, have methods to construct them, such as
-/// [`Leaf::build`] or [`Leaf::fail`]. The [`ensure`] macro also
-/// accepts these kinds of context selectors.
+/// [`Leaf::build`] or [`Leaf::fail`]. The
+/// [`ensure`](crate::ensure!) macro also accepts these kinds of
+/// context selectors.
 ///
 /// ```
 /// # use snafu::guide::examples::basic::*;
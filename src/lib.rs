@@ -82,12 +82,14 @@
     not(feature = "backtraces"),
     not(feature = "backtraces-impl-backtrace-crate"),
     not(feature = "unstable-backtraces-impl-std"),
+    not(feature = "std_backtrace"),
 ))]
 mod backtrace_inert;
 #[cfg(all(
     not(feature = "backtraces"),
     not(feature = "backtraces-impl-backtrace-crate"),
     not(feature = "unstable-backtraces-impl-std"),
+    not(feature = "std_backtrace"),
 ))]
 pub use crate::backtrace_inert::*;
 
@@ -95,15 +97,31 @@ pub use crate::backtrace_inert::*;
     feature = "backtraces",
     not(feature = "backtraces-impl-backtrace-crate"),
     not(feature = "unstable-backtraces-impl-std"),
+    not(feature = "std_backtrace"),
 ))]
 mod backtrace_shim;
 #[cfg(all(
     feature = "backtraces",
     not(feature = "backtraces-impl-backtrace-crate"),
     not(feature = "unstable-backtraces-impl-std"),
+    not(feature = "std_backtrace"),
 ))]
 pub use crate::backtrace_shim::*;
 
+#[cfg(all(
+    feature = "std_backtrace",
+    not(feature = "backtraces-impl-backtrace-crate"),
+    not(feature = "unstable-backtraces-impl-std"),
+))]
+#[allow(clippy::incompatible_msrv)] // Gated by the feature flag
+mod backtrace_std;
+#[cfg(all(
+    feature = "std_backtrace",
+    not(feature = "backtraces-impl-backtrace-crate"),
+    not(feature = "unstable-backtraces-impl-std"),
+))]
+pub use crate::backtrace_std::*;
+
 #[cfg(feature = "backtraces-impl-backtrace-crate")]
 pub use backtrace::Backtrace;
 
@@ -116,6 +134,15 @@ pub mod futures01;
 #[cfg(feature = "futures")]
 pub mod futures;
 
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use crate::serde_support::SerializeDisplay;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_crate as serde;
+
 pub use snafu_derive::Snafu;
 
 #[cfg(feature = "guide")]
@@ -158,6 +185,7 @@ generate_guide! {
         pub mod opaque;
         pub mod philosophy;
         pub mod structs;
+        pub mod testing;
         pub mod the_macro;
         pub mod upgrading;
 
@@ -167,6 +195,8 @@ generate_guide! {
 
 doc_comment::doctest!("../README.md", readme_tests);
 
+use core::convert::TryFrom;
+
 #[cfg(any(feature = "std", test))]
 #[doc(hidden)]
 pub use std::error::Error;
@@ -204,7 +234,75 @@ macro_rules! ensure {
     };
 }
 
-/// Additions to [`Result`](std::result::Result).
+/// Instantiate and return a stringly-typed error message.
+///
+/// This can be used with the provided [`Whatever`] type or with a
+/// custom error type that uses `snafu(whatever)`.
+///
+/// # Without an underlying error
+///
+/// Provide a format string and any optional arguments. The macro
+/// will unconditionally exit the calling function with an error.
+///
+/// ```rust
+/// use snafu::{whatever, Whatever};
+///
+/// fn example(id: i32) -> Result<(), Whatever> {
+///     if id < 0 {
+///         whatever!("ID {} is invalid", id);
+///     }
+///     Ok(())
+/// }
+///
+/// let e = example(-42).unwrap_err();
+/// assert_eq!(e.to_string(), "ID -42 is invalid");
+/// ```
+///
+/// # With an underlying error
+///
+/// Provide a `Result` as the first argument, followed by a format
+/// string and any optional arguments. If the `Result` is an error,
+/// the formatted string will be appended to the error and the macro
+/// will exit the calling function with an error. If the `Result` is
+/// not an error, the macro will evaluate to the `Ok` value of the
+/// `Result`.
+///
+/// ```rust
+/// use snafu::{whatever, Whatever};
+///
+/// fn example(value: &str) -> Result<i32, Whatever> {
+///     let value = whatever!(value.parse::<i32>(), "{:?} is not a number", value);
+///     Ok(value * 2)
+/// }
+///
+/// assert_eq!(example("21").unwrap(), 42);
+/// let e = example("hello").unwrap_err();
+/// assert_eq!(e.to_string(), r#""hello" is not a number"#);
+/// ```
+///
+/// [`Whatever`]: crate::Whatever
+#[cfg(any(feature = "std", test))]
+#[macro_export]
+macro_rules! whatever {
+    ($fmt:literal $(, $($arg:expr),* $(,)?)?) => {
+        return core::result::Result::Err(
+            $crate::FromString::without_source(format!($fmt $(, $($arg),*)?))
+        );
+    };
+    ($source:expr, $fmt:literal $(, $($arg:expr),* $(,)?)?) => {
+        match $source {
+            core::result::Result::Ok(v) => v,
+            core::result::Result::Err(e) => {
+                return core::result::Result::Err($crate::FromString::with_source(
+                    core::convert::Into::into(e),
+                    format!($fmt $(, $($arg),*)?),
+                ));
+            }
+        }
+    };
+}
+
+/// Additions to [`Result`].
 pub trait ResultExt<T, E>: Sized {
     /// Extend a [`Result`]'s error with additional context-sensitive information.
     ///
@@ -238,7 +336,7 @@ pub trait ResultExt<T, E>: Sized {
     /// ```
     ///
     /// Note that the context selector will call
-    /// [`Into::into`](std::convert::Into::into) on each field, so the types
+    /// [`Into::into`] on each field, so the types
     /// are not required to exactly match.
     fn context<C, E2>(self, context: C) -> Result<T, E2>
     where
@@ -277,317 +375,1992 @@ pub trait ResultExt<T, E>: Sized {
     /// ```
     ///
     /// Note that this *may not* be needed in many cases because the context
-    /// selector will call [`Into::into`](std::convert::Into::into) on each
+    /// selector will call [`Into::into`] on each
     /// field.
+    ///
+    /// The closure is only called when the `Result` is an `Err`. As
+    /// with [`context`](ResultExt::context), any backtrace is only
+    /// captured while the new error is being created, so the `Ok`
+    /// path does no additional work.
     fn with_context<F, C, E2>(self, context: F) -> Result<T, E2>
     where
         F: FnOnce() -> C,
         C: IntoError<E2, Source = E>,
         E2: Error + ErrorCompat;
 
-    #[doc(hidden)]
-    #[deprecated(since = "0.4.0", note = "use ResultExt::context instead")]
-    fn eager_context<C, E2>(self, context: C) -> Result<T, E2>
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, recording `location` as the place the error was
+    /// created.
+    ///
+    /// You will usually want the [`context!`](context!) macro, which
+    /// captures the location of the code that invokes it.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{location, ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ReadFile {
+    ///         source: std::io::Error,
+    ///         location: snafu::Location,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> Result<Vec<u8>, Error> {
+    ///     std::fs::read("/a/file/that/does/not/exist")
+    ///         .context_with_location(ReadFile, location!())
+    /// }
+    /// ```
+    fn context_with_location<C, E2>(self, context: C, location: Location) -> Result<T, E2>
     where
         C: IntoError<E2, Source = E>,
-        E2: Error + ErrorCompat,
-    {
-        self.context(context)
-    }
+        E2: Error + ErrorCompat;
 
-    #[doc(hidden)]
-    #[deprecated(since = "0.4.0", note = "use ResultExt::with_context instead")]
-    fn with_eager_context<F, C, E2>(self, context: F) -> Result<T, E2>
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, then pass a reference to the new error to `f`.
+    ///
+    /// This allows reporting errors, such as to a logging system, at
+    /// the point where context is added without interrupting the
+    /// flow of the `?` operator.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ReadConfig { source: std::io::Error },
+    /// }
+    ///
+    /// fn log_error(e: &Error) {
+    ///     eprintln!("Error: {}", e);
+    /// }
+    ///
+    /// fn example() -> Result<Vec<u8>, Error> {
+    ///     let config = std::fs::read("config.toml").inspect_err_context(ReadConfig, log_error)?;
+    ///     Ok(config)
+    /// }
+    /// ```
+    fn inspect_err_context<C, F, E2>(self, context: C, f: F) -> Result<T, E2>
     where
-        F: FnOnce() -> C,
         C: IntoError<E2, Source = E>,
-        E2: Error + ErrorCompat,
-    {
-        self.with_context(context)
-    }
-}
+        F: FnOnce(&E2),
+        E2: Error + ErrorCompat;
 
-impl<T, E> ResultExt<T, E> for Result<T, E> {
-    fn context<C, E2>(self, context: C) -> Result<T, E2>
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, then pass the new error to `recover` to produce a
+    /// fallback value.
+    ///
+    /// This allows recovering from an error while still recording it,
+    /// such as in a logging system, with the same context as any other
+    /// error.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ReadConfig { source: std::io::Error },
+    /// }
+    ///
+    /// fn example() -> Vec<u8> {
+    ///     std::fs::read("config.toml").unwrap_or_context(ReadConfig, |e: Error| {
+    ///         eprintln!("Using the default configuration: {}", e);
+    ///         Vec::new()
+    ///     })
+    /// }
+    /// ```
+    fn unwrap_or_context<C, F, E2>(self, context: C, recover: F) -> T
     where
         C: IntoError<E2, Source = E>,
-        E2: Error + ErrorCompat,
-    {
-        self.map_err(|error| context.into_error(error))
-    }
+        F: FnOnce(E2) -> T,
+        E2: Error + ErrorCompat;
 
-    fn with_context<F, C, E2>(self, context: F) -> Result<T, E2>
+    /// Replace a [`Result`]'s error with the error built by a context
+    /// selector that has no source, the same as calling the
+    /// selector's `fail` method.
+    ///
+    /// This is useful when the original error carries no useful
+    /// information. Use [`OptionExt::context`] for an [`Option`].
+    ///
+    /// [`Result`]: std::result::Result
+    /// [`Option`]: std::option::Option
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(display("The user ID {:?} is not a number", user_id))]
+    ///     InvalidUserId { user_id: String },
+    /// }
+    ///
+    /// fn parse_user_id(user_id: &str) -> Result<u32, Error> {
+    ///     user_id.parse().or_fail_with(InvalidUserId { user_id })
+    /// }
+    ///
+    /// assert_eq!(parse_user_id("42").unwrap(), 42);
+    /// assert_eq!(
+    ///     parse_user_id("alice").unwrap_err().to_string(),
+    ///     r#"The user ID "alice" is not a number"#,
+    /// );
+    /// ```
+    fn or_fail_with<C, E2>(self, context: C) -> Result<T, E2>
     where
-        F: FnOnce() -> C,
-        C: IntoError<E2, Source = E>,
-        E2: Error + ErrorCompat,
-    {
-        self.map_err(|error| {
-            let context = context();
-            context.into_error(error)
-        })
-    }
-}
-
-/// A temporary error type used when converting an [`Option`][] into a
-/// [`Result`][]
-///
-/// [`Option`]: std::option::Option
-/// [`Result`]: std::result::Result
-pub struct NoneError;
+        C: IntoError<E2, Source = NoneError>,
+        E2: Error + ErrorCompat;
 
-/// Additions to [`Option`](std::option::Option).
-pub trait OptionExt<T>: Sized {
-    /// Convert an [`Option`][] into a [`Result`][] with additional
-    /// context-sensitive information.
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, but only when `predicate` returns `true` for the
+    /// error.
     ///
-    /// [Option]: std::option::Option
-    /// [Result]: std::option::Result
+    /// When the predicate returns `false`, the original error is
+    /// converted into the new error type with [`Into::into`][]
+    /// instead. An implementation of `From` is created for variants
+    /// marked with `#[snafu(context(false))]`.
+    ///
+    /// [`Result`]: std::result::Result
+    /// [`Into::into`]: std::convert::Into::into
     ///
     /// ```rust
-    /// use snafu::{OptionExt, Snafu};
+    /// use snafu::{ResultExt, Snafu};
+    /// use std::io;
     ///
     /// #[derive(Debug, Snafu)]
     /// enum Error {
-    ///     UserLookup { user_id: i32 },
-    /// }
+    ///     #[snafu(context(false))]
+    ///     Io { source: io::Error },
     ///
-    /// fn example(user_id: i32) -> Result<(), Error> {
-    ///     let name = username(user_id).context(UserLookup { user_id })?;
-    ///     println!("Username was {}", name);
-    ///     Ok(())
+    ///     MissingConfig { path: String, source: io::Error },
     /// }
     ///
-    /// fn username(user_id: i32) -> Option<String> {
-    ///     /* ... */
-    /// # None
+    /// fn example() -> Result<Vec<u8>, Error> {
+    ///     std::fs::read("config.toml").context_if(
+    ///         |e| e.kind() == io::ErrorKind::NotFound,
+    ///         MissingConfig { path: "config.toml" },
+    ///     )
     /// }
     /// ```
-    ///
-    /// Note that the context selector will call
-    /// [`Into::into`](std::convert::Into::into) on each field, so the types
-    /// are not required to exactly match.
-    fn context<C, E>(self, context: C) -> Result<T, E>
+    fn context_if<P, C, E2>(self, predicate: P, context: C) -> Result<T, E2>
     where
-        C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat;
+        P: FnOnce(&E) -> bool,
+        C: IntoError<E2, Source = E>,
+        E: Into<E2>,
+        E2: Error + ErrorCompat;
 
-    /// Convert an [`Option`][] into a [`Result`][] with
-    /// lazily-generated context-sensitive information.
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, first converting the error into the context
+    /// selector's source type with [`TryFrom`][].
+    ///
+    /// Context selectors always build their error from a source that
+    /// has already been converted; `#[snafu(source(from(...)))]` must
+    /// be infallible. When only some errors can become the source,
+    /// this method performs the fallible conversion up front and
+    /// returns the conversion error in the outer `Result`. The inner
+    /// `Result` is what [`ResultExt::context`][] would have returned.
+    ///
+    /// The nesting means callers have to decide what to do with a
+    /// failed conversion instead of having it silently discarded;
+    /// usually this is another `?` or a call to
+    /// [`Result::unwrap_or_else`][].
     ///
-    /// [`Option`]: std::option::Option
     /// [`Result`]: std::result::Result
+    /// [`TryFrom`]: core::convert::TryFrom
+    /// [`Result::unwrap_or_else`]: std::result::Result::unwrap_or_else
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    /// use std::convert::TryFrom;
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// # impl std::fmt::Display for Timeout {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    /// #         write!(f, "timed out")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for Timeout {}
+    /// impl TryFrom<std::io::Error> for Timeout {
+    ///     type Error = std::io::Error;
+    ///
+    ///     fn try_from(e: std::io::Error) -> Result<Self, Self::Error> {
+    ///         match e.kind() {
+    ///             std::io::ErrorKind::TimedOut => Ok(Timeout),
+    ///             _ => Err(e),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Connect { host: String, source: Timeout },
+    /// }
+    ///
+    /// fn connect(host: &str) -> Result<Result<(), Error>, std::io::Error> {
+    ///     let result: Result<(), std::io::Error> =
+    ///         Err(std::io::ErrorKind::TimedOut.into());
+    ///     result.try_context(Connect { host })
+    /// }
     ///
+    /// assert!(connect("example.com").unwrap().is_err());
     /// ```
-    /// use snafu::{OptionExt, Snafu};
+    fn try_context<C, E2>(
+        self,
+        context: C,
+    ) -> Result<Result<T, E2>, <C::Source as TryFrom<E>>::Error>
+    where
+        C: IntoError<E2>,
+        C::Source: TryFrom<E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Result`]'s error with two layers of context at
+    /// once.
+    ///
+    /// The error becomes the source of the `inner` context, and that
+    /// error in turn becomes the source of the `outer` context. This
+    /// is the same as calling [`ResultExt::context`][] twice, but
+    /// avoids naming the intermediate error type.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum ConfigError {
+    ///     #[snafu(display("Could not read {}", path))]
+    ///     Read { path: String, source: std::io::Error },
+    /// }
     ///
     /// #[derive(Debug, Snafu)]
     /// enum Error {
-    ///     UserLookup {
-    ///         user_id: i32,
-    ///         previous_ids: Vec<i32>,
-    ///     },
+    ///     #[snafu(display("Could not start the server"))]
+    ///     Startup { source: ConfigError },
     /// }
     ///
-    /// fn example(user_id: i32) -> Result<(), Error> {
-    ///     let name = username(user_id).with_context(|| UserLookup {
-    ///         user_id,
-    ///         previous_ids: Vec::new(),
-    ///     })?;
-    ///     println!("Username was {}", name);
-    ///     Ok(())
+    /// fn example() -> Result<Vec<u8>, Error> {
+    ///     std::fs::read("/does/not/exist").context2(Read { path: "/does/not/exist" }, Startup)
     /// }
     ///
-    /// fn username(user_id: i32) -> Option<String> {
-    ///     /* ... */
-    /// # None
+    /// let e = example().unwrap_err();
+    /// assert!(matches!(e, Error::Startup { source: ConfigError::Read { .. } }));
+    /// ```
+    fn context2<C1, C2, E2, E3>(self, inner: C1, outer: C2) -> Result<T, E3>
+    where
+        C1: IntoError<E2, Source = E>,
+        C2: IntoError<E3, Source = E2>,
+        E2: Error + ErrorCompat,
+        E3: Error + ErrorCompat;
+
+    /// Transform a [`Result`]'s value with `map`, or extend its error
+    /// with additional context-sensitive information.
+    ///
+    /// This is the same as calling [`Result::map`][] followed by
+    /// [`ResultExt::context`][].
+    ///
+    /// [`Result`]: std::result::Result
+    /// [`Result::map`]: std::result::Result::map
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ReadConfig { path: String, source: std::io::Error },
+    /// }
+    ///
+    /// fn config_len(path: &str) -> Result<usize, Error> {
+    ///     std::fs::read(path).map_context(|data| data.len(), ReadConfig { path })
     /// }
     /// ```
+    fn map_context<U, M, C, E2>(self, map: M, context: C) -> Result<U, E2>
+    where
+        M: FnOnce(T) -> U,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Convert a [`Result`]'s error into a boxed trait object
+    /// compatible with multiple threads.
     ///
-    /// Note that this *may not* be needed in many cases because the context
-    /// selector will call [`Into::into`](std::convert::Into::into) on each
-    /// field.
-    fn with_context<F, C, E>(self, context: F) -> Result<T, E>
+    /// This is useful when you have errors of multiple types that you
+    /// wish to treat as one type, such as a single variant with a
+    /// `source` field of `Box<dyn Error + Send + Sync>`:
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Parsing {
+    ///         input: String,
+    ///         source: Box<dyn std::error::Error + Send + Sync>,
+    ///     },
+    /// }
+    ///
+    /// fn example(a: &str, b: &str) -> Result<(i32, f64), Error> {
+    ///     let a = a.parse::<i32>().boxed().context(Parsing { input: a })?;
+    ///     let b = b.parse::<f64>().boxed().context(Parsing { input: b })?;
+    ///     Ok((a, b))
+    /// }
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn boxed<'a>(self) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
     where
-        F: FnOnce() -> C,
-        C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat;
+        E: Error + Send + Sync + 'a;
 
-    #[doc(hidden)]
-    #[deprecated(since = "0.4.0", note = "use OptionExt::context instead")]
-    fn eager_context<C, E>(self, context: C) -> Result<T, E>
+    /// Convert a [`Result`]'s error into a boxed trait object.
+    ///
+    /// This is the same as [`ResultExt::boxed`][], but for errors
+    /// that cannot be sent to or shared between threads.
+    ///
+    /// [`Result`]: std::result::Result
+    #[cfg(any(feature = "std", test))]
+    fn boxed_local<'a>(self) -> Result<T, Box<dyn Error + 'a>>
     where
-        C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat,
-    {
-        self.context(context).map_err(Into::into)
-    }
+        E: Error + 'a;
 
-    #[doc(hidden)]
-    #[deprecated(since = "0.4.0", note = "use OptionExt::with_context instead")]
-    fn with_eager_context<F, C, E>(self, context: F) -> Result<T, E>
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information and convert it into a boxed trait object.
+    ///
+    /// This is useful in application code, such as `main`, that
+    /// does not need to know the concrete error type:
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ReadConfig { source: std::io::Error },
+    /// }
+    ///
+    /// fn load_config() -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    ///     std::fs::read("config.toml").boxed_context(ReadConfig)
+    /// }
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn boxed_context<'a, C, E2>(self, context: C) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat + Send + Sync + 'a;
+
+    /// Extend a [`Result`]'s error with additional context-sensitive
+    /// information, first converting the error into a boxed trait
+    /// object compatible with multiple threads.
+    ///
+    /// This is the same as calling [`ResultExt::boxed`][] followed
+    /// by [`ResultExt::context`][] and is useful for hiding the type
+    /// of the source at the boundary of a public API:
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// pub struct Error {
+    ///     path: String,
+    ///     source: Box<dyn std::error::Error + Send + Sync>,
+    /// }
+    ///
+    /// pub fn read_config() -> Result<Vec<u8>, Error> {
+    ///     std::fs::read("config.toml").context_boxed_source(Context { path: "config.toml" })
+    /// }
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn context_boxed_source<'a, C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = Box<dyn Error + Send + Sync + 'a>>,
+        E: Error + Send + Sync + 'a,
+        E2: Error + ErrorCompat;
+
+    /// Extend a [`Result`]'s boxed error with additional
+    /// context-sensitive information, choosing the context selector
+    /// based on the concrete type of the error.
+    ///
+    /// When the boxed error is a `D`, `matched` is used; otherwise
+    /// `fallback` is. Both selectors take the boxed error as their
+    /// source and must build the same error type, usually two
+    /// variants of one enum. The source keeps its boxed type; use
+    /// [`downcast_ref`][] on the source to access the concrete error.
+    ///
+    /// [`Result`]: std::result::Result
+    /// [`downcast_ref`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.downcast_ref
+    ///
+    /// ```rust
+    /// use snafu::{ResultExt, Snafu};
+    /// use std::{error::Error as StdError, io};
+    ///
+    /// type BoxError = Box<dyn StdError + Send + Sync>;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     #[snafu(display("Could not reach the plugin"))]
+    ///     PluginIo { source: BoxError },
+    ///
+    ///     #[snafu(display("The plugin failed"))]
+    ///     Plugin { source: BoxError },
+    /// }
+    ///
+    /// fn run_plugin() -> Result<(), BoxError> {
+    ///     Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed").into())
+    /// }
+    ///
+    /// fn example() -> Result<(), Error> {
+    ///     run_plugin().context_downcast::<io::Error, _, _, _>(PluginIo, Plugin)
+    /// }
+    ///
+    /// assert!(matches!(example(), Err(Error::PluginIo { .. })));
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn context_downcast<D, C, F, E2>(self, matched: C, fallback: F) -> Result<T, E2>
+    where
+        D: Error + 'static,
+        E: core::ops::Deref,
+        E::Target: AsErrorSource,
+        C: IntoError<E2, Source = E>,
+        F: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    #[doc(hidden)]
+    #[deprecated(since = "0.4.0", note = "use ResultExt::context instead")]
+    fn eager_context<C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        self.context(context)
+    }
+
+    #[doc(hidden)]
+    #[deprecated(since = "0.4.0", note = "use ResultExt::with_context instead")]
+    fn with_eager_context<F, C, E2>(self, context: F) -> Result<T, E2>
     where
         F: FnOnce() -> C,
-        C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
     {
-        self.with_context(context).map_err(Into::into)
+        self.with_context(context)
     }
 }
 
-impl<T> OptionExt<T> for Option<T> {
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    // These avoid closures so that `#[track_caller]` can see through
+    // them to the code that added the context.
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context<C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => Err(context.into_error(error)),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn with_context<F, C, E2>(self, context: F) -> Result<T, E2>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => {
+                let context = context();
+                Err(context.into_error(error))
+            }
+        }
+    }
+
+    fn context_with_location<C, E2>(self, context: C, location: Location) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => Err(context.into_error_with_location(error, location)),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn inspect_err_context<C, F, E2>(self, context: C, f: F) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = E>,
+        F: FnOnce(&E2),
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => {
+                let error = context.into_error(error);
+                f(&error);
+                Err(error)
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn unwrap_or_context<C, F, E2>(self, context: C, recover: F) -> T
+    where
+        C: IntoError<E2, Source = E>,
+        F: FnOnce(E2) -> T,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => v,
+            Err(error) => recover(context.into_error(error)),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn or_fail_with<C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = NoneError>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(_) => Err(context.into_error(NoneError)),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context_if<P, C, E2>(self, predicate: P, context: C) -> Result<T, E2>
+    where
+        P: FnOnce(&E) -> bool,
+        C: IntoError<E2, Source = E>,
+        E: Into<E2>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => {
+                if predicate(&error) {
+                    Err(context.into_error(error))
+                } else {
+                    Err(error.into())
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn try_context<C, E2>(
+        self,
+        context: C,
+    ) -> Result<Result<T, E2>, <C::Source as TryFrom<E>>::Error>
+    where
+        C: IntoError<E2>,
+        C::Source: TryFrom<E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(Ok(v)),
+            Err(error) => {
+                let source = C::Source::try_from(error)?;
+                Ok(Err(context.into_error(source)))
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context2<C1, C2, E2, E3>(self, inner: C1, outer: C2) -> Result<T, E3>
+    where
+        C1: IntoError<E2, Source = E>,
+        C2: IntoError<E3, Source = E2>,
+        E2: Error + ErrorCompat,
+        E3: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => Err(outer.into_error(inner.into_error(error))),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn map_context<U, M, C, E2>(self, map: M, context: C) -> Result<U, E2>
+    where
+        M: FnOnce(T) -> U,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(map(v)),
+            Err(error) => Err(context.into_error(error)),
+        }
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn boxed<'a>(self) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
+    where
+        E: Error + Send + Sync + 'a,
+    {
+        self.map_err(|error| Box::new(error) as _)
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn boxed_local<'a>(self) -> Result<T, Box<dyn Error + 'a>>
+    where
+        E: Error + 'a,
+    {
+        self.map_err(|error| Box::new(error) as _)
+    }
+
+    #[cfg(any(feature = "std", test))]
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn boxed_context<'a, C, E2>(self, context: C) -> Result<T, Box<dyn Error + Send + Sync + 'a>>
+    where
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat + Send + Sync + 'a,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => Err(Box::new(context.into_error(error))),
+        }
+    }
+
+    #[cfg(any(feature = "std", test))]
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context_boxed_source<'a, C, E2>(self, context: C) -> Result<T, E2>
+    where
+        C: IntoError<E2, Source = Box<dyn Error + Send + Sync + 'a>>,
+        E: Error + Send + Sync + 'a,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => Err(context.into_error(Box::new(error))),
+        }
+    }
+
+    #[cfg(any(feature = "std", test))]
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context_downcast<D, C, F, E2>(self, matched: C, fallback: F) -> Result<T, E2>
+    where
+        D: Error + 'static,
+        E: core::ops::Deref,
+        E::Target: AsErrorSource,
+        C: IntoError<E2, Source = E>,
+        F: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(error) => {
+                if error.as_error_source().is::<D>() {
+                    Err(matched.into_error(error))
+                } else {
+                    Err(fallback.into_error(error))
+                }
+            }
+        }
+    }
+}
+
+/// A temporary error type used when converting an [`Option`][] into a
+/// [`Result`][]
+///
+/// [`Option`]: std::option::Option
+/// [`Result`]: std::result::Result
+pub struct NoneError;
+
+/// Additions to [`Option`].
+pub trait OptionExt<T>: Sized {
+    /// Convert an [`Option`][] into a [`Result`][] with additional
+    /// context-sensitive information.
+    ///
+    /// [`Option`]: std::option::Option
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{OptionExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     UserLookup { user_id: i32 },
+    /// }
+    ///
+    /// fn example(user_id: i32) -> Result<(), Error> {
+    ///     let name = username(user_id).context(UserLookup { user_id })?;
+    ///     println!("Username was {}", name);
+    ///     Ok(())
+    /// }
+    ///
+    /// fn username(user_id: i32) -> Option<String> {
+    ///     /* ... */
+    /// # None
+    /// }
+    /// ```
+    ///
+    /// Note that the context selector will call
+    /// [`Into::into`] on each field, so the types
+    /// are not required to exactly match.
     fn context<C, E>(self, context: C) -> Result<T, E>
     where
         C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat,
-    {
-        self.ok_or_else(|| context.into_error(NoneError))
+        E: Error + ErrorCompat;
+
+    /// Convert an [`Option`][] into a [`Result`][] with
+    /// lazily-generated context-sensitive information.
+    ///
+    /// [`Option`]: std::option::Option
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```
+    /// use snafu::{OptionExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     UserLookup {
+    ///         user_id: i32,
+    ///         previous_ids: Vec<i32>,
+    ///     },
+    /// }
+    ///
+    /// fn example(user_id: i32) -> Result<(), Error> {
+    ///     let name = username(user_id).with_context(|| UserLookup {
+    ///         user_id,
+    ///         previous_ids: Vec::new(),
+    ///     })?;
+    ///     println!("Username was {}", name);
+    ///     Ok(())
+    /// }
+    ///
+    /// fn username(user_id: i32) -> Option<String> {
+    ///     /* ... */
+    /// # None
+    /// }
+    /// ```
+    ///
+    /// Note that this *may not* be needed in many cases because the context
+    /// selector will call [`Into::into`] on each
+    /// field.
+    fn with_context<F, C, E>(self, context: F) -> Result<T, E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat;
+
+    #[doc(hidden)]
+    #[deprecated(since = "0.4.0", note = "use OptionExt::context instead")]
+    fn eager_context<C, E>(self, context: C) -> Result<T, E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        self.context(context)
+    }
+
+    #[doc(hidden)]
+    #[deprecated(since = "0.4.0", note = "use OptionExt::with_context instead")]
+    fn with_eager_context<F, C, E>(self, context: F) -> Result<T, E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        self.with_context(context)
+    }
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn context<C, E>(self, context: C) -> Result<T, E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(context.into_error(NoneError)),
+        }
+    }
+
+    #[cfg_attr(feature = "rust_1_46", track_caller)]
+    fn with_context<F, C, E>(self, context: F) -> Result<T, E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(context().into_error(NoneError)),
+        }
+    }
+}
+
+/// Additions to [`Iterator`]s of [`Result`]s.
+pub trait IteratorExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Extend each error produced by the iterator with the same
+    /// context-sensitive information.
+    ///
+    /// The context selector is cloned for each error; successful
+    /// values are passed through untouched.
+    ///
+    /// ```rust
+    /// use snafu::{IteratorExt, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     ParseBatch {
+    ///         batch: u32,
+    ///         source: std::num::ParseIntError,
+    ///     },
+    /// }
+    ///
+    /// fn example(lines: &[&str]) -> Result<Vec<u8>, Error> {
+    ///     lines
+    ///         .iter()
+    ///         .map(|line| line.parse())
+    ///         .contexts(ParseBatch { batch: 7_u32 })
+    ///         .collect()
+    /// }
+    ///
+    /// assert_eq!(example(&["1", "2"]).unwrap(), [1, 2]);
+    /// assert!(example(&["1", "two"]).is_err());
+    /// ```
+    ///
+    /// Note that the context selector will call
+    /// [`Into::into`] on each field, so the types
+    /// are not required to exactly match.
+    fn contexts<C, E2>(self, context: C) -> Contexts<Self, C, E2>
+    where
+        C: IntoError<E2, Source = E> + Clone,
+        E2: Error + ErrorCompat;
+}
+
+impl<I, T, E> IteratorExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    fn contexts<C, E2>(self, context: C) -> Contexts<Self, C, E2>
+    where
+        C: IntoError<E2, Source = E> + Clone,
+        E2: Error + ErrorCompat,
+    {
+        Contexts {
+            iter: self,
+            context,
+            _error: core::marker::PhantomData,
+        }
+    }
+}
+
+/// An iterator that adds context to each error of another iterator.
+///
+/// This is created by [`IteratorExt::contexts`].
+#[derive(Debug, Clone)]
+pub struct Contexts<I, C, E2> {
+    iter: I,
+    context: C,
+    _error: core::marker::PhantomData<fn() -> E2>,
+}
+
+impl<I, T, E, C, E2> Iterator for Contexts<I, C, E2>
+where
+    I: Iterator<Item = Result<T, E>>,
+    C: IntoError<E2, Source = E> + Clone,
+    E2: Error + ErrorCompat,
+{
+    type Item = Result<T, E2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let context = &self.context;
+        self.iter
+            .next()
+            .map(|item| item.map_err(|error| context.clone().into_error(error)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Backports changes to the [`Error`] trait to
+/// versions of Rust lacking them.
+///
+/// It is recommended to always call these methods explicitly so that
+/// it is easy to replace usages of this trait when you start
+/// supporting a newer version of Rust.
+///
+/// ```
+/// # use snafu::{Snafu, ErrorCompat};
+/// # #[derive(Debug, Snafu)] enum Example {};
+/// # fn example(error: Example) {
+/// ErrorCompat::backtrace(&error); // Recommended
+/// error.backtrace();              // Discouraged
+/// # }
+/// ```
+pub trait ErrorCompat {
+    /// Returns a [`Backtrace`] that may be printed.
+    fn backtrace(&self) -> Option<&Backtrace> {
+        None
+    }
+
+    /// Returns the [`Location`] where the error was created.
+    fn location(&self) -> Option<&Location> {
+        None
+    }
+
+    /// Returns an iterator for traversing the chain of errors,
+    /// starting with the current error
+    /// and continuing with recursive calls to `Error::source`.
+    ///
+    /// To omit the current error and only traverse its sources,
+    /// use `skip(1)`.
+    fn iter_chain(&self) -> ChainCompat<'_>
+    where
+        Self: AsErrorSource,
+    {
+        ChainCompat::new(self.as_error_source())
+    }
+
+    /// Returns the last error in the chain of sources, which is the
+    /// current error if it has no source.
+    fn root_cause(&self) -> &(dyn Error + 'static)
+    where
+        Self: AsErrorSource,
+    {
+        let mut error = self.as_error_source();
+        while let Some(source) = error.source() {
+            error = source;
+        }
+        error
+    }
+
+    /// Replaces any [`Backtrace`] with a disabled one, such
+    /// as before serializing or logging the error.
+    ///
+    /// Errors that delegate their backtrace to their source strip the
+    /// source's backtrace instead. Backtrace fields are reset using
+    /// [`GenerateBacktrace::disabled`][].
+    fn strip_backtrace(&mut self) {}
+
+    /// Returns the error after calling
+    /// [`strip_backtrace`](ErrorCompat::strip_backtrace) on it.
+    ///
+    /// ```rust
+    /// use snafu::{ErrorCompat, Snafu};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Request { backtrace: Option<snafu::Backtrace> },
+    /// }
+    ///
+    /// let e = ErrorCompat::without_backtrace(Request.build());
+    /// assert!(ErrorCompat::backtrace(&e).is_none());
+    /// ```
+    fn without_backtrace(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.strip_backtrace();
+        self
+    }
+}
+
+impl<E> ErrorCompat for &E
+where
+    E: ErrorCompat,
+{
+    fn backtrace(&self) -> Option<&Backtrace> {
+        (**self).backtrace()
+    }
+
+    fn location(&self) -> Option<&Location> {
+        (**self).location()
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<E> ErrorCompat for Box<E>
+where
+    E: ErrorCompat,
+{
+    fn backtrace(&self) -> Option<&Backtrace> {
+        (**self).backtrace()
+    }
+
+    fn location(&self) -> Option<&Location> {
+        (**self).location()
+    }
+
+    fn strip_backtrace(&mut self) {
+        (**self).strip_backtrace()
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<E> ErrorCompat for std::sync::Arc<E>
+where
+    E: ErrorCompat,
+{
+    fn backtrace(&self) -> Option<&Backtrace> {
+        (**self).backtrace()
+    }
+
+    fn location(&self) -> Option<&Location> {
+        (**self).location()
+    }
+
+    // The error can only be changed while this is the sole reference
+    // to it; shared errors keep their backtrace.
+    fn strip_backtrace(&mut self) {
+        if let Some(error) = std::sync::Arc::get_mut(self) {
+            error.strip_backtrace()
+        }
+    }
+}
+
+/// An iterator over an Error and its sources.
+///
+/// If you want to omit the initial error and only process its sources, use `skip(1)`.
+///
+/// Can be created via [`ErrorCompat::iter_chain`][].
+///
+/// ```rust
+/// use snafu::{ChainCompat, ErrorCompat, ResultExt, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load the configuration: {}", source))]
+///     LoadConfig { source: std::num::ParseIntError },
+/// }
+///
+/// let e = "nope".parse::<u8>().context(LoadConfig).unwrap_err();
+///
+/// for (depth, error) in e.iter_chain().enumerate() {
+///     eprintln!("{}: {}", depth, error);
+/// }
+///
+/// assert_eq!(ChainCompat::new(&e).count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChainCompat<'a> {
+    inner: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> ChainCompat<'a> {
+    /// Creates a new error chain iterator.
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        ChainCompat { inner: Some(error) }
+    }
+}
+
+impl<'a> Iterator for ChainCompat<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner {
+            None => None,
+            Some(e) => {
+                self.inner = e.source();
+                Some(e)
+            }
+        }
+    }
+}
+
+/// Displays an error followed by each of its sources, separated by
+/// `": "` unless [customized](DisplayChain::separator).
+///
+/// This is useful in a display format to include the entire chain of
+/// sources instead of only the immediate one, without each error having
+/// to append its own source:
+///
+/// ```rust
+/// use snafu::{DisplayChain, ResultExt, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Could not parse the port"))]
+/// struct ParsePortError {
+///     source: std::num::ParseIntError,
+/// }
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load the configuration: {}", DisplayChain::new(source)))]
+///     LoadConfig { source: ParsePortError },
+/// }
+///
+/// let e = "nope"
+///     .parse::<u16>()
+///     .context(ParsePortContext)
+///     .context(LoadConfig)
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     e.to_string(),
+///     "Could not load the configuration: Could not parse the port: invalid digit found in string",
+/// );
+/// ```
+///
+/// Errors that already include their source in their own `Display`
+/// output, such as those using the default display format, will
+/// appear more than once.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayChain<'a> {
+    error: &'a (dyn Error + 'a),
+    separator: &'a str,
+}
+
+impl<'a> DisplayChain<'a> {
+    /// Creates a display for the error and each of its sources.
+    pub fn new(error: &'a (dyn Error + 'a)) -> Self {
+        DisplayChain {
+            error,
+            separator: ": ",
+        }
+    }
+
+    /// Sets the text written between each error.
+    pub fn separator(self, separator: &'a str) -> Self {
+        DisplayChain { separator, ..self }
+    }
+}
+
+impl<'a> core::fmt::Display for DisplayChain<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        let mut source = self.error.source();
+        while let Some(cause) = source {
+            write!(f, "{}{}", self.separator, cause)?;
+            source = cause.source();
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts the receiver into an [`Error`][] trait object, suitable
+/// for use in [`Error::source`][].
+///
+/// It is expected that most users of SNAFU will not directly interact
+/// with this trait.
+///
+/// [`Error`]: std::error::Error
+/// [`Error::source`]: std::error::Error::source
+//
+// Given an error enum with multiple types of underlying causes:
+//
+// ```rust
+// enum Error {
+//     BoxTraitObjectSendSync(Box<dyn error::Error + Send + Sync + 'static>),
+//     BoxTraitObject(Box<dyn error::Error + 'static>),
+//     Boxed(Box<io::Error>),
+//     Unboxed(io::Error),
+// }
+// ```
+//
+// This trait provides the answer to what consistent expression can go
+// in each match arm:
+//
+// ```rust
+// impl error::Error for Error {
+//     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+//         use Error::*;
+//
+//         let v = match *self {
+//             BoxTraitObjectSendSync(ref e) => ...,
+//             BoxTraitObject(ref e) => ...,
+//             Boxed(ref e) => ...,
+//             Unboxed(ref e) => ...,
+//         };
+//
+//         Some(v)
+//     }
+// }
+//
+// Existing methods like returning `e`, `&**e`, `Borrow::borrow(e)`,
+// `Deref::deref(e)`, and `AsRef::as_ref(e)` do not work for various
+// reasons.
+pub trait AsErrorSource {
+    /// For maximum effectiveness, this needs to be called as a method
+    /// to benefit from Rust's automatic dereferencing of method
+    /// receivers.
+    fn as_error_source(&self) -> &(dyn Error + 'static);
+}
+
+impl AsErrorSource for dyn Error + 'static {
+    fn as_error_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsErrorSource for dyn Error + Send + 'static {
+    fn as_error_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsErrorSource for dyn Error + Sync + 'static {
+    fn as_error_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsErrorSource for dyn Error + Send + Sync + 'static {
+    fn as_error_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl<T: Error + 'static> AsErrorSource for T {
+    fn as_error_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+/// Combines an underlying error with additional information
+/// about the error.
+///
+/// It is expected that most users of SNAFU will not directly interact
+/// with this trait.
+///
+/// Implementing it for your own types allows [`ResultExt::context`]
+/// to produce them. For example, a `Tagged` wrapper can receive the
+/// error created by any context selector:
+///
+/// ```rust
+/// use snafu::{ErrorCompat, IntoError, Location, ResultExt, Snafu};
+/// use std::{error, fmt};
+///
+/// #[derive(Debug)]
+/// struct Tagged<E> {
+///     tag: &'static str,
+///     error: E,
+/// }
+///
+/// impl<E: fmt::Display> fmt::Display for Tagged<E> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "[{}] {}", self.tag, self.error)
+///     }
+/// }
+///
+/// impl<E: error::Error + 'static> error::Error for Tagged<E> {
+///     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+///         Some(&self.error)
+///     }
+/// }
+///
+/// impl<E: ErrorCompat> ErrorCompat for Tagged<E> {
+///     fn backtrace(&self) -> Option<&snafu::Backtrace> {
+///         self.error.backtrace()
+///     }
+/// }
+///
+/// /// Wraps a context selector, tagging the error it creates.
+/// struct Tag<C>(&'static str, C);
+///
+/// impl<C, E> IntoError<Tagged<E>> for Tag<C>
+/// where
+///     C: IntoError<E>,
+///     E: error::Error + ErrorCompat + 'static,
+/// {
+///     type Source = C::Source;
+///
+///     fn into_error(self, source: Self::Source) -> Tagged<E> {
+///         Tagged {
+///             tag: self.0,
+///             error: self.1.into_error(source),
+///         }
+///     }
+///
+///     fn into_error_with_location(self, source: Self::Source, location: Location) -> Tagged<E> {
+///         Tagged {
+///             tag: self.0,
+///             error: self.1.into_error_with_location(source, location),
+///         }
+///     }
+/// }
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not read {}", path))]
+///     ReadConfig { path: String, source: std::io::Error },
+/// }
+///
+/// fn example() -> Result<Vec<u8>, Tagged<Error>> {
+///     std::fs::read("/a/file/that/does/not/exist")
+///         .context(Tag("config", ReadConfig { path: "/a/file/that/does/not/exist" }))
+/// }
+///
+/// let e = example().unwrap_err();
+/// assert_eq!(e.tag, "config");
+/// assert_eq!(e.to_string(), "[config] Could not read /a/file/that/does/not/exist");
+/// ```
+pub trait IntoError<E>
+where
+    E: Error + ErrorCompat,
+{
+    /// The underlying error
+    type Source;
+
+    /// Combine the information to produce the error
+    fn into_error(self, source: Self::Source) -> E;
+
+    /// Combine the information to produce the error, recording
+    /// `location` as the place it was created.
+    ///
+    /// Errors without a [`Location`] field ignore the
+    /// location.
+    fn into_error_with_location(self, source: Self::Source, location: Location) -> E
+    where
+        Self: Sized,
+    {
+        let _ = location;
+        self.into_error(source)
+    }
+}
+
+/// A context selector that returns the underlying error unchanged.
+///
+/// This allows code that is generic over the context selector, such
+/// as that written by a macro, to call [`ResultExt::context`] even
+/// when the error already has the final type.
+///
+/// ```rust
+/// use snafu::{ResultExt, Snafu, Unchanged};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     Login { user: String },
+/// }
+///
+/// fn example() -> Result<(), Error> {
+///     login("alice").context(Unchanged)
+/// }
+///
+/// fn login(user: &str) -> Result<(), Error> {
+///     /* ... */
+/// # Ok(())
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Unchanged;
+
+impl<E> IntoError<E> for Unchanged
+where
+    E: Error + ErrorCompat,
+{
+    type Source = E;
+
+    fn into_error(self, source: Self::Source) -> E {
+        source
+    }
+}
+
+/// A leaf error that wraps a message, for when a dependency reports
+/// failure as a string instead of as an error type.
+///
+/// This allows the message to be used as the source of another
+/// error:
+///
+/// ```rust
+/// use snafu::{ResultExt, Snafu, StringError};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not parse the manifest"))]
+///     ParseManifest { source: StringError },
+/// }
+///
+/// fn parse(text: &str) -> Result<u32, String> {
+///     text.parse().map_err(|_| format!("{:?} is not a number", text))
+/// }
+///
+/// fn example() -> Result<u32, Error> {
+///     parse("ten").map_err(StringError::new).context(ParseManifest)
+/// }
+///
+/// let e = example().unwrap_err();
+/// let source = std::error::Error::source(&e).unwrap();
+/// assert_eq!(source.to_string(), r#""ten" is not a number"#);
+/// ```
+///
+/// See [`DisplayError`] to wrap a value that implements
+/// [`Display`](core::fmt::Display) without converting it to a string.
+#[cfg(any(feature = "std", test))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StringError(String);
+
+#[cfg(any(feature = "std", test))]
+impl StringError {
+    /// Creates an error with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        StringError(message.into())
+    }
+
+    /// Returns the message.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the message, consuming the error.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl From<String> for StringError {
+    fn from(message: String) -> Self {
+        StringError(message)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<'a> From<&'a str> for StringError {
+    fn from(message: &'a str) -> Self {
+        StringError(message.into())
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl core::fmt::Display for StringError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl Error for StringError {}
+
+#[cfg(any(feature = "std", test))]
+impl ErrorCompat for StringError {}
+
+/// A leaf error that wraps any value implementing
+/// [`Display`](core::fmt::Display), for when a dependency reports
+/// failure with a type that does not implement [`Error`].
+///
+/// The wrapped value is used for both `Display` and `Debug`:
+///
+/// ```rust
+/// use snafu::{DisplayError, ResultExt, Snafu};
+///
+/// struct Status(u16);
+///
+/// impl std::fmt::Display for Status {
+///     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         write!(f, "status {}", self.0)
+///     }
+/// }
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("The request failed"))]
+///     Request { source: DisplayError<Status> },
+/// }
+///
+/// fn request() -> Result<(), Status> {
+///     Err(Status(503))
+/// }
+///
+/// fn example() -> Result<(), Error> {
+///     request().map_err(DisplayError::new).context(Request)
+/// }
+///
+/// let e = example().unwrap_err();
+/// let source = std::error::Error::source(&e).unwrap();
+/// assert_eq!(source.to_string(), "status 503");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DisplayError<T>(T);
+
+impl<T> DisplayError<T> {
+    /// Wraps the value.
+    pub fn new(value: T) -> Self {
+        DisplayError(value)
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::fmt::Display for DisplayError<T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
     }
+}
 
-    fn with_context<F, C, E>(self, context: F) -> Result<T, E>
-    where
-        F: FnOnce() -> C,
-        C: IntoError<E, Source = NoneError>,
-        E: Error + ErrorCompat,
-    {
-        self.ok_or_else(|| context().into_error(NoneError))
+impl<T> core::fmt::Debug for DisplayError<T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_tuple("DisplayError")
+            .field(&format_args!("{}", self.0))
+            .finish()
     }
 }
 
-/// Backports changes to the [`Error`](std::error::Error) trait to
-/// versions of Rust lacking them.
+impl<T> Error for DisplayError<T> where T: core::fmt::Display {}
+
+impl<T> ErrorCompat for DisplayError<T> where T: core::fmt::Display {}
+
+/// Takes a string message and builds the corresponding error.
 ///
-/// It is recommended to always call these methods explicitly so that
-/// it is easy to replace usages of this trait when you start
-/// supporting a newer version of Rust.
+/// It is expected that most users of SNAFU will not directly interact
+/// with this trait; it is implemented for errors that use
+/// `snafu(whatever)` and is used by the [`whatever`] macro.
+#[cfg(any(feature = "std", test))]
+pub trait FromString {
+    /// The underlying error
+    type Source;
+
+    /// Create a brand new error from the given string
+    fn without_source(message: String) -> Self;
+
+    /// Wrap an existing error with the given string
+    fn with_source(source: Self::Source, message: String) -> Self;
+}
+
+/// A basic error type that you can use as a first step to better
+/// error handling.
 ///
+/// You can use this type in your own application as a quick way to
+/// create errors or add basic context to another error. This can also
+/// be used in a library, but consider wrapping it in an
+/// [opaque](crate::guide::opaque) error to avoid putting the SNAFU
+/// crate in your public API.
+///
+/// ```rust
+/// use snafu::{whatever, Whatever};
+///
+/// type Result<T, E = Whatever> = std::result::Result<T, E>;
+///
+/// fn subtract_numbers(a: u32, b: u32) -> Result<u32> {
+///     if a > b {
+///         Ok(a - b)
+///     } else {
+///         whatever!("Can't subtract {} - {}", a, b)
+///     }
+/// }
+///
+/// fn complicated_math(a: u32, b: u32) -> Result<u32> {
+///     let val = whatever!(subtract_numbers(a, b), "Can't do the math");
+///     Ok(val * 2)
+/// }
 /// ```
-/// # use snafu::{Snafu, ErrorCompat};
-/// # #[derive(Debug, Snafu)] enum Example {};
-/// # fn example(error: Example) {
-/// ErrorCompat::backtrace(&error); // Recommended
-/// error.backtrace();              // Discouraged
-/// # }
+///
+/// See [`whatever!`][crate::whatever!] for detailed usage instructions.
+#[cfg(any(feature = "std", test))]
+#[derive(Debug, Snafu)]
+#[snafu(crate_root(crate))]
+#[snafu(whatever)]
+pub struct Whatever {
+    #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+    source: Option<Box<dyn std::error::Error>>,
+    message: String,
+    backtrace: Backtrace,
+}
+
+/// Print an error and each of its sources to standard error and
+/// return the status the process should exit with.
+///
+/// A successful result returns `0`. Otherwise, `exit_code` is called
+/// with the error, which pairs well with the `exit_code` method
+/// generated by `#[snafu(exit_code(...))]`:
+///
+/// ```rust
+/// use snafu::Snafu;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(exit_code(2))]
+///     InvalidArguments,
+/// }
+///
+/// fn run() -> Result<(), Error> {
+///     InvalidArguments.fail()
+/// }
+///
+/// fn main() {
+///     let status = snafu::report(run(), Error::exit_code);
+///     # assert_eq!(status, 2);
+///     # if false {
+///     std::process::exit(status);
+///     # }
+/// }
 /// ```
-pub trait ErrorCompat {
-    /// Returns a [`Backtrace`](Backtrace) that may be printed.
-    fn backtrace(&self) -> Option<&Backtrace> {
-        None
+#[cfg(any(feature = "std", test))]
+pub fn report<T, E, F>(result: Result<T, E>, exit_code: F) -> i32
+where
+    E: Error,
+    F: FnOnce(&E) -> i32,
+{
+    match result {
+        Ok(_) => 0,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+
+            let mut source = error.source();
+            while let Some(cause) = source {
+                eprintln!("Caused by: {}", cause);
+                source = cause.source();
+            }
+
+            exit_code(&error)
+        }
+    }
+}
+
+/// Formats an error along with each of its sources and, if one was
+/// captured, its backtrace.
+///
+/// This is useful for presenting an error to the end user, such as
+/// at the end of `main`:
+///
+/// ```rust
+/// use snafu::{Report, ResultExt, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not load the configuration"))]
+///     LoadConfig { source: std::io::Error },
+/// }
+///
+/// fn load_config() -> Result<(), Error> {
+///     let result = Err(std::io::Error::new(std::io::ErrorKind::Other, "disk on fire"));
+///     result.context(LoadConfig)
+/// }
+///
+/// fn main() {
+///     if let Err(e) = load_config() {
+///         let report = Report::from(e).to_string();
+///         # assert!(report.starts_with("Could not load the configuration\nCaused by:\n    disk on fire"));
+///         println!("{}", report);
+///     }
+/// }
+/// ```
+///
+/// The `Debug` implementation produces the same output, so `Report`
+/// can also be returned from `main`.
+#[cfg(any(feature = "std", test))]
+pub struct Report<E>(E);
+
+#[cfg(any(feature = "std", test))]
+impl<E> Report<E> {
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<E> From<E> for Report<E> {
+    fn from(error: E) -> Self {
+        Report(error)
     }
 }
 
-impl<'a, E> ErrorCompat for &'a E
+#[cfg(any(feature = "std", test))]
+impl<E> core::fmt::Display for Report<E>
 where
-    E: ErrorCompat,
+    E: Error + ErrorCompat,
 {
-    fn backtrace(&self) -> Option<&Backtrace> {
-        (**self).backtrace()
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut source = self.0.source();
+        if source.is_some() {
+            write!(f, "\nCaused by:")?;
+        }
+        while let Some(cause) = source {
+            write!(f, "\n    {}", cause)?;
+            source = cause.source();
+        }
+
+        if let Some(backtrace) = ErrorCompat::backtrace(&self.0) {
+            #[cfg(feature = "backtraces-impl-backtrace-crate")]
+            write!(f, "\n\nBacktrace:\n{:?}", backtrace)?;
+
+            #[cfg(not(feature = "backtraces-impl-backtrace-crate"))]
+            write!(f, "\n\nBacktrace:\n{}", backtrace)?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(any(feature = "std", test))]
-impl<E> ErrorCompat for Box<E>
+impl<E> core::fmt::Debug for Report<E>
 where
-    E: ErrorCompat,
+    E: Error + ErrorCompat,
 {
-    fn backtrace(&self) -> Option<&Backtrace> {
-        (**self).backtrace()
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
     }
 }
 
-/// Converts the receiver into an [`Error`][] trait object, suitable
-/// for use in [`Error::source`][].
+/// The source code location where an error was reported.
 ///
-/// It is expected that most users of SNAFU will not directly interact
-/// with this trait.
+/// This is much cheaper to create than a [`Backtrace`]
+/// and is often enough to pinpoint where a failure occurred. Any
+/// field with the type `snafu::Location`, or any field marked with
+/// `#[snafu(location)]`, will be populated automatically when the
+/// error is created:
 ///
-/// [`Error`]: std::error::Error
-/// [`Error::source`]: std::error::Error::source
-//
-// Given an error enum with multiple types of underlying causes:
-//
-// ```rust
-// enum Error {
-//     BoxTraitObjectSendSync(Box<dyn error::Error + Send + Sync + 'static>),
-//     BoxTraitObject(Box<dyn error::Error + 'static>),
-//     Boxed(Box<io::Error>),
-//     Unboxed(io::Error),
-// }
-// ```
-//
-// This trait provides the answer to what consistent expression can go
-// in each match arm:
-//
-// ```rust
-// impl error::Error for Error {
-//     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-//         use Error::*;
-//
-//         let v = match *self {
-//             BoxTraitObjectSendSync(ref e) => ...,
-//             BoxTraitObject(ref e) => ...,
-//             Boxed(ref e) => ...,
-//             Unboxed(ref e) => ...,
-//         };
-//
-//         Some(v)
-//     }
-// }
-//
-// Existing methods like returning `e`, `&**e`, `Borrow::borrow(e)`,
-// `Deref::deref(e)`, and `AsRef::as_ref(e)` do not work for various
-// reasons.
-pub trait AsErrorSource {
-    /// For maximum effectiveness, this needs to be called as a method
-    /// to benefit from Rust's automatic dereferencing of method
-    /// receivers.
-    fn as_error_source(&self) -> &(dyn Error + 'static);
+/// ```rust
+/// use snafu::{ErrorCompat, ResultExt, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not read the file (at {})", location))]
+///     ReadFile {
+///         source: std::io::Error,
+///         location: snafu::Location,
+///     },
+/// }
+///
+/// fn example() -> Result<Vec<u8>, Error> {
+///     std::fs::read("/a/file/that/does/not/exist").context(ReadFile)
+/// }
+///
+/// let e = example().unwrap_err();
+/// assert!(ErrorCompat::location(&e).is_some());
+/// ```
+///
+/// Without the `rust_1_46` feature flag, the recorded location is
+/// where the error type is defined. Enabling the feature uses
+/// `#[track_caller]` to record where the error was created, such as
+/// the call to [`ResultExt::context`] or
+/// [`ensure!`](ensure!).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// The file where the error was reported
+    pub file: &'static str,
+    /// The line where the error was reported
+    pub line: u32,
+    /// The column where the error was reported
+    pub column: u32,
 }
 
-impl AsErrorSource for dyn Error + 'static {
-    fn as_error_source(&self) -> &(dyn Error + 'static) {
-        self
+impl Location {
+    /// Constructs a location from its parts.
+    ///
+    /// You will usually want the [`location!`](location!) macro instead.
+    pub fn new(file: &'static str, line: u32, column: u32) -> Self {
+        Location { file, line, column }
     }
-}
 
-impl AsErrorSource for dyn Error + Send + 'static {
-    fn as_error_source(&self) -> &(dyn Error + 'static) {
-        self
+    /// The location of the code that called the current function.
+    #[cfg(feature = "rust_1_46")]
+    #[track_caller]
+    #[allow(clippy::incompatible_msrv)] // Gated by the feature flag
+    pub fn caller() -> Self {
+        let location = core::panic::Location::caller();
+        Location::new(location.file(), location.line(), location.column())
     }
 }
 
-impl AsErrorSource for dyn Error + Sync + 'static {
-    fn as_error_source(&self) -> &(dyn Error + 'static) {
-        self
+impl core::fmt::Display for Location {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
     }
 }
 
-impl AsErrorSource for dyn Error + Send + Sync + 'static {
-    fn as_error_source(&self) -> &(dyn Error + 'static) {
-        self
-    }
+/// Constructs a [`Location`](Location) for the place where the
+/// macro is invoked.
+///
+/// ```rust
+/// let location = snafu::location!();
+/// assert_eq!(location.file, file!());
+/// ```
+#[macro_export]
+macro_rules! location {
+    () => {
+        $crate::Location::new(file!(), line!(), column!())
+    };
 }
 
-impl<T: Error + 'static> AsErrorSource for T {
-    fn as_error_source(&self) -> &(dyn Error + 'static) {
-        self
+/// Extend a [`Result`](std::result::Result)'s error with additional
+/// context-sensitive information, recording where the context was
+/// added.
+///
+/// This is the same as [`ResultExt::context`](ResultExt::context), but
+/// the error's [`Location`](Location) field is set to the place where
+/// the macro is invoked. A method cannot see where it was called from
+/// without the `rust_1_46` feature flag, so this is a macro instead.
+///
+/// ```rust
+/// use snafu::{context, ErrorCompat, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     ReadFile {
+///         source: std::io::Error,
+///         location: snafu::Location,
+///     },
+/// }
+///
+/// fn example() -> Result<Vec<u8>, Error> {
+///     context!(std::fs::read("/a/file/that/does/not/exist"), ReadFile)
+/// }
+///
+/// let e = example().unwrap_err();
+/// assert_eq!(ErrorCompat::location(&e).unwrap().file, file!());
+/// ```
+#[macro_export]
+macro_rules! context {
+    ($result:expr, $context:expr $(,)?) => {
+        $crate::ResultExt::context_with_location($result, $context, $crate::location!())
+    };
+}
+
+/// Extend a [`Result`](std::result::Result)'s error with additional
+/// context-sensitive information, recording a `Debug` rendering of
+/// the listed values.
+///
+/// The context selector must have a `debug_snapshot` field, which is
+/// filled in with each value's expression and its `Debug` output,
+/// such as `user_id = 42, retries = 3`. The values are only formatted
+/// when the `Result` is an error. This preserves the values that
+/// mattered without capturing a whole backtrace.
+///
+/// ```rust
+/// use snafu::{context_dbg, Snafu};
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     #[snafu(display("Could not parse {} ({})", name, debug_snapshot))]
+///     Parse {
+///         name: String,
+///         debug_snapshot: String,
+///         source: std::num::ParseIntError,
+///     },
+/// }
+///
+/// fn example(name: &str, input: &str, radix: u32) -> Result<u32, Error> {
+///     context_dbg!(
+///         u32::from_str_radix(input, radix),
+///         Parse { name },
+///         debug_vars = [input, radix],
+///     )
+/// }
+///
+/// let e = example("port", "eighty", 10).unwrap_err();
+/// assert_eq!(
+///     e.to_string(),
+///     r#"Could not parse port (input = "eighty", radix = 10)"#,
+/// );
+/// ```
+#[cfg(any(feature = "std", test))]
+#[macro_export]
+macro_rules! context_dbg {
+    ($result:expr, $($selector:ident)::+ { $($fields:tt)* }, debug_vars = [$($var:expr),* $(,)?] $(,)?) => {
+        $crate::ResultExt::with_context($result, || $($selector)::+ {
+            debug_snapshot: $crate::format_debug_snapshot(&[
+                $((stringify!($var), &$var as &dyn core::fmt::Debug)),*
+            ]),
+            $($fields)*
+        })
+    };
+    ($result:expr, $($selector:ident)::+, debug_vars = [$($var:expr),* $(,)?] $(,)?) => {
+        $crate::context_dbg!($result, $($selector)::+ {}, debug_vars = [$($var),*])
+    };
+}
+
+/// Formats the values recorded by [`context_dbg`](context_dbg).
+#[cfg(any(feature = "std", test))]
+#[doc(hidden)]
+pub fn format_debug_snapshot(vars: &[(&str, &dyn core::fmt::Debug)]) -> String {
+    use std::fmt::Write;
+
+    let mut snapshot = String::new();
+    for (i, (name, value)) in vars.iter().enumerate() {
+        if i != 0 {
+            snapshot.push_str(", ");
+        }
+        let _ = write!(snapshot, "{} = {:?}", name, value);
     }
+    snapshot
 }
 
-/// Combines an underlying error with additional information
-/// about the error.
+/// Writes a format string that is only known at runtime, such as
+/// one loaded for a translation. This is used by
+/// `#[snafu(display(fmt = ...))]`.
 ///
-/// It is expected that most users of SNAFU will not directly interact
-/// with this trait.
-pub trait IntoError<E>
-where
-    E: Error + ErrorCompat,
-{
-    /// The underlying error
-    type Source;
+/// Only positional placeholders, `{}` and `{0}`, are supported and
+/// each argument is written with its `Display` implementation. Any
+/// other placeholder, or one without a matching argument, is written
+/// unchanged.
+#[doc(hidden)]
+pub fn write_runtime_format(
+    f: &mut core::fmt::Formatter,
+    format: &str,
+    args: &[&dyn core::fmt::Display],
+) -> core::fmt::Result {
+    use core::fmt::Write;
 
-    /// Combine the information to produce the error
-    fn into_error(self, source: Self::Source) -> E;
+    let mut next_index = 0;
+    let mut rest = format;
+
+    while let Some(start) = rest.find(|c| c == '{' || c == '}') {
+        f.write_str(&rest[..start])?;
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            f.write_str(&rest[..1])?;
+            rest = &rest[2..];
+            continue;
+        }
+
+        if rest.starts_with('}') {
+            f.write_char('}')?;
+            rest = &rest[1..];
+            continue;
+        }
+
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let placeholder = &rest[..=end];
+        let name = &placeholder[1..end];
+
+        let index = if name.is_empty() {
+            let index = next_index;
+            next_index += 1;
+            Some(index)
+        } else {
+            name.parse::<usize>().ok()
+        };
+
+        match index.and_then(|i| args.get(i)) {
+            Some(arg) => core::fmt::Display::fmt(arg, f)?,
+            None => f.write_str(placeholder)?,
+        }
+        rest = &rest[end + 1..];
+    }
+
+    f.write_str(rest)
 }
 
 /// Construct a backtrace, allowing it to be optional.
@@ -597,6 +2370,19 @@ pub trait GenerateBacktrace {
 
     /// Retrieve the optional backtrace
     fn as_backtrace(&self) -> Option<&Backtrace>;
+
+    /// Create an instance that does not contain a backtrace.
+    ///
+    /// This is used by [`ErrorCompat::strip_backtrace`][]. The default
+    /// implementation calls [`generate`](GenerateBacktrace::generate),
+    /// so types that can represent a missing backtrace should
+    /// override it.
+    fn disabled() -> Self
+    where
+        Self: Sized,
+    {
+        Self::generate()
+    }
 }
 
 /// Only create a backtrace when an environment variable is set.
@@ -635,6 +2421,10 @@ impl GenerateBacktrace for Option<Backtrace> {
         }
     }
 
+    fn disabled() -> Self {
+        None
+    }
+
     fn as_backtrace(&self) -> Option<&Backtrace> {
         self.as_ref()
     }
@@ -646,6 +2436,10 @@ impl GenerateBacktrace for Backtrace {
         Backtrace::new()
     }
 
+    fn disabled() -> Self {
+        Backtrace::from(Vec::new())
+    }
+
     fn as_backtrace(&self) -> Option<&Backtrace> {
         Some(self)
     }
@@ -657,6 +2451,10 @@ impl GenerateBacktrace for Backtrace {
         Backtrace::force_capture()
     }
 
+    fn disabled() -> Self {
+        Backtrace::disabled()
+    }
+
     fn as_backtrace(&self) -> Option<&Backtrace> {
         Some(self)
     }
@@ -0,0 +1,39 @@
+//! Support for `#[snafu(serialize)]`.
+//!
+//! This module is only available when the `serde` feature flag is
+//! enabled.
+
+use crate::Location;
+use core::fmt;
+use serde_crate::ser::{Serialize, SerializeStruct, Serializer};
+
+impl Serialize for Location {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut location = serializer.serialize_struct("Location", 3)?;
+        location.serialize_field("file", self.file)?;
+        location.serialize_field("line", &self.line)?;
+        location.serialize_field("column", &self.column)?;
+        location.end()
+    }
+}
+
+/// Serializes a value as the string produced by its `Display`
+/// implementation. This is used for the message and the source of an
+/// error.
+#[doc(hidden)]
+pub struct SerializeDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<T> Serialize for SerializeDisplay<'_, T>
+where
+    T: ?Sized + fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self.0)
+    }
+}
@@ -0,0 +1,85 @@
+use snafu::{ResultExt, Snafu};
+use std::{error::Error as _, sync::Arc, thread};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner"))]
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum Error {
+    #[snafu(display("Shared {}: {}", id, source))]
+    Shared {
+        id: i32,
+        #[snafu(source(arc))]
+        source: Arc<InnerError>,
+    },
+
+    #[snafu(context(false))]
+    Converted {
+        #[snafu(source(arc))]
+        source: Arc<std::fmt::Error>,
+    },
+}
+
+fn boom() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn the_source_is_wrapped_in_an_arc() {
+    let e = boom().context(Shared { id: 1 }).unwrap_err();
+    assert_eq!(e.to_string(), "Shared 1: inner");
+
+    let e = Error::from(std::fmt::Error);
+    assert!(matches!(e, Error::Converted { .. }));
+}
+
+#[test]
+fn error_source_looks_through_the_arc() {
+    let e = boom().context(Shared { id: 1 }).unwrap_err();
+    let source = e.source().expect("Must have a source");
+    assert!(source.downcast_ref::<InnerError>().is_some());
+}
+
+#[test]
+fn clones_share_the_source() {
+    let e = boom().context(Shared { id: 1 }).unwrap_err();
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let e = e.clone();
+            thread::spawn(move || e.to_string())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "Shared 1: inner");
+    }
+}
+
+mod structs {
+    use snafu::Snafu;
+    use std::sync::Arc;
+
+    #[derive(Debug, Snafu)]
+    struct Error {
+        #[snafu(source(arc))]
+        source: Arc<std::fmt::Error>,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(source(arc))]
+    struct ApiError(Arc<Error>);
+
+    #[test]
+    fn tuple_structs_wrap_the_inner_error_in_an_arc() {
+        use snafu::IntoError;
+
+        let e = Context.into_error(std::fmt::Error);
+        let api = ApiError::from(e);
+        assert_eq!(Arc::strong_count(&api.0), 1);
+    }
+}
@@ -0,0 +1,62 @@
+use snafu::{Backtrace, ErrorCompat, Snafu};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static CHECKS: AtomicUsize = AtomicUsize::new(0);
+
+fn never() -> bool {
+    CHECKS.fetch_add(1, Ordering::SeqCst);
+    false
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Validation {
+        #[snafu(backtrace(capture_if = "never()"))]
+        backtrace: Option<Backtrace>,
+    },
+
+    Internal {
+        #[snafu(backtrace(capture_if("true")))]
+        trace: Option<Backtrace>,
+    },
+
+    Unconditional {
+        backtrace: Option<Backtrace>,
+    },
+}
+
+#[test]
+fn the_condition_is_checked_each_time_an_error_is_created() {
+    let e = Validation.build();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+    let _ = Validation.build();
+    assert_eq!(CHECKS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn a_true_condition_behaves_like_an_optional_backtrace() {
+    let conditional = Internal.build();
+    let unconditional = Unconditional.build();
+    assert_eq!(
+        ErrorCompat::backtrace(&conditional).is_some(),
+        ErrorCompat::backtrace(&unconditional).is_some(),
+    );
+}
+
+mod structs {
+    use super::*;
+
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+
+    #[derive(Debug, Snafu)]
+    struct Error {
+        #[snafu(backtrace(capture_if = "ENABLED.load(Ordering::SeqCst)"))]
+        backtrace: Option<Backtrace>,
+    }
+
+    #[test]
+    fn the_condition_can_be_used_on_structs() {
+        let e = Context.build();
+        assert!(e.backtrace.is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use snafu::{Backtrace, ErrorCompat, GenerateBacktrace, ResultExt, Snafu};
+
+// A backtrace that is never captured, like an `Option<Backtrace>`
+// when backtraces are disabled by the environment.
+#[derive(Debug)]
+struct Uncaptured;
+
+impl GenerateBacktrace for Uncaptured {
+    fn generate() -> Self {
+        Uncaptured
+    }
+
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        None
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Leaf { backtrace: Backtrace },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(backtrace(search_chain))]
+    WithoutBacktrace { source: InnerError },
+
+    #[snafu(backtrace(search_chain))]
+    NotCaptured {
+        source: InnerError,
+        backtrace: Uncaptured,
+    },
+
+    NotSearched {
+        source: InnerError,
+        backtrace: Uncaptured,
+    },
+
+    #[snafu(backtrace(search_chain))]
+    Nested { source: Box<Error> },
+}
+
+fn leaf() -> Result<(), InnerError> {
+    Leaf.fail()
+}
+
+#[test]
+fn the_source_backtrace_is_returned_without_our_own() {
+    let e = leaf().context(WithoutBacktrace).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn the_source_backtrace_is_returned_when_ours_is_missing() {
+    let e = leaf().context(NotCaptured).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn the_chain_is_not_searched_by_default() {
+    let e = leaf().context(NotSearched).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+}
+
+#[test]
+fn the_search_continues_through_each_level() {
+    let e = leaf().context(WithoutBacktrace).unwrap_err();
+    let e = Err::<(), _>(Box::new(e)).context(Nested).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+
+    let e = leaf().context(NotSearched).unwrap_err();
+    let e = Err::<(), _>(Box::new(e)).context(Nested).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_none());
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(backtrace(search_chain))]
+    struct Error {
+        source: InnerError,
+    }
+
+    #[test]
+    fn structs_can_search_the_chain() {
+        let e = leaf().context(Context).unwrap_err();
+        assert!(ErrorCompat::backtrace(&e).is_some());
+    }
+}
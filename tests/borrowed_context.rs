@@ -0,0 +1,34 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not authenticate {}", user_name))]
+    Authenticating {
+        user_name: String,
+        source: std::num::ParseIntError,
+    },
+}
+
+fn selector_for(user_name: &str) -> Authenticating<&str> {
+    Authenticating { user_name }
+}
+
+#[test]
+fn borrowed_fields_are_converted_when_the_error_is_created() {
+    let name = String::from("alice");
+    let e = "x".parse::<i32>().context(selector_for(&name)).unwrap_err();
+    drop(name);
+
+    let Error::Authenticating { user_name, .. } = e;
+    assert_eq!(user_name, "alice");
+}
+
+#[test]
+fn closures_can_return_borrowing_selectors() {
+    let name = String::from("bob");
+    let e = "x"
+        .parse::<i32>()
+        .with_context(|| Authenticating { user_name: &*name })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not authenticate bob");
+}
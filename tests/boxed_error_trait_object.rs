@@ -35,6 +35,7 @@ mod trait_object_send_sync {
 }
 
 #[derive(Debug, Snafu)]
+#[allow(clippy::enum_variant_names)]
 enum Error {
     TraitObject {
         user_id: i32,
@@ -72,3 +73,75 @@ fn implements_error() {
     check::<Error>();
     example().unwrap();
 }
+
+mod concrete_source_errors {
+    use snafu::{ResultExt, Snafu};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Parsing {
+            input: String,
+            source: Box<dyn std::error::Error + Send + Sync>,
+        },
+
+        Local {
+            source: Box<dyn std::error::Error>,
+        },
+    }
+
+    fn parse_both(a: &str, b: &str) -> Result<(i32, f64), Error> {
+        let a = a.parse::<i32>().boxed().context(Parsing { input: a })?;
+        let b = b.parse::<f64>().boxed().context(Parsing { input: b })?;
+        Ok((a, b))
+    }
+
+    #[test]
+    fn are_boxed_into_the_trait_object() {
+        assert_eq!(parse_both("1", "2.5").unwrap(), (1, 2.5));
+
+        match parse_both("x", "2.5").unwrap_err() {
+            Error::Parsing { source, .. } => assert!(source.is::<std::num::ParseIntError>()),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        match parse_both("1", "y").unwrap_err() {
+            Error::Parsing { source, .. } => assert!(source.is::<std::num::ParseFloatError>()),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn can_be_boxed_without_thread_safety() {
+        let e = "x".parse::<u8>().boxed_local().context(Local).unwrap_err();
+        assert!(matches!(e, Error::Local { .. }));
+    }
+
+    #[derive(Debug, Snafu)]
+    enum ConfigError {
+        #[snafu(display("Could not parse the port"))]
+        Port { source: std::num::ParseIntError },
+    }
+
+    #[test]
+    fn context_can_be_added_while_boxing() {
+        let e = "x".parse::<u16>().boxed_context(Port).unwrap_err();
+        assert_eq!(e.to_string(), "Could not parse the port");
+        assert!(e.is::<ConfigError>());
+    }
+
+    #[test]
+    fn source_can_be_boxed_while_adding_context() {
+        let e = "x"
+            .parse::<i32>()
+            .context_boxed_source(Parsing { input: "x" })
+            .unwrap_err();
+
+        match e {
+            Error::Parsing { input, source } => {
+                assert_eq!(input, "x");
+                assert!(source.is::<std::num::ParseIntError>());
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
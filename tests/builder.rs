@@ -0,0 +1,129 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(builder)]
+    OpenConfig {
+        filename: String,
+        attempt: Option<u32>,
+        reason: Option<String>,
+        source: InnerError,
+    },
+
+    #[snafu(builder)]
+    InvalidUser {
+        user_id: i32,
+        note: Option<&'static str>,
+    },
+
+    #[snafu(builder)]
+    WriteFile {
+        path: String,
+        mode: u32,
+        source: InnerError,
+    },
+}
+
+fn open() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn optional_fields_default_to_none() {
+    let e = open()
+        .context(OpenConfig::new("/etc/app.toml"))
+        .unwrap_err();
+    assert!(matches!(
+        e,
+        Error::OpenConfig {
+            ref filename,
+            attempt: None,
+            reason: None,
+            ..
+        } if filename == "/etc/app.toml"
+    ));
+}
+
+#[test]
+fn optional_fields_can_be_set() {
+    let e = open()
+        .context(
+            OpenConfig::new("/etc/app.toml")
+                .with_attempt(3)
+                .with_reason("retrying".to_string()),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        e,
+        Error::OpenConfig {
+            attempt: Some(3),
+            reason: Some(ref reason),
+            ..
+        } if reason == "retrying"
+    ));
+}
+
+#[test]
+fn builder_can_fail() {
+    let e = InvalidUser::new(42)
+        .with_note("banned")
+        .fail::<()>()
+        .unwrap_err();
+    assert!(matches!(
+        e,
+        Error::InvalidUser {
+            user_id: 42,
+            note: Some("banned"),
+        }
+    ));
+}
+
+#[test]
+fn new_takes_required_fields_in_order() {
+    let e = open()
+        .context(WriteFile::new("/tmp/out", 0o644_u32))
+        .unwrap_err();
+    assert!(matches!(
+        e,
+        Error::WriteFile {
+            ref path,
+            mode: 0o644,
+            ..
+        } if path == "/tmp/out"
+    ));
+}
+
+#[test]
+fn struct_literal_still_works() {
+    let e = InvalidUser {
+        user_id: 1,
+        note: None,
+    }
+    .build();
+    assert!(matches!(e, Error::InvalidUser { note: None, .. }));
+}
+
+mod structs {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(builder)]
+    struct LimitError {
+        limit: usize,
+        hint: Option<String>,
+    }
+
+    #[test]
+    fn struct_selectors_have_builders() {
+        let e = LimitContext::new(10_usize)
+            .with_hint("raise it".into())
+            .build();
+        assert_eq!(e.limit, 10);
+        assert_eq!(e.hint.as_ref().map(String::as_str), Some("raise it"));
+    }
+}
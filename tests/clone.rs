@@ -0,0 +1,89 @@
+use snafu::{Backtrace, ErrorCompat, ResultExt, Snafu};
+use std::{error::Error as _, sync::Arc};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner"))]
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum Error {
+    #[snafu(display("Leaf {}", name))]
+    Leaf {
+        name: String,
+    },
+
+    // `InnerError` is not `Clone`, so it is shared instead.
+    Shared {
+        #[snafu(source(from(InnerError, Arc::new)))]
+        source: Arc<InnerError>,
+        backtrace: Backtrace,
+    },
+
+    Tuple(#[snafu(source(false))] u8),
+}
+
+fn boom() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn simple_fields_are_cloned() {
+    let e = Leaf { name: "alpha" }.build();
+    let cloned = e.clone();
+    assert_eq!(cloned.to_string(), "Leaf alpha");
+    assert!(matches!(cloned, Error::Leaf { ref name } if name == "alpha"));
+}
+
+#[test]
+fn arc_wrapped_sources_are_shared() {
+    let e = boom().context(Shared).unwrap_err();
+    let cloned = e.clone();
+
+    match (&e, &cloned) {
+        (Error::Shared { source: a, .. }, Error::Shared { source: b, .. }) => {
+            assert!(Arc::ptr_eq(a, b))
+        }
+        _ => panic!("Expected both errors to be `Shared`"),
+    }
+
+    assert!(ErrorCompat::backtrace(&cloned).is_some());
+    let source = cloned.source().expect("Must have a source");
+    assert_eq!(source.to_string(), "inner");
+}
+
+#[test]
+fn positional_fields_are_cloned() {
+    let e = Tuple(3_u8).build();
+    assert!(matches!(e.clone(), Error::Tuple(3)));
+}
+
+mod structs {
+    use snafu::Snafu;
+    use std::sync::Arc;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(clone)]
+    struct Error {
+        id: i32,
+    }
+
+    #[test]
+    fn named_structs_can_be_cloned() {
+        let e = Context { id: 42 }.build();
+        assert_eq!(e.clone().id, 42);
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(clone, source(from(Error, Arc::new)))]
+    struct ApiError(Arc<Error>);
+
+    #[test]
+    fn tuple_structs_can_be_cloned() {
+        let e = ApiError::from(Context { id: 42 }.build());
+        let cloned = e.clone();
+        assert!(Arc::ptr_eq(&e.0, &cloned.0));
+    }
+}
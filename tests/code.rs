@@ -0,0 +1,34 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(code("E0001"))]
+    OpenConfig {
+        filename: String,
+    },
+
+    #[snafu(code = "E0002")]
+    Parse(#[snafu(source(false))] usize),
+
+    Eof,
+}
+
+#[test]
+fn code_is_returned_for_each_variant() {
+    let e = OpenConfig {
+        filename: "/etc/app.toml",
+    }
+    .build();
+    assert_eq!(e.code(), Some("E0001"));
+    assert_eq!(Parse(3_usize).build().code(), Some("E0002"));
+}
+
+#[test]
+fn variants_without_a_code_return_none() {
+    assert_eq!(Eof.build().code(), None);
+}
+
+#[test]
+fn code_is_independent_of_display() {
+    assert_eq!(Eof.build().to_string(), "Eof");
+}
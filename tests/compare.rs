@@ -0,0 +1,146 @@
+use snafu::{Backtrace, Location, ResultExt, Snafu};
+
+#[derive(Debug, PartialEq, Snafu)]
+enum InnerError {
+    Boom { id: i32 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(compare)]
+enum Error {
+    UserIdInvalid {
+        user_id: i32,
+        backtrace: Backtrace,
+    },
+
+    Wrapped {
+        source: InnerError,
+        #[snafu(location)]
+        location: Location,
+    },
+
+    Io {
+        name: String,
+        #[snafu(compare(false))]
+        source: std::io::Error,
+    },
+
+    Positional(InnerError, u8),
+
+    Unit,
+}
+
+fn io_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, "boom")
+}
+
+#[test]
+fn equal_fields_are_equal() {
+    assert_eq!(
+        UserIdInvalid { user_id: 0 }.build(),
+        UserIdInvalid { user_id: 0 }.build()
+    );
+    assert_ne!(
+        UserIdInvalid { user_id: 0 }.build(),
+        UserIdInvalid { user_id: 1 }.build()
+    );
+}
+
+#[test]
+fn results_can_be_compared() {
+    fn example(user_id: i32) -> Result<(), Error> {
+        UserIdInvalid { user_id }.fail()
+    }
+
+    assert_eq!(example(0), Err(UserIdInvalid { user_id: 0 }.build()));
+}
+
+#[test]
+fn sources_are_compared() {
+    let a = Boom { id: 1 }.fail::<()>().context(Wrapped).unwrap_err();
+    let b = Boom { id: 1 }.fail::<()>().context(Wrapped).unwrap_err();
+    let c = Boom { id: 2 }.fail::<()>().context(Wrapped).unwrap_err();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn fields_can_be_excluded() {
+    let a = Err::<(), _>(io_error())
+        .context(Io { name: "a" })
+        .unwrap_err();
+    let b = Err::<(), _>(io_error())
+        .context(Io { name: "a" })
+        .unwrap_err();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn different_variants_are_not_equal() {
+    assert_ne!(Unit.build(), UserIdInvalid { user_id: 0 }.build());
+    assert_eq!(Unit.build(), Unit.build());
+}
+
+#[test]
+fn positional_fields_are_compared() {
+    let a = Err::<(), _>(InnerError::Boom { id: 1 })
+        .context(Positional(1_u8))
+        .unwrap_err();
+    let b = Err::<(), _>(InnerError::Boom { id: 1 })
+        .context(Positional(2_u8))
+        .unwrap_err();
+    assert_ne!(a, b);
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(compare)]
+    enum Error<'a, T> {
+        Bad { name: &'a str, value: T },
+    }
+
+    #[test]
+    fn type_parameters_must_be_comparable() {
+        let a: Error<'_, i32> = Bad {
+            name: "a",
+            value: 1,
+        }
+        .build();
+        let b: Error<'_, i32> = Bad {
+            name: "a",
+            value: 1,
+        }
+        .build();
+        assert!(a == b);
+    }
+}
+
+mod structs {
+    use snafu::{Backtrace, Snafu};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(compare)]
+    struct Error {
+        id: i32,
+        backtrace: Backtrace,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(compare)]
+    struct Opaque(Error);
+
+    #[test]
+    fn named_structs_can_be_compared() {
+        assert_eq!(Context { id: 1 }.build(), Context { id: 1 }.build());
+        assert_ne!(Context { id: 1 }.build(), Context { id: 2 }.build());
+    }
+
+    #[test]
+    fn tuple_structs_can_be_compared() {
+        let a = Opaque::from(Context { id: 1 }.build());
+        let b = Opaque::from(Context { id: 1 }.build());
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,62 @@
+use snafu::{ErrorCompat, Location, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Low {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Middle {
+    #[snafu(display("Reading {}", path))]
+    Read { path: String, source: Low },
+}
+
+#[derive(Debug, Snafu)]
+enum High {
+    #[snafu(display("Starting {}", name))]
+    Startup { name: String, source: Middle },
+
+    Wrapped {
+        source: Middle,
+        #[snafu(location)]
+        location: Location,
+    },
+}
+
+#[test]
+fn both_layers_are_attached() {
+    let r: Result<(), Low> = Boom.fail();
+    let e = r
+        .context2(Read { path: "app.toml" }, Startup { name: "app" })
+        .unwrap_err();
+
+    assert!(matches!(
+        e,
+        High::Startup {
+            source: Middle::Read { .. },
+            ..
+        }
+    ));
+    let messages: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(messages, ["Starting app", "Reading app.toml", "Boom"]);
+}
+
+#[test]
+fn ok_values_are_kept() {
+    let r: Result<i32, Low> = Ok(42);
+    let v = r.context2(Read { path: "app.toml" }, Startup { name: "app" });
+    assert_eq!(v.unwrap(), 42);
+}
+
+#[test]
+fn locations_record_the_caller() {
+    let r: Result<(), Low> = Boom.fail();
+    let line = line!() + 1;
+    let e = r.context2(Read { path: "app.toml" }, Wrapped).unwrap_err();
+
+    let location = ErrorCompat::location(&e).expect("Must have a location");
+    assert_eq!(location.file, file!());
+    if cfg!(feature = "rust_1_46") {
+        assert_eq!(location.line, line);
+    }
+}
@@ -0,0 +1,91 @@
+use snafu::{context_dbg, Snafu};
+use std::cell::Cell;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    WithFields {
+        user_id: u32,
+        debug_snapshot: String,
+        source: InnerError,
+    },
+
+    OnlySnapshot {
+        debug_snapshot: String,
+        source: InnerError,
+    },
+}
+
+fn fail() -> Result<i32, InnerError> {
+    Boom.fail()
+}
+
+fn succeed() -> Result<i32, InnerError> {
+    Ok(42)
+}
+
+#[test]
+fn listed_values_are_recorded() {
+    let name = "alpha";
+    let retries = 3;
+    let e = context_dbg!(
+        fail(),
+        WithFields { user_id: 7_u32 },
+        debug_vars = [name, retries],
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        e,
+        Error::WithFields { user_id: 7, ref debug_snapshot, .. }
+            if debug_snapshot == r#"name = "alpha", retries = 3"#
+    ));
+}
+
+#[test]
+fn expressions_are_recorded_with_their_source_text() {
+    let values = vec![1, 2];
+    let e = context_dbg!(fail(), OnlySnapshot, debug_vars = [values.len(), values]).unwrap_err();
+
+    assert!(matches!(
+        e,
+        Error::OnlySnapshot { ref debug_snapshot, .. }
+            if debug_snapshot == "values.len() = 2, values = [1, 2]"
+    ));
+}
+
+#[test]
+fn no_values_records_an_empty_snapshot() {
+    let e = context_dbg!(fail(), OnlySnapshot {}, debug_vars = []).unwrap_err();
+
+    assert!(matches!(
+        e,
+        Error::OnlySnapshot { ref debug_snapshot, .. } if debug_snapshot.is_empty()
+    ));
+}
+
+#[test]
+fn values_are_not_formatted_on_success() {
+    struct Counting<'a>(&'a Cell<u32>);
+
+    impl std::fmt::Debug for Counting<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.write_str("counting")
+        }
+    }
+
+    let formatted = Cell::new(0);
+    let counting = Counting(&formatted);
+
+    let v = context_dbg!(succeed(), OnlySnapshot, debug_vars = [counting]).unwrap();
+    assert_eq!(v, 42);
+    assert_eq!(formatted.get(), 0);
+
+    context_dbg!(fail(), OnlySnapshot, debug_vars = [counting]).unwrap_err();
+    assert_eq!(formatted.get(), 1);
+}
@@ -0,0 +1,131 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(context(default))]
+    Request {
+        user_id: u32,
+        host: String,
+        retries: u8,
+        source: InnerError,
+    },
+
+    #[snafu(context(default))]
+    Leaf { user_id: u32, reason: String },
+
+    #[snafu(context(default))]
+    Positional(#[snafu(source(false))] u32, String),
+
+    #[snafu(context(default))]
+    Empty,
+}
+
+#[test]
+fn struct_update_syntax_fills_in_the_other_fields() {
+    let r: Result<(), InnerError> = Boom.fail();
+    let e = r
+        .context(Request {
+            user_id: 42,
+            ..Default::default()
+        })
+        .unwrap_err();
+
+    match e {
+        Error::Request {
+            user_id,
+            host,
+            retries,
+            ..
+        } => {
+            assert_eq!(user_id, 42);
+            assert_eq!(host, "");
+            assert_eq!(retries, 0);
+        }
+        _ => panic!("Unexpected variant"),
+    }
+}
+
+#[test]
+fn leaf_selectors_can_be_defaulted() {
+    let e = Leaf {
+        reason: String::from("denied"),
+        ..Default::default()
+    }
+    .build();
+    assert!(matches!(e, Error::Leaf { user_id: 0, ref reason } if reason == "denied"));
+}
+
+#[test]
+fn positional_and_unit_selectors_can_be_defaulted() {
+    let e = Positional::default().build();
+    assert!(matches!(e, Error::Positional(0, ref s) if s.is_empty()));
+
+    fn defaulted<T: Default>() -> T {
+        T::default()
+    }
+    let e = defaulted::<Empty>().build();
+    assert!(matches!(e, Error::Empty));
+}
+
+#[test]
+fn the_selector_can_still_be_built_with_other_types() {
+    let e = Leaf {
+        user_id: 1_u8,
+        reason: "denied",
+    }
+    .build();
+    assert!(matches!(e, Error::Leaf { user_id: 1, .. }));
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error<T: std::fmt::Debug, U: std::fmt::Debug> {
+        #[snafu(context(default))]
+        Generic { value: T, count: u8 },
+
+        #[snafu(context(default))]
+        Other { other: U },
+    }
+
+    #[test]
+    fn generic_fields_need_their_type_named() {
+        let e: Error<i32, ()> = Generic::<i32, u8> {
+            count: 3,
+            ..Default::default()
+        }
+        .build();
+        assert!(matches!(e, Error::Generic { value: 0, count: 3 }));
+
+        let e: Error<(), String> = Other::<String>::default().build();
+        assert!(matches!(e, Error::Other { ref other } if other.is_empty()));
+    }
+}
+
+mod structs {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(default))]
+    struct Error {
+        user_id: u32,
+        host: String,
+    }
+
+    #[test]
+    fn struct_selectors_can_be_defaulted() {
+        let e = Context {
+            user_id: 7,
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(e.user_id, 7);
+        assert_eq!(e.host, "");
+    }
+}
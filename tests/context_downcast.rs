@@ -0,0 +1,71 @@
+use snafu::{ResultExt, Snafu};
+use std::{error::Error as StdError, fmt, io};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Io { source: BoxError },
+    Parse { source: BoxError },
+    Other { name: String, source: BoxError },
+}
+
+fn io_error() -> Result<(), BoxError> {
+    Err(io::Error::new(io::ErrorKind::Other, "io").into())
+}
+
+fn fmt_error() -> Result<(), BoxError> {
+    Err(fmt::Error.into())
+}
+
+#[test]
+fn the_matched_selector_is_used_when_the_type_matches() {
+    let e = io_error()
+        .context_downcast::<io::Error, _, _, _>(Io, Other { name: "io" })
+        .unwrap_err();
+    assert!(matches!(e, Error::Io { .. }));
+
+    let source = e.source().expect("Must have a source");
+    assert!(source.downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn the_fallback_selector_is_used_otherwise() {
+    let e = fmt_error()
+        .context_downcast::<io::Error, _, _, _>(Io, Other { name: "fmt" })
+        .unwrap_err();
+    assert!(matches!(e, Error::Other { ref name, .. } if name == "fmt"));
+}
+
+#[test]
+fn selections_can_be_chained() {
+    fn classify(r: Result<(), BoxError>) -> Error {
+        r.context_downcast::<io::Error, _, _, _>(Io, Parse)
+            .unwrap_err()
+    }
+
+    assert!(matches!(classify(io_error()), Error::Io { .. }));
+    assert!(matches!(classify(fmt_error()), Error::Parse { .. }));
+}
+
+#[test]
+fn ok_values_are_passed_through() {
+    let r: Result<i32, BoxError> = Ok(1);
+    let r = r.context_downcast::<io::Error, _, _, Error>(Io, Parse);
+    assert_eq!(r.unwrap(), 1);
+}
+
+#[test]
+fn concrete_boxed_errors_are_supported() {
+    #[derive(Debug, Snafu)]
+    enum BoxedError {
+        Matched { source: Box<io::Error> },
+        Fallback { source: Box<io::Error> },
+    }
+
+    let r: Result<(), Box<io::Error>> = Err(Box::new(io::Error::new(io::ErrorKind::Other, "io")));
+    let e = r
+        .context_downcast::<io::Error, _, _, _>(Matched, Fallback)
+        .unwrap_err();
+    assert!(matches!(e, BoxedError::Matched { .. }));
+}
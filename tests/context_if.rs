@@ -0,0 +1,52 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    NotFound,
+    Denied,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(context(false))]
+    Inner {
+        source: InnerError,
+    },
+
+    Missing {
+        name: String,
+        source: InnerError,
+    },
+}
+
+fn lookup(inner: InnerError) -> Result<(), Error> {
+    Err(inner).context_if(
+        |e| matches!(e, InnerError::NotFound),
+        Missing { name: "config" },
+    )
+}
+
+#[test]
+fn context_is_added_when_the_predicate_matches() {
+    let e = lookup(InnerError::NotFound).unwrap_err();
+    assert!(matches!(e, Error::Missing { ref name, .. } if name == "config"));
+}
+
+#[test]
+fn error_is_converted_when_the_predicate_does_not_match() {
+    let e = lookup(InnerError::Denied).unwrap_err();
+    assert!(matches!(
+        e,
+        Error::Inner {
+            source: InnerError::Denied
+        }
+    ));
+}
+
+#[test]
+fn predicate_is_not_called_on_success() {
+    let result: Result<i32, InnerError> = Ok(42);
+    let value: Result<i32, Error> =
+        result.context_if(|_| panic!("Must not be called"), Missing { name: "x" });
+    assert_eq!(value.unwrap(), 42);
+}
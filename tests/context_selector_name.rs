@@ -0,0 +1,173 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+fn inner() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+mod variant_attributes {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(context(suffix(Ctx)))]
+        WithSuffix {
+            source: InnerError,
+        },
+
+        #[snafu(context(suffix = "Ctx"))]
+        WithCompatSuffix {
+            id: i32,
+        },
+
+        #[snafu(context(name(RenamedSelector)))]
+        WithName {
+            source: InnerError,
+        },
+
+        #[snafu(context(name = "CompatRenamedSelector"))]
+        WithCompatName {
+            id: i32,
+        },
+
+        Unchanged,
+    }
+
+    #[test]
+    fn selectors_can_be_given_a_suffix() {
+        let e = inner().context(WithSuffixCtx).unwrap_err();
+        assert!(matches!(e, Error::WithSuffix { .. }));
+
+        let e = WithCompatSuffixCtx { id: 42 }.build();
+        assert!(matches!(e, Error::WithCompatSuffix { id: 42 }));
+    }
+
+    #[test]
+    fn selectors_can_be_given_an_exact_name() {
+        let e = inner().context(RenamedSelector).unwrap_err();
+        assert!(matches!(e, Error::WithName { .. }));
+
+        let e = CompatRenamedSelector { id: 42 }.build();
+        assert!(matches!(e, Error::WithCompatName { id: 42 }));
+    }
+
+    #[test]
+    fn selectors_default_to_the_variant_name() {
+        let e = Unchanged.build();
+        assert!(matches!(e, Error::Unchanged));
+    }
+}
+
+mod default_suffix {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(suffix(Snafu)))]
+    enum Error {
+        UsesDefault {
+            source: InnerError,
+        },
+
+        #[snafu(context(suffix(Ctx)))]
+        OverridesSuffix,
+
+        #[snafu(context(name(OverridesName)))]
+        IgnoresDefault,
+
+        #[snafu(context(false))]
+        NoSelector {
+            source: std::fmt::Error,
+        },
+    }
+
+    #[test]
+    fn the_enum_can_set_a_default_suffix() {
+        let e = inner().context(UsesDefaultSnafu).unwrap_err();
+        assert!(matches!(e, Error::UsesDefault { .. }));
+    }
+
+    #[test]
+    fn variants_can_override_the_default_suffix() {
+        assert!(matches!(OverridesSuffixCtx.build(), Error::OverridesSuffix));
+        assert!(matches!(OverridesName.build(), Error::IgnoresDefault));
+    }
+
+    #[test]
+    fn variants_without_context_are_unaffected() {
+        let e: Error = std::fmt::Error.into();
+        assert!(matches!(e, Error::NoSelector { .. }));
+    }
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(suffix(Snafu)))]
+    struct SuffixError;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(name(Renamed)))]
+    struct NameError;
+
+    #[test]
+    fn struct_selectors_can_be_renamed() {
+        let _: SuffixError = SuffixSnafu.build();
+        let _: NameError = Renamed.build();
+    }
+}
+
+mod fail_name {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(context(fail_name(raise)))]
+        Renamed { id: i32 },
+
+        #[snafu(context(fail_name(raise)))]
+        #[snafu(context(suffix(Snafu)))]
+        WithSuffix,
+    }
+
+    impl Renamed<i32> {
+        // Would conflict with the generated method if it were not renamed
+        #[allow(dead_code)]
+        fn fail(self) {}
+    }
+
+    #[test]
+    fn fail_can_be_renamed() {
+        let e = Renamed { id: 42 }.raise::<()>().unwrap_err();
+        assert!(matches!(e, Error::Renamed { id: 42 }));
+        assert!(matches!(
+            WithSuffixSnafu.raise::<()>(),
+            Err(Error::WithSuffix)
+        ));
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(fail_name(raise)))]
+    struct StructError;
+
+    #[test]
+    fn struct_fail_can_be_renamed() {
+        let _: Result<(), StructError> = StructContext.raise();
+    }
+
+    #[test]
+    fn leaf_selectors_implement_into_error() {
+        fn generic<C: snafu::IntoError<Error, Source = snafu::NoneError>>(c: C) -> Error {
+            c.into_error(snafu::NoneError)
+        }
+
+        assert!(matches!(
+            generic(Renamed { id: 1 }),
+            Error::Renamed { id: 1 }
+        ));
+    }
+}
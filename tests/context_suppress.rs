@@ -0,0 +1,74 @@
+use snafu::{Backtrace, ErrorCompat, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(context(suppress), display("Unexpected end of file"))]
+    Eof,
+
+    #[snafu(context(suppress), display("Invalid token {} at {}", token, offset))]
+    InvalidToken { token: String, offset: usize },
+
+    #[snafu(context(suppress))]
+    WithBacktrace { backtrace: Backtrace },
+
+    #[snafu(context(suppress), display("{}", _0))]
+    Positional(#[snafu(source(false))] &'static str),
+}
+
+// Would conflict with the context selector if one were generated
+#[allow(dead_code)]
+struct Eof;
+
+#[test]
+fn an_inherent_constructor_is_generated() {
+    let e = Error::eof();
+    assert!(matches!(e, Error::Eof));
+    assert_eq!(e.to_string(), "Unexpected end of file");
+}
+
+#[test]
+fn the_constructor_accepts_the_context_fields() {
+    let e = Error::invalid_token("}", 42_usize);
+    assert_eq!(e.to_string(), "Invalid token } at 42");
+
+    let e = Error::positional("oops");
+    assert_eq!(e.to_string(), "oops");
+}
+
+#[test]
+fn the_constructor_captures_a_backtrace() {
+    let e = Error::with_backtrace();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+mod visibility {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    pub enum Error {
+        #[snafu(context(suppress), visibility(pub))]
+        Closed,
+    }
+}
+
+#[test]
+fn the_constructor_uses_the_selector_visibility() {
+    let e = visibility::Error::closed();
+    assert!(matches!(e, visibility::Error::Closed));
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error<T: std::fmt::Debug> {
+        #[snafu(context(suppress))]
+        Invalid { value: T },
+    }
+
+    #[test]
+    fn generic_errors_get_a_constructor() {
+        let e = Error::invalid(7_i32);
+        assert!(matches!(e, Error::Invalid { value: 7 }));
+    }
+}
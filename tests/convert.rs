@@ -0,0 +1,95 @@
+use snafu::Snafu;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    ByInto {
+        path: PathBuf,
+    },
+
+    ByToOwned {
+        #[snafu(convert(to_owned))]
+        path: PathBuf,
+    },
+
+    ByClone {
+        #[snafu(convert(clone))]
+        path: PathBuf,
+    },
+
+    Mixed {
+        #[snafu(convert(to_owned))]
+        name: String,
+        #[snafu(convert(into))]
+        id: u64,
+    },
+}
+
+#[test]
+fn into_is_the_default() {
+    let e = ByInto { path: "/etc" }.build();
+    assert!(matches!(e, Error::ByInto { ref path } if path == Path::new("/etc")));
+}
+
+#[test]
+fn to_owned_accepts_borrowed_values() {
+    let path = Path::new("/var/log");
+    let e = ByToOwned { path }.build();
+    assert!(matches!(e, Error::ByToOwned { ref path } if path == Path::new("/var/log")));
+
+    let path = PathBuf::from("/tmp");
+    let e = ByToOwned { path: &path }.build();
+    assert!(matches!(e, Error::ByToOwned { ref path } if path == Path::new("/tmp")));
+}
+
+#[test]
+fn clone_accepts_references_to_the_field_type() {
+    let path = PathBuf::from("/srv");
+    let e = ByClone { path: &path }.build();
+    assert!(matches!(e, Error::ByClone { ref path } if path == Path::new("/srv")));
+}
+
+#[test]
+fn conversions_can_be_mixed() {
+    let e = Mixed {
+        name: "alpha",
+        id: 7_u8,
+    }
+    .build();
+    assert!(matches!(e, Error::Mixed { ref name, id: 7 } if name == "alpha"));
+}
+
+mod structs {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    struct Error {
+        #[snafu(convert(to_owned))]
+        name: String,
+    }
+
+    #[test]
+    fn conversions_work_on_structs() {
+        let e = Context { name: "beta" }.build();
+        assert_eq!(e.name, "beta");
+    }
+}
+
+mod suppressed {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(context(suppress))]
+        Missing {
+            #[snafu(convert(to_owned))]
+            key: String,
+        },
+    }
+
+    #[test]
+    fn conversions_apply_to_constructors() {
+        let e = Error::missing("gamma");
+        assert!(matches!(e, Error::Missing { ref key } if key == "gamma"));
+    }
+}
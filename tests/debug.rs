@@ -0,0 +1,100 @@
+use snafu::{Backtrace, ErrorCompat, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom { id: i32 },
+}
+
+#[derive(Snafu)]
+#[snafu(debug)]
+enum Error {
+    Leaf { user_id: i32, backtrace: Backtrace },
+
+    Wrapped { name: String, source: InnerError },
+
+    Optional { backtrace: Option<Backtrace> },
+
+    Positional(InnerError, u8),
+
+    Unit,
+}
+
+#[test]
+fn backtraces_are_hidden() {
+    let e = Leaf { user_id: 42 }.build();
+    assert_eq!(
+        format!("{:?}", e),
+        "Leaf { user_id: 42, backtrace: Backtrace(<captured>) }"
+    );
+}
+
+#[test]
+fn backtraces_are_shown_by_the_alternate_format() {
+    let e = Leaf { user_id: 42 }.build();
+    let debug = format!("{:#?}", e);
+    assert!(debug.starts_with("Leaf {\n    user_id: 42,\n    backtrace: "));
+    assert!(!debug.contains("<captured>"), "{}", debug);
+}
+
+#[test]
+fn missing_optional_backtraces_are_shown() {
+    let e = Optional.build();
+    if ErrorCompat::backtrace(&e).is_none() {
+        assert_eq!(format!("{:?}", e), "Optional { backtrace: None }");
+    }
+}
+
+#[test]
+fn sources_are_shown_in_declaration_order() {
+    let e = Boom { id: 1 }
+        .fail::<()>()
+        .context(Wrapped { name: "alice" })
+        .unwrap_err();
+    assert_eq!(
+        format!("{:?}", e),
+        r#"Wrapped { name: "alice", source: Boom { id: 1 } }"#
+    );
+}
+
+#[test]
+fn positional_and_unit_variants_are_shown() {
+    let e = Boom { id: 2 }
+        .fail::<()>()
+        .context(Positional(7_u8))
+        .unwrap_err();
+    assert_eq!(format!("{:?}", e), "Positional(Boom { id: 2 }, 7)");
+    assert_eq!(format!("{:?}", Unit.build()), "Unit");
+}
+
+mod structs {
+    use snafu::{Backtrace, Snafu};
+
+    #[derive(Snafu)]
+    #[snafu(debug)]
+    struct Error {
+        r#type: &'static str,
+        backtrace: Backtrace,
+    }
+
+    #[derive(Snafu)]
+    #[snafu(debug)]
+    struct OpaqueError(Error);
+
+    #[test]
+    fn named_structs_hide_backtraces() {
+        let e = Context { r#type: "disk" }.build();
+        assert_eq!(
+            format!("{:?}", e),
+            r#"Error { type: "disk", backtrace: Backtrace(<captured>) }"#
+        );
+    }
+
+    #[test]
+    fn tuple_structs_show_the_inner_error() {
+        let e = OpaqueError::from(Context { r#type: "disk" }.build());
+        assert_eq!(
+            format!("{:?}", e),
+            r#"OpaqueError(Error { type: "disk", backtrace: Backtrace(<captured>) })"#
+        );
+    }
+}
@@ -9,6 +9,7 @@ enum InnerError {
 #[derive(Debug, Snafu)]
 enum Error {
     NoDisplay { source: InnerError },
+    NoDisplayOrSource { user_id: i32 },
 }
 
 #[test]
@@ -16,3 +17,9 @@ fn default_error_display() {
     let err: Error = AnError.fail::<()>().context(NoDisplay).unwrap_err();
     assert_eq!(format!("{}", err), "NoDisplay: inner error",);
 }
+
+#[test]
+fn default_error_display_without_source() {
+    let err = NoDisplayOrSource { user_id: 42 }.build();
+    assert_eq!(format!("{}", err), "NoDisplayOrSource");
+}
@@ -0,0 +1,84 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu, PartialEq)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(compare)]
+enum Error {
+    #[snafu(display("Failed after {} retries talking to {}", retries, host))]
+    Leaf {
+        host: String,
+        #[snafu(default)]
+        retries: u32,
+    },
+
+    #[snafu(display("Failed with status {}", status))]
+    WithSource {
+        source: InnerError,
+        #[snafu(default = 500)]
+        status: u16,
+    },
+
+    WithExpression {
+        #[snafu(default(String::from("unknown")))]
+        user: String,
+    },
+
+    #[snafu(display("{} at {}", _0, _1))]
+    Tuple(#[snafu(source(false))] &'static str, #[snafu(default)] u8),
+}
+
+#[test]
+fn default_fields_are_not_part_of_the_selector() {
+    let e = Leaf { host: "example" }.build();
+    assert_eq!(
+        e,
+        Error::Leaf {
+            host: "example".into(),
+            retries: 0,
+        }
+    );
+    assert_eq!(e.to_string(), "Failed after 0 retries talking to example");
+}
+
+#[test]
+fn default_fields_can_use_an_expression() {
+    let e = Boom.fail::<()>().context(WithSource).unwrap_err();
+    assert_eq!(e.to_string(), "Failed with status 500");
+
+    let e = WithExpression.build();
+    assert_eq!(
+        e,
+        Error::WithExpression {
+            user: "unknown".into(),
+        }
+    );
+}
+
+#[test]
+fn default_fields_can_be_positional() {
+    let e = Tuple("parse").build();
+    assert_eq!(e.to_string(), "parse at 0");
+}
+
+mod structs {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("{} attempts", attempts))]
+    struct Error {
+        name: String,
+        #[snafu(default = 3)]
+        attempts: u8,
+    }
+
+    #[test]
+    fn default_fields_work_on_structs() {
+        let e = Context { name: "alpha" }.build();
+        assert_eq!(e.name, "alpha");
+        assert_eq!(e.to_string(), "3 attempts");
+    }
+}
@@ -0,0 +1,96 @@
+use snafu::{Backtrace, GenerateBacktrace, OptionExt, ResultExt, Snafu};
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Each test uses its own backtrace type and counter as tests run in
+// parallel.
+macro_rules! counting_backtrace {
+    ($name:ident, $counter:ident) => {
+        static $counter: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug)]
+        struct $name;
+
+        impl GenerateBacktrace for $name {
+            fn generate() -> Self {
+                $counter.fetch_add(1, Ordering::SeqCst);
+                $name
+            }
+
+            fn as_backtrace(&self) -> Option<&Backtrace> {
+                None
+            }
+        }
+    };
+}
+
+counting_backtrace!(WithContextBacktrace, WITH_CONTEXT_CAPTURES);
+counting_backtrace!(ContextBacktrace, CONTEXT_CAPTURES);
+counting_backtrace!(OptionBacktrace, OPTION_CAPTURES);
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    WithContext {
+        source: InnerError,
+        backtrace: WithContextBacktrace,
+    },
+
+    Context {
+        source: InnerError,
+        backtrace: ContextBacktrace,
+    },
+
+    Missing {
+        backtrace: OptionBacktrace,
+    },
+}
+
+fn succeed() -> Result<i32, InnerError> {
+    Ok(42)
+}
+
+fn fail() -> Result<i32, InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn with_context_does_no_work_on_ok() {
+    let called = Cell::new(0);
+    let selector = || {
+        called.set(called.get() + 1);
+        WithContext
+    };
+
+    assert_eq!(succeed().with_context(selector).unwrap(), 42);
+    assert_eq!(called.get(), 0);
+    assert_eq!(WITH_CONTEXT_CAPTURES.load(Ordering::SeqCst), 0);
+
+    fail().with_context(selector).unwrap_err();
+    assert_eq!(called.get(), 1);
+    assert_eq!(WITH_CONTEXT_CAPTURES.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn context_does_not_capture_a_backtrace_on_ok() {
+    assert_eq!(succeed().context(Context).unwrap(), 42);
+    assert_eq!(CONTEXT_CAPTURES.load(Ordering::SeqCst), 0);
+
+    fail().context(Context).unwrap_err();
+    assert_eq!(CONTEXT_CAPTURES.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn option_context_does_not_capture_a_backtrace_on_some() {
+    assert_eq!(Some(42).context(Missing).unwrap(), 42);
+    assert_eq!(OPTION_CAPTURES.load(Ordering::SeqCst), 0);
+
+    None::<i32>.context(Missing).unwrap_err();
+    assert_eq!(OPTION_CAPTURES.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,59 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(deprecated = "use `ReadConfig` instead")]
+    OpenConfig {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[snafu(deprecated("use `ReadConfig` instead"))]
+    LoadConfig { path: String },
+
+    #[snafu(deprecated, builder)]
+    Legacy { attempt: Option<u32> },
+
+    ReadConfig {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_selectors_can_still_be_used() {
+    let r: Result<(), _> = Err(std::io::Error::new(std::io::ErrorKind::Other, "gone"));
+    let e = r.context(OpenConfig { path: "a.toml" }).unwrap_err();
+    assert!(matches!(e, Error::OpenConfig { .. }));
+
+    let e = LoadConfig { path: "a.toml" }.build();
+    assert!(matches!(e, Error::LoadConfig { .. }));
+
+    let e = Legacy::new().with_attempt(2).build();
+    assert!(matches!(e, Error::Legacy { attempt: Some(2) }));
+}
+
+#[test]
+fn other_selectors_are_not_deprecated() {
+    let r: Result<(), _> = Err(std::io::Error::new(std::io::ErrorKind::Other, "gone"));
+    let e = r.context(ReadConfig { path: "a.toml" }).unwrap_err();
+    assert!(matches!(e, Error::ReadConfig { .. }));
+}
+
+mod structs {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(deprecated = "use `crate::Error` instead")]
+    struct Error {
+        path: String,
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn struct_selectors_can_be_deprecated() {
+        let e = Context { path: "a.toml" }.build();
+        assert_eq!(e.path, "a.toml");
+    }
+}
@@ -0,0 +1,43 @@
+#![allow(deprecated)]
+
+use snafu::Snafu;
+use std::error::Error as _;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(description = "the configuration could not be read")]
+    ReadConfig {
+        path: String,
+    },
+
+    Unnamed,
+}
+
+#[test]
+fn description_can_be_provided() {
+    let e = ReadConfig { path: "app.toml" }.build();
+    assert_eq!(e.description(), "the configuration could not be read");
+}
+
+#[test]
+fn description_defaults_to_the_variant_name() {
+    let e = Unnamed.build();
+    assert!(e.description().contains("Unnamed"));
+}
+
+mod structs {
+    use snafu::Snafu;
+    use std::error::Error as _;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(description = "a user was not found")]
+    struct Error {
+        user_id: u32,
+    }
+
+    #[test]
+    fn description_can_be_provided_for_structs() {
+        let e = Context { user_id: 1_u32 }.build();
+        assert_eq!(e.description(), "a user was not found");
+    }
+}
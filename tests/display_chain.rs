@@ -0,0 +1,69 @@
+use snafu::{DisplayChain, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("The disk is full"))]
+    DiskFull,
+}
+
+#[derive(Debug, Snafu)]
+enum MiddleError {
+    #[snafu(display("Could not write the file"))]
+    Write { source: InnerError },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Saving failed: {}", DisplayChain::new(source)))]
+    Save { source: MiddleError },
+
+    #[snafu(display("Flushing failed because {}", DisplayChain::new(source).separator(", because ")))]
+    Flush { source: MiddleError },
+
+    #[snafu(display("Boxed: {}", DisplayChain::new(&**source)))]
+    Boxed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+fn middle() -> Result<(), MiddleError> {
+    DiskFull.fail().context(Write)
+}
+
+#[test]
+fn every_source_is_displayed() {
+    let e = middle().context(Save).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Saving failed: Could not write the file: The disk is full"
+    );
+}
+
+#[test]
+fn the_separator_can_be_changed() {
+    let e = middle().context(Flush).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Flushing failed because Could not write the file, because The disk is full"
+    );
+}
+
+#[test]
+fn trait_objects_can_be_displayed() {
+    let e = middle()
+        .map_err(|e| Box::new(e) as _)
+        .context(Boxed)
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Boxed: Could not write the file: The disk is full"
+    );
+}
+
+#[test]
+fn an_error_without_sources_is_displayed_alone() {
+    assert_eq!(
+        DisplayChain::new(&InnerError::DiskFull).to_string(),
+        "The disk is full"
+    );
+}
@@ -0,0 +1,60 @@
+use snafu::{DisplayError, ResultExt, Snafu, StringError};
+use std::error::Error as _;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not load"))]
+    Load { source: StringError },
+
+    #[snafu(display("Could not connect"))]
+    Connect { source: DisplayError<u16> },
+}
+
+fn load() -> Result<(), String> {
+    Err("not found".into())
+}
+
+fn connect() -> Result<(), u16> {
+    Err(503)
+}
+
+#[test]
+fn string_errors_can_be_used_as_a_source() {
+    let e = load().map_err(StringError::new).context(Load).unwrap_err();
+    let source = e.source().expect("Must have a source");
+    assert_eq!(source.to_string(), "not found");
+    assert!(source.downcast_ref::<StringError>().is_some());
+}
+
+#[test]
+fn string_errors_can_be_created_from_strings() {
+    let e = StringError::from("borrowed");
+    assert_eq!(e.as_str(), "borrowed");
+    assert_eq!(e, StringError::from(String::from("borrowed")));
+    assert_eq!(e.into_inner(), "borrowed");
+}
+
+#[test]
+fn display_errors_can_be_used_as_a_source() {
+    let e = connect()
+        .map_err(DisplayError::new)
+        .context(Connect)
+        .unwrap_err();
+    let source = e.source().expect("Must have a source");
+    assert_eq!(source.to_string(), "503");
+}
+
+#[test]
+fn display_errors_use_display_for_debug() {
+    struct NotDebug;
+
+    impl std::fmt::Display for NotDebug {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "not debug")
+        }
+    }
+
+    let e = DisplayError::new(NotDebug);
+    assert_eq!(format!("{:?}", e), "DisplayError(not debug)");
+    assert_eq!(e.to_string(), "not debug");
+}
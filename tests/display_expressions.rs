@@ -0,0 +1,76 @@
+use snafu::Snafu;
+use std::path::PathBuf;
+
+const PREFIX: &str = "request";
+
+fn describe(code: &i32, kind: &str) -> String {
+    format!("{} error {}", kind, code)
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("{} failed: {}", PREFIX, describe(code, kind)))]
+    RequestFailed { code: i32, kind: String },
+
+    #[snafu(display("{} of {} bytes", size * count, size))]
+    TooLarge { size: usize, count: usize },
+
+    #[snafu(display("Could not open {filename.display()}: {code}"))]
+    InlineExpression { filename: PathBuf, code: i32 },
+
+    #[snafu(display("{size:>4}|{size * count:<4}|{{literal}}"))]
+    InlineWithSpec { size: usize, count: usize },
+
+    #[snafu(display("{} then {code} and {code}", describe(code, kind), code = code + 1))]
+    InlineMixed { code: i32, kind: String },
+}
+
+#[test]
+fn free_functions_and_constants_can_be_used() {
+    let e = RequestFailed {
+        code: 404,
+        kind: "client",
+    }
+    .build();
+    assert_eq!(e.to_string(), "request failed: client error 404");
+}
+
+#[test]
+fn fields_can_be_combined() {
+    let e = TooLarge {
+        size: 4_usize,
+        count: 3_usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "12 of 4 bytes");
+}
+
+#[test]
+fn inline_expressions_can_be_used() {
+    let e = InlineExpression {
+        filename: "/etc/app.toml",
+        code: 2,
+    }
+    .build();
+    assert_eq!(e.to_string(), "Could not open /etc/app.toml: 2");
+}
+
+#[test]
+fn inline_arguments_keep_their_format_specification() {
+    let e = InlineWithSpec {
+        size: 4_usize,
+        count: 3_usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "   4|12  |{literal}");
+}
+
+#[test]
+fn inline_arguments_can_be_mixed_with_explicit_arguments() {
+    let e = InlineMixed {
+        code: 1,
+        kind: "server",
+    }
+    .build();
+    assert_eq!(e.to_string(), "server error 1 then 2 and 2");
+}
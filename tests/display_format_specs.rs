@@ -0,0 +1,139 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("code {:04x}", code))]
+    Hex { code: u32 },
+
+    #[snafu(display("code {:#06X}", code))]
+    AlternateHex { code: u32 },
+
+    #[snafu(display("[{:>8}] [{:<6}] [{:^7}]", name, name, name))]
+    Aligned { name: String },
+
+    #[snafu(display("[{:*>8}]", name))]
+    Filled { name: String },
+
+    #[snafu(display("ratio {:.3}", ratio))]
+    Precision { ratio: f64 },
+
+    #[snafu(display("ratio {:08.2}", ratio))]
+    ZeroPaddedPrecision { ratio: f64 },
+
+    #[snafu(display("[{:>width$}] {:.prec$}", code, ratio, width = 6, prec = 1))]
+    NamedWidthAndPrecision { code: u32, ratio: f64 },
+
+    #[snafu(display = r#"("code {:04x}", code)"#)]
+    AssignedHex { code: u32 },
+
+    #[snafu(display("[{code:>width$}] [{ratio:.prec$}]"))]
+    InlineWidthFromFields {
+        code: u32,
+        width: usize,
+        ratio: f64,
+        prec: usize,
+    },
+
+    #[snafu(display("code {code:04x} ratio {ratio:.3} name {name:>6}"))]
+    Inline { code: u32, ratio: f64, name: String },
+
+    #[snafu(display("code {code:#x} {code:?} {ratio:e}"))]
+    InlineOtherTraits { code: u32, ratio: f64 },
+
+    /// Hex code {code:04x}
+    DocComment { code: u32 },
+
+    /// Padded [{code:>width$}]
+    DocCommentWidthFromField { code: u32, width: usize },
+}
+
+#[test]
+fn hexadecimal_and_zero_padding() {
+    assert_eq!(Hex { code: 42_u32 }.build().to_string(), "code 002a");
+    assert_eq!(
+        AlternateHex { code: 255_u32 }.build().to_string(),
+        "code 0x00FF"
+    );
+}
+
+#[test]
+fn alignment_and_fill() {
+    assert_eq!(
+        Aligned { name: "abc" }.build().to_string(),
+        "[     abc] [abc   ] [  abc  ]"
+    );
+    assert_eq!(Filled { name: "abc" }.build().to_string(), "[*****abc]");
+}
+
+#[test]
+fn precision() {
+    assert_eq!(
+        Precision { ratio: 1.0 / 3.0 }.build().to_string(),
+        "ratio 0.333"
+    );
+    assert_eq!(
+        ZeroPaddedPrecision { ratio: 12.345 }.build().to_string(),
+        "ratio 00012.35"
+    );
+}
+
+#[test]
+fn width_and_precision_arguments() {
+    let e = NamedWidthAndPrecision {
+        code: 7_u32,
+        ratio: 2.25,
+    }
+    .build();
+    assert_eq!(e.to_string(), "[     7] 2.2");
+}
+
+#[test]
+fn inline_fields_keep_their_specifiers() {
+    let e = Inline {
+        code: 42_u32,
+        ratio: 0.5,
+        name: "abc",
+    }
+    .build();
+    assert_eq!(e.to_string(), "code 002a ratio 0.500 name    abc");
+
+    let e = InlineOtherTraits {
+        code: 255_u32,
+        ratio: 1500.0,
+    }
+    .build();
+    assert_eq!(e.to_string(), "code 0xff 255 1.5e3");
+}
+
+#[test]
+fn inline_widths_and_precisions_can_refer_to_fields() {
+    let e = InlineWidthFromFields {
+        code: 7_u32,
+        width: 4_usize,
+        ratio: 0.125,
+        prec: 2_usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "[   7] [0.12]");
+}
+
+#[test]
+fn other_display_forms_keep_their_specifiers() {
+    assert_eq!(
+        AssignedHex { code: 42_u32 }.build().to_string(),
+        "code 002a"
+    );
+    assert_eq!(
+        DocCommentWidthFromField {
+            code: 7_u32,
+            width: 3_usize
+        }
+        .build()
+        .to_string(),
+        "Padded [  7]"
+    );
+    assert_eq!(
+        DocComment { code: 42_u32 }.build().to_string(),
+        "Hex code 002a"
+    );
+}
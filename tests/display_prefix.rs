@@ -0,0 +1,76 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner"))]
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_prefix = "database error: ")]
+enum Error {
+    #[snafu(display("could not connect to {}", host))]
+    Connect {
+        host: String,
+    },
+
+    /// the query {query} failed
+    Query {
+        query: String,
+    },
+
+    Unformatted,
+
+    WithSource {
+        source: InnerError,
+    },
+
+    #[snafu(context(false), transparent)]
+    Transparent {
+        source: std::fmt::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_prefix("config: "))]
+enum ParenthesizedError {
+    #[snafu(display("missing"))]
+    Missing,
+}
+
+#[test]
+fn prefix_is_added_to_explicit_formats() {
+    let e = Connect { host: "localhost" }.build();
+    assert_eq!(
+        e.to_string(),
+        "database error: could not connect to localhost"
+    );
+}
+
+#[test]
+fn prefix_is_added_to_doc_comments() {
+    let e = Query { query: "SELECT" }.build();
+    assert_eq!(e.to_string(), "database error: the query SELECT failed");
+}
+
+#[test]
+fn prefix_is_added_to_the_default_format() {
+    assert_eq!(
+        Unformatted.build().to_string(),
+        "database error: Unformatted"
+    );
+
+    let e = Boom.fail::<()>().context(WithSource).unwrap_err();
+    assert_eq!(e.to_string(), "database error: WithSource: inner");
+}
+
+#[test]
+fn prefix_is_not_added_to_transparent_variants() {
+    let e = Error::from(std::fmt::Error);
+    assert_eq!(e.to_string(), std::fmt::Error.to_string());
+}
+
+#[test]
+fn prefix_can_be_parenthesized() {
+    assert_eq!(Missing.build().to_string(), "config: missing");
+}
@@ -0,0 +1,82 @@
+use snafu::{ResultExt, Snafu};
+
+const USER_NOT_FOUND: &str = "User {} was not found in {}";
+const REORDERED: &str = "In {1}: user {0} is missing";
+const UNSUPPORTED: &str = "{{literal}} {name} {5} {:?} {}";
+
+mod messages {
+    pub const READ_FAILED: &str = "Could not read {}: {}";
+}
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display(fmt = USER_NOT_FOUND, user_id, group))]
+    UserNotFound { user_id: u32, group: String },
+
+    #[snafu(display(fmt = REORDERED, user_id, group))]
+    Reordered { user_id: u32, group: String },
+
+    #[snafu(display(fmt = messages::READ_FAILED, path, source))]
+    ReadFailed { path: String, source: InnerError },
+
+    #[snafu(display(fmt = select(*english), count))]
+    Dynamic { english: bool, count: usize },
+
+    #[snafu(display(fmt = UNSUPPORTED, value))]
+    Unsupported { value: u8 },
+}
+
+fn select(english: bool) -> &'static str {
+    if english {
+        "{} items"
+    } else {
+        "{} éléments"
+    }
+}
+
+#[test]
+fn positional_placeholders_are_filled_in_order() {
+    let e = UserNotFound {
+        user_id: 42_u32,
+        group: "admins",
+    }
+    .build();
+    assert_eq!(e.to_string(), "User 42 was not found in admins");
+}
+
+#[test]
+fn placeholders_can_refer_to_arguments_by_index() {
+    let e = Reordered {
+        user_id: 42_u32,
+        group: "admins",
+    }
+    .build();
+    assert_eq!(e.to_string(), "In admins: user 42 is missing");
+}
+
+#[test]
+fn the_format_can_be_a_path_or_an_expression() {
+    let e = Boom
+        .fail::<()>()
+        .context(ReadFailed { path: "/etc/app" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not read /etc/app: Boom");
+
+    let e = Dynamic {
+        english: false,
+        count: 3_usize,
+    }
+    .build();
+    assert_eq!(e.to_string(), "3 éléments");
+}
+
+#[test]
+fn unsupported_placeholders_are_written_unchanged() {
+    let e = Unsupported { value: 7_u8 }.build();
+    assert_eq!(e.to_string(), "{literal} {name} {5} {:?} 7");
+}
@@ -0,0 +1,96 @@
+use snafu::{ResultExt, Snafu};
+use std::fmt;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display(with = "display_limits"))]
+    Limits { low: u32, high: u32 },
+
+    #[snafu(display(with = "formatting::display_source"))]
+    WithSource { name: String, source: InnerError },
+
+    #[snafu(display(with = "display_position"))]
+    Positional(#[snafu(source(false))] &'static str, u8),
+}
+
+fn display_limits(low: &u32, high: &u32, f: &mut fmt::Formatter) -> fmt::Result {
+    if low > high {
+        write!(f, "The range {}..{} is empty", low, high)
+    } else {
+        write!(f, "The value must be between {} and {}", low, high)
+    }
+}
+
+mod formatting {
+    use std::fmt;
+
+    pub fn display_source(
+        name: &String,
+        source: &super::InnerError,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        write!(f, "{} failed: {}", name, source)
+    }
+}
+
+fn display_position(name: &&'static str, index: &u8, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}[{}]", name, index)
+}
+
+#[test]
+fn the_function_writes_the_message() {
+    let e = Limits {
+        low: 1_u32,
+        high: 9_u32,
+    }
+    .build();
+    assert_eq!(e.to_string(), "The value must be between 1 and 9");
+
+    let e = Limits {
+        low: 9_u32,
+        high: 1_u32,
+    }
+    .build();
+    assert_eq!(e.to_string(), "The range 9..1 is empty");
+}
+
+#[test]
+fn the_function_receives_the_source() {
+    let e = Boom
+        .fail::<()>()
+        .context(WithSource { name: "alpha" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "alpha failed: Boom");
+}
+
+#[test]
+fn the_function_receives_positional_fields_in_order() {
+    let e = Positional("items", 3_u8).build();
+    assert_eq!(e.to_string(), "items[3]");
+}
+
+mod structs {
+    use snafu::Snafu;
+    use std::fmt;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display(with = "display_error"))]
+    struct Error {
+        user_id: u32,
+    }
+
+    fn display_error(user_id: &u32, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "User #{} is not allowed", user_id)
+    }
+
+    #[test]
+    fn the_function_is_called_for_structs() {
+        let e = Context { user_id: 42_u32 }.build();
+        assert_eq!(e.to_string(), "User #42 is not allowed");
+    }
+}
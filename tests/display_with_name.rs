@@ -0,0 +1,54 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("disk on fire"))]
+    Disk,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_with_name)]
+enum Error {
+    #[snafu(display("Could not open {}", path))]
+    OpenConfig {
+        path: String,
+    },
+
+    Defaulted,
+
+    #[snafu(transparent, context(false))]
+    Transparent {
+        source: InnerError,
+    },
+}
+
+#[test]
+fn the_variant_name_is_written_before_the_message() {
+    let e = OpenConfig { path: "app.toml" }.build();
+    assert_eq!(e.to_string(), "[OpenConfig] Could not open app.toml");
+
+    assert_eq!(Defaulted.build().to_string(), "[Defaulted] Defaulted");
+}
+
+#[test]
+fn transparent_variants_are_not_labeled() {
+    let e: Error = Disk.build().into();
+    assert_eq!(e.to_string(), "disk on fire");
+}
+
+mod with_prefix {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display_with_name, display_prefix = "config: ")]
+    enum Error {
+        #[snafu(display("missing"))]
+        Missing { source: InnerError },
+    }
+
+    #[test]
+    fn the_name_comes_before_the_prefix() {
+        let e = Disk.fail::<()>().context(Missing).unwrap_err();
+        assert_eq!(e.to_string(), "[Missing] config: missing");
+    }
+}
@@ -14,6 +14,15 @@ enum Error {
 
     #[doc(hidden)]
     Hidden,
+
+    /// User {user_id} has no access to {path}
+    Interpolated { user_id: i32, path: String },
+
+    /// The index {index:>3} is out of range
+    FormatSpec { index: usize, length: usize },
+
+    /// Expected {{id}} literally
+    Braces { id: i32 },
 }
 
 #[test]
@@ -40,3 +49,40 @@ fn display_is_stronger_than_doc_comment() {
         "This is always stronger!",
     );
 }
+
+#[test]
+fn non_comment_doc_attributes_are_ignored() {
+    assert_eq!(Error::Hidden.to_string(), "Hidden");
+}
+
+#[test]
+fn doc_comment_can_interpolate_fields() {
+    assert_eq!(
+        Error::Interpolated {
+            user_id: 42,
+            path: "/etc".to_string(),
+        }
+        .to_string(),
+        "User 42 has no access to /etc",
+    );
+}
+
+#[test]
+fn doc_comment_fields_can_use_format_specs() {
+    assert_eq!(
+        Error::FormatSpec {
+            index: 7,
+            length: 3,
+        }
+        .to_string(),
+        "The index   7 is out of range",
+    );
+}
+
+#[test]
+fn doc_comment_escaped_braces_are_not_fields() {
+    assert_eq!(
+        Error::Braces { id: 1 }.to_string(),
+        "Expected {id} literally"
+    );
+}
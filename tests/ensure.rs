@@ -20,3 +20,32 @@ fn accepts_trailing_commas() {
     }
     let _ = example(42);
 }
+
+#[test]
+fn returns_from_the_enclosing_function() {
+    fn example(values: &[i32]) -> Result<usize> {
+        let mut checked = 0;
+        for &value in values {
+            {
+                ensure!(
+                    value > 0,
+                    AVeryLongVariantName {
+                        a_long_piece_of_information: value,
+                    }
+                );
+            }
+            checked += 1;
+        }
+        Ok(checked)
+    }
+
+    assert_eq!(example(&[1, 2, 3]).unwrap(), 3);
+
+    let e = example(&[1, -2, 3]).unwrap_err();
+    assert!(matches!(
+        e,
+        Error::AVeryLongVariantName {
+            a_long_piece_of_information: -2
+        }
+    ));
+}
@@ -0,0 +1,59 @@
+use snafu::{ChainCompat, ErrorCompat, IntoError, Snafu};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("level 0"))]
+struct Level0;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("level 1"))]
+    Level1 { source: Level0 },
+
+    #[snafu(display("level 2"))]
+    Level2 {
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+}
+
+fn nested() -> Error {
+    let e = Level1.into_error(Level0);
+    Level2.into_error(e)
+}
+
+#[test]
+fn iterates_over_the_error_and_its_sources() {
+    let e = nested();
+    let messages: Vec<_> = e.iter_chain().map(ToString::to_string).collect();
+    assert_eq!(messages, ["level 2", "level 1", "level 0"]);
+}
+
+#[test]
+fn skipping_the_first_item_yields_only_the_sources() {
+    let e = nested();
+    let messages: Vec<_> = e.iter_chain().skip(1).map(ToString::to_string).collect();
+    assert_eq!(messages, ["level 1", "level 0"]);
+}
+
+#[test]
+fn can_find_a_specific_error_type() {
+    let e = nested();
+    let found = ChainCompat::new(&e).find_map(|e| e.downcast_ref::<Level0>());
+    assert!(found.is_some());
+}
+
+#[test]
+fn an_error_without_a_source_yields_only_itself() {
+    assert_eq!(Level0.iter_chain().count(), 1);
+}
+
+#[test]
+fn the_root_cause_is_the_last_source() {
+    let e = nested();
+    assert_eq!(e.root_cause().to_string(), "level 0");
+}
+
+#[test]
+fn an_error_without_a_source_is_its_own_root_cause() {
+    assert_eq!(Level0.root_cause().to_string(), "level 0");
+}
@@ -0,0 +1,45 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(exit_code(2))]
+    InvalidArguments,
+
+    #[snafu(exit_code = 74)]
+    ReadInput {
+        source: InnerError,
+    },
+
+    Unexpected,
+}
+
+#[test]
+fn exit_code_is_returned_for_each_variant() {
+    assert_eq!(InvalidArguments.build().exit_code(), 2);
+
+    let e = Err::<(), _>(InnerError::Boom)
+        .context(ReadInput)
+        .unwrap_err();
+    assert_eq!(e.exit_code(), 74);
+}
+
+#[test]
+fn variants_without_an_exit_code_use_one() {
+    assert_eq!(Unexpected.build().exit_code(), 1);
+}
+
+#[test]
+fn report_returns_the_exit_code() {
+    let result: Result<(), Error> = Err(InnerError::Boom).context(ReadInput);
+    assert_eq!(snafu::report(result, Error::exit_code), 74);
+}
+
+#[test]
+fn report_returns_zero_on_success() {
+    assert_eq!(snafu::report(Ok::<_, Error>(()), Error::exit_code), 0);
+}
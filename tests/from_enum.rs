@@ -0,0 +1,110 @@
+use snafu::{Backtrace, IntoError, ResultExt, Snafu};
+
+mod low {
+    use snafu::{Backtrace, Snafu};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub(crate)))]
+    pub(crate) enum Error {
+        ReadConfig {
+            path: String,
+            source: std::io::Error,
+        },
+        Parse {
+            line: u32,
+            backtrace: Backtrace,
+        },
+        Empty,
+        Positional(std::fmt::Error, u8),
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(from_enum(low::Error))]
+enum Error {
+    #[snafu(display("Could not read {}", path))]
+    ReadConfig {
+        path: String,
+        source: std::io::Error,
+    },
+    #[snafu(display("Bad line {}", line))]
+    Parse {
+        line: u64,
+        backtrace: Backtrace,
+    },
+    Empty,
+    Positional(std::fmt::Error, u32),
+    #[snafu(from_enum(false))]
+    HighLevelOnly {
+        reason: String,
+    },
+}
+
+fn read() -> Result<(), low::Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::NotFound, "gone")).context(low::ReadConfig {
+        path: "/etc/app.toml",
+    })
+}
+
+#[test]
+fn variants_with_the_same_name_are_converted() {
+    let e = Error::from(read().unwrap_err());
+    assert!(matches!(e, Error::ReadConfig { ref path, .. } if path == "/etc/app.toml"));
+    assert_eq!(e.to_string(), "Could not read /etc/app.toml");
+}
+
+#[test]
+fn fields_are_converted_with_into() {
+    let e: Error = low::Parse { line: 7_u32 }.build().into();
+    assert!(matches!(e, Error::Parse { line: 7, .. }));
+    assert_eq!(e.to_string(), "Bad line 7");
+
+    let e: Error = low::Positional(3_u8).into_error(std::fmt::Error).into();
+    assert!(matches!(e, Error::Positional(_, 3)));
+}
+
+#[test]
+fn unit_variants_are_converted() {
+    let e: Error = low::Empty.build().into();
+    assert!(matches!(e, Error::Empty));
+}
+
+#[test]
+fn question_mark_converts_the_error() {
+    fn example() -> Result<(), Error> {
+        read()?;
+        HighLevelOnly {
+            reason: "unreachable",
+        }
+        .fail()
+    }
+
+    assert!(matches!(example(), Err(Error::ReadConfig { .. })));
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    mod low {
+        use snafu::Snafu;
+
+        #[derive(Debug, Snafu)]
+        #[snafu(visibility(pub(crate)))]
+        pub(crate) enum Error<T: std::fmt::Debug> {
+            Invalid { value: T },
+        }
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(from_enum(low::Error<T>))]
+    enum Error<T: std::fmt::Debug> {
+        Invalid { value: T },
+    }
+
+    #[test]
+    fn generic_enums_are_converted() {
+        let low: low::Error<i32> = low::Invalid { value: 42 }.build();
+        let e: Error<i32> = low.into();
+        assert!(matches!(e, Error::Invalid { value: 42 }));
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::{Backtrace, ResultExt, Snafu};
 
 type BoxError = Box<dyn std::error::Error>;
@@ -97,4 +99,43 @@ mod bounds {
             check_bounds::<ApiError<i32>>();
         }
     }
+
+    mod multiple_bounds {
+        use snafu::{IntoError, Snafu};
+        use std::fmt::{Debug, Display};
+
+        #[derive(Debug, Snafu)]
+        enum Error<T, U>
+        where
+            T: Display + Clone,
+            U: Debug + Default,
+        {
+            #[snafu(display("Boom: {} ({:?})", value, extra))]
+            Boom {
+                value: T,
+                extra: U,
+                source: std::io::Error,
+            },
+
+            #[snafu(display("Bang: {}", value))]
+            Bang { value: T },
+        }
+
+        #[test]
+        fn implements_error() {
+            fn check_bounds<T: std::error::Error>() {}
+            check_bounds::<Error<i32, bool>>();
+
+            let io = std::io::Error::new(std::io::ErrorKind::Other, "inner");
+            let e: Error<i32, bool> = Boom {
+                value: 1,
+                extra: true,
+            }
+            .into_error(io);
+            assert_eq!(e.to_string(), "Boom: 1 (true)");
+
+            let e: Error<i32, bool> = Bang { value: 2 }.build();
+            assert_eq!(e.to_string(), "Bang: 2");
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use snafu::{Backtrace, ResultExt, Snafu};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    OpenConfig {
+        #[snafu(getter)]
+        filename: PathBuf,
+        source: std::io::Error,
+    },
+
+    SaveConfig {
+        #[snafu(getter)]
+        filename: PathBuf,
+        #[snafu(getter)]
+        attempt: u32,
+    },
+
+    Retry {
+        #[snafu(getter)]
+        attempt: u32,
+        backtrace: Backtrace,
+    },
+
+    Unrelated {
+        filename: PathBuf,
+    },
+}
+
+fn open() -> Result<(), Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::NotFound, "gone")).context(OpenConfig {
+        filename: "/etc/app.toml",
+    })
+}
+
+#[test]
+fn returns_the_field_from_variants_that_have_it() {
+    let e = open().unwrap_err();
+    assert_eq!(
+        e.filename().map(PathBuf::as_path),
+        Some(Path::new("/etc/app.toml"))
+    );
+
+    let e = SaveConfig {
+        filename: "/tmp/app.toml",
+        attempt: 2_u32,
+    }
+    .build();
+    assert_eq!(
+        e.filename().map(PathBuf::as_path),
+        Some(Path::new("/tmp/app.toml"))
+    );
+    assert_eq!(e.attempt(), Some(&2));
+}
+
+#[test]
+fn returns_none_for_other_variants() {
+    let e = open().unwrap_err();
+    assert_eq!(e.attempt(), None);
+
+    let e = Retry { attempt: 3_u32 }.build();
+    assert!(e.filename().is_none());
+    assert_eq!(e.attempt(), Some(&3));
+}
+
+#[test]
+fn fields_without_the_attribute_are_skipped() {
+    let e = Unrelated { filename: "/" }.build();
+    assert!(e.filename().is_none());
+}
+
+mod raw_idents {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Mismatch {
+            #[snafu(getter)]
+            r#type: String,
+        },
+    }
+
+    #[test]
+    fn supports_raw_identifiers() {
+        let e = Mismatch { r#type: "u8" }.build();
+        assert_eq!(e.r#type().map(String::as_str), Some("u8"));
+    }
+}
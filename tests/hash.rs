@@ -0,0 +1,130 @@
+use snafu::{Backtrace, IntoError, Location, ResultExt, Snafu};
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Snafu)]
+enum InnerError {
+    Boom { id: i32 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(compare, hash)]
+enum Error {
+    UserIdInvalid {
+        user_id: i32,
+        backtrace: Backtrace,
+    },
+
+    Wrapped {
+        source: InnerError,
+        #[snafu(location)]
+        location: Location,
+    },
+
+    Io {
+        name: String,
+        #[snafu(compare(false))]
+        source: std::io::Error,
+    },
+
+    Skipped {
+        #[snafu(hash(false))]
+        source: InnerError,
+    },
+
+    Positional(InnerError, u8),
+
+    Unit,
+}
+
+impl Eq for Error {}
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn io_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, "boom")
+}
+
+#[test]
+fn equal_errors_have_equal_hashes() {
+    let a = Boom { id: 1 }.fail::<()>().context(Wrapped).unwrap_err();
+    let b = Boom { id: 1 }.fail::<()>().context(Wrapped).unwrap_err();
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+
+    let a = Err::<(), _>(io_error())
+        .context(Io { name: "a" })
+        .unwrap_err();
+    let b = Err::<(), _>(io_error())
+        .context(Io { name: "a" })
+        .unwrap_err();
+    assert_eq!(hash_of(&a), hash_of(&b));
+}
+
+#[test]
+// With the `backtraces` feature, `Backtrace` resolves its symbols
+// lazily behind a lock. The generated `Hash` and `PartialEq` skip
+// backtraces, so that state cannot change an error's hash.
+#[allow(clippy::mutable_key_type)]
+fn distinct_errors_are_deduplicated() {
+    let mut seen = HashSet::new();
+    seen.insert(UserIdInvalid { user_id: 0 }.build());
+    seen.insert(UserIdInvalid { user_id: 0 }.build());
+    seen.insert(UserIdInvalid { user_id: 1 }.build());
+    seen.insert(Positional(7_u8).into_error(InnerError::Boom { id: 1 }));
+    seen.insert(Positional(7_u8).into_error(InnerError::Boom { id: 1 }));
+    seen.insert(Unit.build());
+    assert_eq!(seen.len(), 4);
+}
+
+#[test]
+fn variants_with_the_same_fields_hash_differently() {
+    let a = Boom { id: 1 }.fail::<()>().context(Wrapped).unwrap_err();
+    let b = Positional(0_u8).into_error(InnerError::Boom { id: 1 });
+    assert_ne!(hash_of(&a), hash_of(&b));
+}
+
+#[test]
+fn unhashed_fields_are_skipped() {
+    let a = Boom { id: 1 }.fail::<()>().context(Skipped).unwrap_err();
+    let b = Boom { id: 2 }.fail::<()>().context(Skipped).unwrap_err();
+    assert_ne!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+}
+
+mod structs {
+    use super::hash_of;
+    use snafu::{Backtrace, Snafu};
+
+    #[derive(Debug, Snafu)]
+    #[snafu(compare, hash)]
+    struct Error {
+        id: i32,
+        backtrace: Backtrace,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(compare, hash)]
+    struct OpaqueError(Error);
+
+    #[test]
+    fn named_structs_can_be_hashed() {
+        assert_eq!(
+            hash_of(&Context { id: 1 }.build()),
+            hash_of(&Context { id: 1 }.build())
+        );
+    }
+
+    #[test]
+    fn tuple_structs_can_be_hashed() {
+        let a = OpaqueError::from(Context { id: 1 }.build());
+        let b = OpaqueError::from(Context { id: 1 }.build());
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+}
@@ -0,0 +1,39 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(code("E0001"), help("Create the file with `app init`"))]
+    OpenConfig {
+        filename: String,
+    },
+
+    #[snafu(help = "Check the line for a missing quote")]
+    Parse(#[snafu(source(false))] usize),
+
+    Eof,
+}
+
+#[test]
+fn help_is_returned_for_each_variant() {
+    let e = OpenConfig {
+        filename: "/etc/app.toml",
+    }
+    .build();
+    assert_eq!(e.help(), Some("Create the file with `app init`"));
+    assert_eq!(
+        Parse(3_usize).build().help(),
+        Some("Check the line for a missing quote")
+    );
+}
+
+#[test]
+fn variants_without_help_return_none() {
+    assert_eq!(Eof.build().help(), None);
+}
+
+#[test]
+fn help_is_independent_of_code_and_display() {
+    let e = OpenConfig { filename: "" }.build();
+    assert_eq!(e.code(), Some("E0001"));
+    assert_eq!(e.to_string(), "OpenConfig");
+}
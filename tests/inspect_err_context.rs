@@ -0,0 +1,32 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Wrapped {}", id))]
+    Wrapped { id: i32, source: InnerError },
+}
+
+#[test]
+fn closure_receives_the_wrapped_error() {
+    let mut seen = None;
+    let result: Result<(), _> = Err(InnerError::Boom);
+    let e = result
+        .inspect_err_context(Wrapped { id: 42 }, |e| seen = Some(e.to_string()))
+        .unwrap_err();
+
+    assert_eq!(seen.as_ref().map(String::as_str), Some("Wrapped 42"));
+    assert_eq!(e.to_string(), "Wrapped 42");
+}
+
+#[test]
+fn closure_is_not_called_on_success() {
+    let result: Result<i32, InnerError> = Ok(1);
+    let value: Result<i32, Error> =
+        result.inspect_err_context(Wrapped { id: 42 }, |_| panic!("Must not be called"));
+    assert_eq!(value.unwrap(), 1);
+}
@@ -0,0 +1,62 @@
+use snafu::{IteratorExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("Item {} is bad", item))]
+    Bad { item: i32 },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Processing batch {} failed: {}", batch, source))]
+    Batch { batch: String, source: InnerError },
+}
+
+fn check(item: i32) -> Result<i32, InnerError> {
+    if item < 0 {
+        Bad { item }.fail()
+    } else {
+        Ok(item * 2)
+    }
+}
+
+#[test]
+fn successful_values_are_unchanged() {
+    let values: Result<Vec<_>, Error> = vec![1, 2, 3]
+        .into_iter()
+        .map(check)
+        .contexts(Batch { batch: "first" })
+        .collect();
+    assert_eq!(values.unwrap(), [2, 4, 6]);
+}
+
+#[test]
+fn each_error_gets_the_context() {
+    let errors: Vec<_> = vec![-1, 2, -3]
+        .into_iter()
+        .map(check)
+        .contexts(Batch { batch: "second" })
+        .filter_map(Result::err)
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "Processing batch second failed: Item -1 is bad",
+            "Processing batch second failed: Item -3 is bad",
+        ]
+    );
+}
+
+#[test]
+fn collecting_stops_at_the_first_error() {
+    let result: Result<Vec<_>, Error> = vec![1, -2, -3]
+        .into_iter()
+        .map(check)
+        .contexts(Batch { batch: "third" })
+        .collect();
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Processing batch third failed: Item -2 is bad"
+    );
+}
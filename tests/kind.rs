@@ -0,0 +1,61 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(kind)]
+enum Error {
+    OpenConfig { filename: String },
+    Parse(#[snafu(source(false))] usize),
+    Eof,
+}
+
+#[test]
+fn kind_ignores_the_fields() {
+    let e = OpenConfig {
+        filename: "/etc/app.toml",
+    }
+    .build();
+    assert_eq!(e.kind(), ErrorKind::OpenConfig);
+    assert_eq!(Parse(3_usize).build().kind(), ErrorKind::Parse);
+    assert_eq!(Eof.build().kind(), ErrorKind::Eof);
+}
+
+#[test]
+fn kinds_can_be_matched() {
+    let description = match Eof.build().kind() {
+        ErrorKind::OpenConfig | ErrorKind::Parse => "other",
+        ErrorKind::Eof => "end of file",
+    };
+    assert_eq!(description, "end of file");
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(kind)]
+    pub enum GenericError<T>
+    where
+        T: std::fmt::Debug + std::fmt::Display,
+    {
+        Invalid { value: T },
+    }
+
+    #[test]
+    fn kind_is_available_for_generic_errors() {
+        let e: GenericError<i32> = Invalid { value: 42 }.build();
+        assert_eq!(e.kind(), GenericErrorKind::Invalid);
+    }
+}
+
+mod empty {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(kind)]
+    pub enum EmptyError {}
+
+    #[allow(dead_code)]
+    fn kind(e: &EmptyError) -> EmptyErrorKind {
+        e.kind()
+    }
+}
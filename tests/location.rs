@@ -0,0 +1,143 @@
+use snafu::{ErrorCompat, Location, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("wrapped at {}", location))]
+    Wrapped {
+        source: InnerError,
+        location: snafu::Location,
+    },
+
+    Leaf {
+        id: i32,
+        #[snafu(location)]
+        created_at: Location,
+    },
+
+    OptedOut {
+        #[snafu(location(false))]
+        place: snafu::Location,
+    },
+
+    ElsewhereNamed {
+        place: geo::Location,
+        source: InnerError,
+    },
+
+    NoLocation,
+}
+
+mod geo {
+    #[derive(Debug)]
+    pub struct Location;
+}
+
+fn inner() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn location_fields_are_populated() {
+    let e = inner().context(Wrapped).unwrap_err();
+    let location = ErrorCompat::location(&e).expect("Must have a location");
+    assert_eq!(location.file, file!());
+    assert!(e.to_string().starts_with("wrapped at "));
+}
+
+#[test]
+fn location_attribute_marks_any_field() {
+    let e = Leaf { id: 42 }.build();
+    assert!(ErrorCompat::location(&e).is_some());
+}
+
+#[test]
+fn location_fields_can_opt_out() {
+    let place = snafu::location!();
+    let e = OptedOut { place }.build();
+    assert!(ErrorCompat::location(&e).is_none());
+}
+
+#[test]
+fn other_types_named_location_are_not_populated() {
+    let place = geo::Location;
+    let e = inner().context(ElsewhereNamed { place }).unwrap_err();
+    assert!(ErrorCompat::location(&e).is_none());
+}
+
+#[test]
+fn variants_without_a_location_return_none() {
+    assert!(ErrorCompat::location(&NoLocation.build()).is_none());
+}
+
+#[test]
+fn context_macro_records_the_invocation() {
+    let line = line!() + 1;
+    let e: Error = snafu::context!(inner(), Wrapped).unwrap_err();
+    let location = ErrorCompat::location(&e).unwrap();
+    assert_eq!(location.file, file!());
+    assert_eq!(location.line, line);
+}
+
+#[test]
+fn context_with_location_uses_the_given_location() {
+    let given = Location::new("elsewhere.rs", 1, 2);
+    let e = inner().context_with_location(Wrapped, given).unwrap_err();
+    assert_eq!(ErrorCompat::location(&e), Some(&given));
+}
+
+#[test]
+fn context_with_location_ignores_the_location_without_a_field() {
+    #[derive(Debug, Snafu)]
+    struct Unlocated {
+        source: InnerError,
+    }
+
+    let given = Location::new("elsewhere.rs", 1, 2);
+    let e = inner()
+        .context_with_location(UnlocatedContext, given)
+        .unwrap_err();
+    assert!(ErrorCompat::location(&e).is_none());
+}
+
+#[test]
+fn location_displays_as_file_line_column() {
+    let location = Location::new("src/main.rs", 12, 5);
+    assert_eq!(location.to_string(), "src/main.rs:12:5");
+}
+
+#[cfg(feature = "rust_1_46")]
+mod track_caller {
+    use super::*;
+    use snafu::ensure;
+
+    fn check_ensure(id: i32) -> Result<(), Error> {
+        ensure!(id > 0, Leaf { id });
+        Ok(())
+    }
+    const ENSURE_LINE: u32 = line!() - 3;
+
+    #[test]
+    fn context_records_the_caller() {
+        let line = line!() + 1;
+        let e = inner().context(Wrapped).unwrap_err();
+        assert_eq!(ErrorCompat::location(&e).unwrap().line, line);
+    }
+
+    #[test]
+    fn build_records_the_caller() {
+        let line = line!() + 1;
+        let e = Leaf { id: 1 }.build();
+        assert_eq!(ErrorCompat::location(&e).unwrap().line, line);
+    }
+
+    #[test]
+    fn ensure_records_the_caller() {
+        let e = check_ensure(-1).unwrap_err();
+        assert_eq!(ErrorCompat::location(&e).unwrap().line, ENSURE_LINE);
+    }
+}
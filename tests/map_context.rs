@@ -0,0 +1,31 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Parsing {} failed", input))]
+    Parse { input: String, source: InnerError },
+}
+
+#[test]
+fn ok_values_are_mapped() {
+    let r: Result<u8, InnerError> = Ok(20);
+    let v = r.map_context(|v| u32::from(v) * 2, Parse { input: "20" });
+    assert_eq!(v.unwrap(), 40);
+}
+
+#[test]
+fn errors_get_context_without_calling_map() {
+    let r: Result<u8, InnerError> = Boom.fail();
+    let e = r
+        .map_context(
+            |_| -> u32 { panic!("must not be called") },
+            Parse { input: "x" },
+        )
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Parsing x failed");
+}
@@ -0,0 +1,92 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug)]
+struct Config;
+
+#[derive(Debug, Snafu)]
+#[snafu(module)]
+enum ConfigError {
+    #[snafu(display("Could not open {}", path))]
+    OpenConfig {
+        path: String,
+        source: InnerError,
+    },
+
+    Missing {
+        config: Config,
+    },
+
+    #[snafu(context(false))]
+    Inner {
+        source: std::fmt::Error,
+    },
+}
+
+// Would conflict with the selector if it were not in a module
+#[allow(dead_code)]
+struct OpenConfig;
+
+#[test]
+fn selectors_are_placed_in_a_module() {
+    let r: Result<(), InnerError> = Boom.fail();
+    let e = r
+        .context(config_error::OpenConfig { path: "a.toml" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not open a.toml");
+
+    let e = config_error::Missing { config: Config }.build();
+    assert!(matches!(e, ConfigError::Missing { .. }));
+}
+
+#[test]
+fn from_impls_are_still_generated() {
+    let e = ConfigError::from(std::fmt::Error);
+    assert!(matches!(e, ConfigError::Inner { .. }));
+}
+
+mod named {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(module(selectors), visibility(pub(crate)))]
+    pub enum Error {
+        #[snafu(display("Bad {}", id))]
+        Bad { id: u32 },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(module = "assigned", visibility(pub(crate)))]
+    pub enum OtherError {
+        Worse { id: u32 },
+    }
+}
+
+#[test]
+fn the_module_can_be_named() {
+    let e = named::selectors::Bad { id: 3_u32 }.build();
+    assert_eq!(e.to_string(), "Bad 3");
+
+    let e = named::assigned::Worse { id: 4_u32 }.build();
+    assert!(matches!(e, named::OtherError::Worse { id: 4 }));
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(module)]
+    enum Error<T: std::fmt::Debug> {
+        Invalid { value: T },
+    }
+
+    #[test]
+    fn generic_errors_can_use_a_module() {
+        let e: Error<i32> = error::Invalid { value: 1 }.build();
+        assert!(matches!(e, Error::Invalid { value: 1 }));
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::Snafu;
 
 // Modules to clash with likely candidates from the standard library.
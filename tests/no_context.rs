@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 extern crate snafu;
 
 use snafu::Snafu;
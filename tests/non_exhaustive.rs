@@ -0,0 +1,48 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(kind)]
+#[non_exhaustive]
+pub enum Error {
+    #[snafu(display("Leaf {}", id))]
+    Leaf { id: i32 },
+
+    #[non_exhaustive]
+    WithSource { source: InnerError },
+
+    #[non_exhaustive]
+    Tuple(InnerError, u8),
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub struct StructError {
+    id: i32,
+}
+
+#[test]
+fn selectors_are_generated_for_non_exhaustive_enums() {
+    let e = Leaf { id: 42 }.build();
+    assert_eq!(e.to_string(), "Leaf 42");
+    assert_eq!(e.kind(), ErrorKind::Leaf);
+}
+
+#[test]
+fn selectors_are_generated_for_non_exhaustive_variants() {
+    let e = Boom.fail::<()>().context(WithSource).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::WithSource);
+
+    let e = Boom.fail::<()>().context(Tuple(1_u8)).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::Tuple);
+}
+
+#[test]
+fn selectors_are_generated_for_non_exhaustive_structs() {
+    let e = StructContext { id: 42 }.build();
+    assert_eq!(e.id, 42);
+}
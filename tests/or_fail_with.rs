@@ -0,0 +1,26 @@
+use snafu::{Backtrace, ErrorCompat, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not parse {}", input))]
+    InvalidNumber { input: String, backtrace: Backtrace },
+}
+
+#[test]
+fn the_error_is_replaced() {
+    let e = "nope"
+        .parse::<u8>()
+        .or_fail_with(InvalidNumber { input: "nope" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Could not parse nope");
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn successful_values_are_unchanged() {
+    let value = "42"
+        .parse::<u8>()
+        .or_fail_with(InvalidNumber { input: "42" })
+        .unwrap();
+    assert_eq!(value, 42);
+}
@@ -0,0 +1,67 @@
+use snafu::{Backtrace, Report, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("The disk is on fire"))]
+    OnFire,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save the file"))]
+    Save { source: InnerError },
+
+    #[snafu(display("Could not sync"))]
+    Sync { source: Error2 },
+
+    #[snafu(display("Nothing to do"))]
+    Idle,
+}
+
+#[derive(Debug, Snafu)]
+enum Error2 {
+    #[snafu(display("Could not flush"))]
+    Flush {
+        source: InnerError,
+        backtrace: Backtrace,
+    },
+}
+
+#[test]
+fn an_error_without_sources_is_only_its_message() {
+    let report = Report::from(Idle.build());
+    assert_eq!(report.to_string(), "Nothing to do");
+}
+
+#[test]
+fn each_source_is_listed() {
+    let e = OnFire
+        .fail::<()>()
+        .context(Flush)
+        .context(Sync)
+        .unwrap_err();
+    let report = Report::from(e).to_string();
+
+    assert!(
+        report.starts_with(
+            "Could not sync\nCaused by:\n    Could not flush\n    The disk is on fire"
+        ),
+        "{}",
+        report,
+    );
+}
+
+#[test]
+fn the_backtrace_of_the_error_is_included() {
+    let e = OnFire.fail::<()>().context(Flush).unwrap_err();
+    let report = Report::from(e).to_string();
+
+    assert!(report.contains("\n\nBacktrace:\n"), "{}", report);
+}
+
+#[test]
+fn debug_matches_display() {
+    let report = Report::from(OnFire.fail::<()>().context(Save).unwrap_err());
+    assert_eq!(format!("{:?}", report), report.to_string());
+    assert!(matches!(report.into_inner(), Error::Save { .. }));
+}
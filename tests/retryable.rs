@@ -0,0 +1,50 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(retryable)]
+    Timeout {
+        after_ms: u64,
+    },
+
+    #[snafu(retryable(true))]
+    ConnectionReset,
+
+    #[snafu(retryable(false))]
+    InvalidRequest {
+        reason: String,
+    },
+
+    NotFound,
+}
+
+#[test]
+fn marked_variants_are_retryable() {
+    assert!(Timeout { after_ms: 500_u64 }.build().is_retryable());
+    assert!(ConnectionReset.build().is_retryable());
+}
+
+#[test]
+fn other_variants_are_not_retryable() {
+    assert!(!InvalidRequest { reason: "bad" }.build().is_retryable());
+    assert!(!NotFound.build().is_retryable());
+}
+
+#[test]
+fn retry_loops_can_use_the_error() {
+    let mut attempts = 0;
+    let result: Result<(), Error> = loop {
+        attempts += 1;
+        let e = if attempts < 3 {
+            ConnectionReset.build()
+        } else {
+            NotFound.build()
+        };
+        if !e.is_retryable() {
+            break Err(e);
+        }
+    };
+
+    assert_eq!(attempts, 3);
+    assert!(matches!(result, Err(Error::NotFound)));
+}
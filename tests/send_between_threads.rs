@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 // This test asserts that errors can be used across threads.
 
 use std::thread;
@@ -46,8 +48,37 @@ fn implements_thread_safe_error() {
     check_send::<InnerError>();
     check_send::<Error>();
 
-    let t = thread::spawn(move || example());
+    let t = thread::spawn(example);
 
     let v = t.join().expect("Thread panicked");
     v.unwrap_err();
 }
+
+mod error_containers {
+    use snafu::{Backtrace, Location, ResultExt, Snafu};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Tracked {
+            source: super::InnerError,
+            backtrace: Backtrace,
+            #[snafu(location)]
+            location: Location,
+        },
+    }
+
+    // Error containers such as `anyhow::Error` require these bounds
+    fn into_container<E>(e: E) -> Box<dyn std::error::Error + Send + Sync + 'static>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Box::new(e)
+    }
+
+    #[test]
+    fn generated_errors_fit_in_error_containers() {
+        let e = super::Boom.fail::<()>().context(Tracked).unwrap_err();
+        let boxed = into_container(e);
+        assert!(boxed.downcast_ref::<Error>().is_some());
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use snafu::{Backtrace, Snafu};
+
+#[derive(Debug, Snafu)]
+#[snafu(send_sync)]
+enum Error {
+    Leaf {
+        name: String,
+        backtrace: Backtrace,
+    },
+
+    Boxed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(send_sync)]
+struct StructError {
+    id: i32,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(send_sync)]
+struct TupleError(Error);
+
+#[derive(Debug, Snafu)]
+#[snafu(send_sync)]
+enum GenericError<'a, T>
+where
+    T: std::fmt::Debug + std::fmt::Display + Send + Sync,
+{
+    Borrowed { name: &'a str, value: T },
+}
+
+#[test]
+fn thread_safe_errors_compile() {
+    fn check<E: std::error::Error + Send + Sync>() {}
+    check::<Error>();
+    check::<StructError>();
+    check::<TupleError>();
+    check::<GenericError<'static, i32>>();
+}
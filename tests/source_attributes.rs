@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -58,6 +60,7 @@ mod transformation {
     use std::io;
 
     #[derive(Debug, Snafu)]
+    #[allow(clippy::enum_variant_names)]
     enum Error {
         TransformationViaClosure {
             #[snafu(source(from(InnerError, |e| io::Error::new(io::ErrorKind::InvalidData, e))))]
@@ -109,3 +112,31 @@ mod transformation {
         api_example().unwrap();
     }
 }
+
+mod additional_errors {
+    use super::*;
+    use snafu::{ResultExt, Snafu};
+    use std::error::Error as _;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        BothFailed {
+            source: InnerError,
+            fallback: InnerError,
+        },
+    }
+
+    #[test]
+    fn only_the_source_is_returned_from_source() {
+        let result: Result<(), _> = Err(InnerError::Boom);
+        let e = result
+            .context(BothFailed {
+                fallback: InnerError::Boom,
+            })
+            .unwrap_err();
+
+        let source = e.source().expect("Must have a source");
+        assert!(source.downcast_ref::<InnerError>().is_some());
+        assert!(matches!(e, Error::BothFailed { .. }));
+    }
+}
@@ -0,0 +1,58 @@
+use snafu::{ResultExt, Snafu};
+use std::{error::Error as StdError, marker::PhantomData};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+fn boxed<S>(source: S) -> BoxError
+where
+    S: StdError + Send + Sync + 'static,
+{
+    Box::new(source)
+}
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error<S> {
+    #[snafu(display("Storing failed: {}", source))]
+    Store {
+        #[snafu(source(from(S, boxed), bound = "S: StdError + Send + Sync + 'static"))]
+        source: BoxError,
+        kind: PhantomData<S>,
+    },
+}
+
+#[test]
+fn the_bound_allows_converting_the_source() {
+    let e: Error<InnerError> = Boom
+        .fail::<()>()
+        .context(Store { kind: PhantomData })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Storing failed: Boom");
+}
+
+mod structs {
+    use super::*;
+    use std::fmt::Debug;
+
+    fn sendable<S: Send>(e: Error<S>) -> Box<Error<S>> {
+        Box::new(e)
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(source(from(Error<S>, sendable), bound = "S: Send"))]
+    struct OpaqueError<S: Debug + 'static>(Box<Error<S>>);
+
+    #[test]
+    fn the_bound_is_used_by_tuple_structs() {
+        let e: Error<InnerError> = Boom
+            .fail::<()>()
+            .context(Store { kind: PhantomData })
+            .unwrap_err();
+        let e = OpaqueError::from(e);
+        assert_eq!(e.to_string(), "Storing failed: Boom");
+    }
+}
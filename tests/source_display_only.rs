@@ -0,0 +1,82 @@
+use snafu::{ResultExt, Snafu};
+use std::{error::Error as _, fmt};
+
+// Implements `Display`, but not `Error`.
+#[derive(Debug)]
+struct LegacyError(&'static str);
+
+impl fmt::Display for LegacyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "legacy failure: {}", self.0)
+    }
+}
+
+fn legacy() -> Result<(), LegacyError> {
+    Err(LegacyError("disk full"))
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not save {}: {}", name, source))]
+    Save {
+        name: String,
+        #[snafu(source(display_only))]
+        source: LegacyError,
+    },
+
+    Load {
+        #[snafu(source(display_only))]
+        cause: LegacyError,
+    },
+
+    Positional(#[snafu(source(display_only))] LegacyError),
+}
+
+#[test]
+fn the_value_is_used_in_display() {
+    let e = legacy().context(Save { name: "report" }).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Could not save report: legacy failure: disk full"
+    );
+}
+
+#[test]
+fn the_value_is_not_returned_as_the_source() {
+    let e = legacy().context(Save { name: "report" }).unwrap_err();
+    assert!(e.source().is_none());
+}
+
+#[test]
+fn display_only_implies_source() {
+    let e = legacy().context(Load).unwrap_err();
+    assert!(matches!(
+        e,
+        Error::Load {
+            cause: LegacyError("disk full")
+        }
+    ));
+    assert_eq!(e.to_string(), "Load: legacy failure: disk full");
+    assert!(e.source().is_none());
+
+    let e = legacy().context(Positional).unwrap_err();
+    assert!(matches!(e, Error::Positional(LegacyError("disk full"))));
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Sync failed: {}", source))]
+    struct Error {
+        #[snafu(source(display_only))]
+        source: LegacyError,
+    }
+
+    #[test]
+    fn works_on_structs() {
+        let e = legacy().context(Context).unwrap_err();
+        assert_eq!(e.to_string(), "Sync failed: legacy failure: disk full");
+        assert!(e.source().is_none());
+    }
+}
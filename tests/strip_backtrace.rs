@@ -0,0 +1,142 @@
+use snafu::{Backtrace, ErrorCompat, GenerateBacktrace, ResultExt, Snafu};
+
+#[derive(Debug, PartialEq)]
+enum Trace {
+    Captured,
+    Disabled,
+}
+
+impl GenerateBacktrace for Trace {
+    fn generate() -> Self {
+        Trace::Captured
+    }
+
+    fn disabled() -> Self {
+        Trace::Disabled
+    }
+
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        None
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Leaf { backtrace: Trace },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Direct {
+        backtrace: Trace,
+    },
+
+    Delegated {
+        #[snafu(backtrace)]
+        source: InnerError,
+    },
+
+    Without {
+        code: u8,
+    },
+}
+
+#[test]
+fn backtrace_fields_are_disabled() {
+    let e = Direct.build();
+    assert!(matches!(
+        e,
+        Error::Direct {
+            backtrace: Trace::Captured
+        }
+    ));
+
+    let e = e.without_backtrace();
+    assert!(matches!(
+        e,
+        Error::Direct {
+            backtrace: Trace::Disabled
+        }
+    ));
+}
+
+#[test]
+fn delegated_backtraces_are_disabled_in_the_source() {
+    let mut e = Leaf.fail::<()>().context(Delegated).unwrap_err();
+    ErrorCompat::strip_backtrace(&mut e);
+    assert!(matches!(
+        e,
+        Error::Delegated {
+            source: InnerError::Leaf {
+                backtrace: Trace::Disabled
+            }
+        }
+    ));
+}
+
+#[test]
+fn other_fields_are_untouched() {
+    let e = Without { code: 7 }.build().without_backtrace();
+    assert!(matches!(e, Error::Without { code: 7 }));
+}
+
+#[test]
+fn shared_errors_are_stripped_when_not_yet_shared() {
+    let mut e = std::sync::Arc::new(Direct.build());
+    ErrorCompat::strip_backtrace(&mut e);
+    assert!(matches!(
+        *e,
+        Error::Direct {
+            backtrace: Trace::Disabled
+        }
+    ));
+}
+
+#[test]
+fn shared_errors_are_untouched_while_shared() {
+    let mut e = std::sync::Arc::new(Direct.build());
+    let other = std::sync::Arc::clone(&e);
+    ErrorCompat::strip_backtrace(&mut e);
+    assert!(matches!(
+        *other,
+        Error::Direct {
+            backtrace: Trace::Captured
+        }
+    ));
+}
+
+mod opaque {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    pub struct OpaqueError(InnerError);
+
+    #[test]
+    fn opaque_errors_strip_the_inner_error() {
+        let e = OpaqueError::from(Leaf.build()).without_backtrace();
+        assert!(matches!(
+            e.0,
+            InnerError::Leaf {
+                backtrace: Trace::Disabled
+            }
+        ));
+    }
+}
+
+mod optional {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    struct Error {
+        backtrace: Option<Backtrace>,
+    }
+
+    #[test]
+    fn optional_backtraces_become_none() {
+        let e = Error {
+            backtrace: Some(Backtrace::generate()),
+        };
+        let e = e.without_backtrace();
+        assert!(e.backtrace.is_none());
+    }
+}
@@ -5,6 +5,7 @@ use snafu::Snafu;
 #[test]
 fn an_error_with_generic_lifetimes_does_not_trigger_the_lint() {
     #[derive(Debug, Snafu)]
+    #[allow(dead_code)]
     struct Error<'id> {
         to: &'id u32,
     }
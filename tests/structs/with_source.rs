@@ -34,7 +34,7 @@ fn implements_error() {
 
     let source = e.source().expect("Source must be present");
     let source_as_io_error =
-        StdError::downcast_ref::<io::Error>(source).expect("Source must be io::Error");
+        <dyn StdError>::downcast_ref::<io::Error>(source).expect("Source must be io::Error");
     assert!(ptr::eq(source_as_io_error, &e.source));
 
     let display = e.to_string();
@@ -0,0 +1,71 @@
+use snafu::{ResultExt, Snafu};
+use std::error::Error as StdError;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner failed"))]
+    Inner { source: RootError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("root cause"))]
+struct RootError;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(transparent)]
+    Passthrough { source: InnerError },
+
+    #[snafu(transparent, context(false))]
+    Converted { source: RootError },
+}
+
+fn inner() -> Result<(), InnerError> {
+    RootContext.fail().context(Inner)
+}
+
+fn root() -> Result<(), RootError> {
+    RootContext.fail()
+}
+
+#[test]
+fn display_is_the_same_as_the_source() {
+    let e = inner().context(Passthrough).unwrap_err();
+    assert_eq!(e.to_string(), "inner failed");
+}
+
+#[test]
+fn source_skips_the_wrapped_error() {
+    let e = inner().context(Passthrough).unwrap_err();
+    let source = e.source().expect("Must have a source");
+    assert_eq!(source.to_string(), "root cause");
+}
+
+#[test]
+fn can_be_combined_with_no_context() {
+    fn example() -> Result<(), Error> {
+        root()?;
+        Ok(())
+    }
+
+    let e = example().unwrap_err();
+    assert_eq!(e.to_string(), "root cause");
+    assert!(e.source().is_none());
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(transparent)]
+    struct Wrapper {
+        source: InnerError,
+    }
+
+    #[test]
+    fn display_is_the_same_as_the_source() {
+        let e = inner().context(WrapperContext).unwrap_err();
+        assert_eq!(e.to_string(), "inner failed");
+        assert_eq!(e.source().unwrap().to_string(), "root cause");
+    }
+}
@@ -0,0 +1,62 @@
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+
+#[derive(Debug, Snafu)]
+enum Low {
+    Timeout,
+    Refused,
+}
+
+#[derive(Debug, Snafu)]
+struct TimeoutError;
+
+impl TryFrom<Low> for TimeoutError {
+    type Error = Low;
+
+    fn try_from(e: Low) -> Result<Self, Self::Error> {
+        match e {
+            Low::Timeout => Ok(TimeoutError),
+            other => Err(other),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Connecting to {} timed out", host))]
+    Connect {
+        host: String,
+        source: TimeoutError,
+    },
+
+    Plain {
+        source: Low,
+    },
+}
+
+#[test]
+fn convertible_errors_get_context() {
+    let r: Result<(), Low> = Err(Low::Timeout);
+    let e = r.try_context(Connect { host: "db" }).unwrap().unwrap_err();
+    assert_eq!(e.to_string(), "Connecting to db timed out");
+}
+
+#[test]
+fn unconvertible_errors_are_returned() {
+    let r: Result<(), Low> = Err(Low::Refused);
+    let e = r.try_context(Connect { host: "db" }).unwrap_err();
+    assert!(matches!(e, Low::Refused));
+}
+
+#[test]
+fn ok_values_are_kept() {
+    let r: Result<i32, Low> = Ok(42);
+    assert_eq!(r.try_context(Connect { host: "db" }).unwrap().unwrap(), 42);
+}
+
+#[test]
+fn identical_sources_always_convert() {
+    let r: Result<(), Low> = Err(Low::Refused);
+    let e = r.try_context(Plain).unwrap_or_else(|e| match e {});
+    assert!(matches!(e, Err(Error::Plain { .. })));
+}
@@ -0,0 +1,71 @@
+use snafu::{Backtrace, ErrorCompat, IntoError, ResultExt, Snafu};
+use std::error::Error as StdError;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner"))]
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Wrapper(InnerError),
+
+    #[snafu(display("line {}: {}", _1, _0))]
+    WithContext(InnerError, usize),
+
+    #[snafu(display("{} at {}", _0, _1))]
+    NoSource(#[snafu(source(false))] String, u32),
+
+    #[snafu(display("{} failed", _0))]
+    ExplicitSource(&'static str, #[snafu(source)] InnerError),
+
+    WithBacktrace(InnerError, #[snafu(backtrace)] Backtrace),
+
+    Eof,
+}
+
+fn boom() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+#[test]
+fn first_field_is_the_source() {
+    let e = boom().context(Wrapper).unwrap_err();
+    assert!(matches!(e, Error::Wrapper(InnerError::Boom)));
+    assert!(e.source().is_some());
+    assert_eq!(e.to_string(), "Wrapper: inner");
+}
+
+#[test]
+fn remaining_fields_form_a_positional_selector() {
+    let e = boom().context(WithContext(7_u8)).unwrap_err();
+    assert!(matches!(e, Error::WithContext(InnerError::Boom, 7)));
+    assert_eq!(e.to_string(), "line 7: inner");
+}
+
+#[test]
+fn first_field_can_opt_out_of_being_the_source() {
+    let e = NoSource("parse", 3_u8).build();
+    assert!(e.source().is_none());
+    assert_eq!(e.to_string(), "parse at 3");
+}
+
+#[test]
+fn a_later_field_can_be_the_source() {
+    let e: Error = ExplicitSource("step").into_error(InnerError::Boom);
+    assert_eq!(e.to_string(), "step failed");
+    assert!(e.source().is_some());
+}
+
+#[test]
+fn backtraces_are_captured() {
+    let e = boom().context(WithBacktrace).unwrap_err();
+    assert!(ErrorCompat::backtrace(&e).is_some());
+}
+
+#[test]
+fn unit_variants_have_fail() {
+    let e = Eof.fail::<()>().unwrap_err();
+    assert_eq!(e.to_string(), "Eof");
+}
@@ -0,0 +1,47 @@
+use snafu::{IntoError, ResultExt, Snafu, Unchanged};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Wrapped { source: InnerError },
+    Leaf { id: i32 },
+}
+
+fn inner() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+fn leaf() -> Result<(), Error> {
+    Leaf { id: 42 }.fail()
+}
+
+// Stands in for code that doesn't know whether the error needs
+// more context.
+fn with_selector<C>(result: Result<(), C::Source>, context: C) -> Result<(), Error>
+where
+    C: IntoError<Error>,
+    C::Source: std::error::Error,
+{
+    result.context(context)
+}
+
+#[test]
+fn the_error_is_not_changed() {
+    let e = with_selector(leaf(), Unchanged).unwrap_err();
+    assert!(matches!(e, Error::Leaf { id: 42 }));
+}
+
+#[test]
+fn can_be_used_alongside_other_selectors() {
+    let e = with_selector(inner(), Wrapped).unwrap_err();
+    assert!(matches!(e, Error::Wrapped { .. }));
+}
+
+#[test]
+fn ok_values_are_passed_through() {
+    assert!(Ok::<(), Error>(()).context(Unchanged).is_ok());
+}
@@ -0,0 +1,33 @@
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Boom,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Wrapped {}", id))]
+    Wrapped { id: i32, source: InnerError },
+}
+
+#[test]
+fn recovery_receives_the_wrapped_error() {
+    let mut seen = None;
+    let result: Result<i32, _> = Err(InnerError::Boom);
+    let value = result.unwrap_or_context(Wrapped { id: 42 }, |e: Error| {
+        seen = Some(e.to_string());
+        -1
+    });
+
+    assert_eq!(value, -1);
+    assert_eq!(seen.as_ref().map(String::as_str), Some("Wrapped 42"));
+}
+
+#[test]
+fn recovery_is_not_called_on_success() {
+    let result: Result<i32, InnerError> = Ok(1);
+    let value =
+        result.unwrap_or_context(Wrapped { id: 42 }, |_: Error| panic!("Must not be called"));
+    assert_eq!(value, 1);
+}
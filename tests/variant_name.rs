@@ -0,0 +1,66 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(variant_name)]
+enum Error {
+    #[snafu(display("Could not open {}", filename))]
+    OpenConfig {
+        filename: String,
+    },
+    SaveConfig,
+    Positional(#[snafu(source(false))] u8),
+}
+
+#[test]
+fn returns_the_name_of_the_variant() {
+    assert_eq!(
+        OpenConfig { filename: "a.toml" }.build().variant_name(),
+        "OpenConfig"
+    );
+    assert_eq!(SaveConfig.build().variant_name(), "SaveConfig");
+    let e: Error = Positional(1_u8).build();
+    assert_eq!(e.variant_name(), "Positional");
+}
+
+#[test]
+fn does_not_use_display() {
+    let e = OpenConfig { filename: "a.toml" }.build();
+    assert_eq!(e.to_string(), "Could not open a.toml");
+    assert_eq!(e.variant_name(), "OpenConfig");
+}
+
+mod generic {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(variant_name)]
+    enum Error<T: std::fmt::Debug> {
+        Invalid { value: T },
+    }
+
+    #[test]
+    fn works_with_generic_enums() {
+        let e: Error<i32> = Invalid { value: 1 }.build();
+        assert_eq!(e.variant_name(), "Invalid");
+    }
+}
+
+mod not_requested {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Invalid,
+    }
+
+    impl Error {
+        fn variant_name(&self) -> &'static str {
+            "user-defined"
+        }
+    }
+
+    #[test]
+    fn existing_methods_are_not_replaced() {
+        assert_eq!(Invalid.build().variant_name(), "user-defined");
+    }
+}
@@ -0,0 +1,44 @@
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+#[snafu(variant_name, variants_const)]
+enum Error {
+    ReadConfig { path: String },
+    Positional(#[snafu(source(false))] u8),
+    Empty,
+}
+
+#[test]
+fn every_variant_is_listed_in_order() {
+    assert_eq!(Error::VARIANTS, &["ReadConfig", "Positional", "Empty"]);
+}
+
+#[test]
+fn the_names_match_the_variant_names() {
+    let errors = [
+        ReadConfig { path: "a" }.build(),
+        Positional(1_u8).build(),
+        Empty.build(),
+    ];
+
+    for (e, name) in errors.iter().zip(Error::VARIANTS) {
+        assert_eq!(e.variant_name(), *name);
+    }
+}
+
+mod generics {
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(variants_const)]
+    #[allow(dead_code)]
+    enum Error<T: std::fmt::Debug> {
+        Invalid { value: T },
+        Missing,
+    }
+
+    #[test]
+    fn generic_enums_list_their_variants() {
+        assert_eq!(Error::<i32>::VARIANTS, &["Invalid", "Missing"]);
+    }
+}
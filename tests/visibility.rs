@@ -19,6 +19,32 @@ mod outer {
                 id: i32,
             },
         }
+
+        #[derive(Debug, Snafu)]
+        pub(crate) enum FieldError {
+            #[snafu(visibility(pub(crate)))]
+            Mixed {
+                #[snafu(visibility(pub(in crate::outer)))]
+                id: i32,
+                #[snafu(visibility)]
+                secret: i32,
+            },
+        }
+
+        pub(crate) fn mixed() -> Mixed<i32, i32> {
+            Mixed { id: 42, secret: 99 }
+        }
+
+        #[test]
+        fn private_fields_are_usable_in_the_same_module() {
+            let _ = Mixed { id: 42, secret: 99 }.build();
+            assert_eq!(mixed().secret, 99);
+        }
+
+        #[test]
+        fn can_reset_visibility() {
+            let _ = Private { id: 42 }.build();
+        }
     }
 
     #[test]
@@ -30,6 +56,11 @@ mod outer {
     fn can_set_visibility() {
         let _ = self::inner::PubInPath { id: 42 }.build();
     }
+
+    #[test]
+    fn can_set_field_visibility() {
+        assert_eq!(self::inner::mixed().id, 42);
+    }
 }
 
 #[test]
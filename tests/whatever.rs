@@ -0,0 +1,119 @@
+use snafu::{whatever, ErrorCompat, ResultExt, Snafu, Whatever};
+use std::error::Error as StdError;
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("inner"))]
+    Boom,
+}
+
+fn boom() -> Result<(), InnerError> {
+    Boom.fail()
+}
+
+mod provided_type {
+    use super::*;
+
+    fn without_source(id: i32) -> Result<(), Whatever> {
+        if id < 0 {
+            whatever!("ID {} is invalid", id);
+        }
+        Ok(())
+    }
+
+    fn with_source() -> Result<i32, Whatever> {
+        whatever!(boom(), "Could not go boom");
+        Ok(42)
+    }
+
+    #[test]
+    fn can_be_created_without_a_source() {
+        let e = without_source(-1).unwrap_err();
+        assert_eq!(e.to_string(), "ID -1 is invalid");
+        assert!(e.source().is_none());
+    }
+
+    #[test]
+    fn can_wrap_an_underlying_error() {
+        let e = with_source().unwrap_err();
+        assert_eq!(e.to_string(), "Could not go boom");
+        let source = e.source().expect("Must have a source");
+        assert_eq!(source.to_string(), "inner");
+    }
+
+    #[test]
+    fn evaluates_to_the_ok_value() {
+        fn example() -> Result<i32, Whatever> {
+            let value = whatever!(Ok::<_, InnerError>(21), "unused");
+            Ok(value * 2)
+        }
+        assert_eq!(example().unwrap(), 42);
+    }
+
+    #[test]
+    fn format_arguments_may_have_a_trailing_comma() {
+        fn example() -> Result<(), Whatever> {
+            whatever!("{} and {}", 1, 2,);
+        }
+        assert_eq!(example().unwrap_err().to_string(), "1 and 2");
+    }
+
+    #[test]
+    fn has_a_backtrace() {
+        let e = without_source(-1).unwrap_err();
+        assert!(ErrorCompat::backtrace(&e).is_some());
+    }
+}
+
+mod custom_type {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(display("Could not go boom"))]
+        Specific { source: InnerError },
+
+        #[snafu(whatever)]
+        Generic {
+            #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+            source: Option<Box<dyn std::error::Error>>,
+            message: String,
+        },
+    }
+
+    fn example(specific: bool) -> Result<(), Error> {
+        if specific {
+            boom().context(Specific)?;
+        }
+        whatever!(boom(), "Generic failure {}", 7);
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_mixed_with_specific_variants() {
+        let e = example(true).unwrap_err();
+        assert!(matches!(e, Error::Specific { .. }));
+
+        let e = example(false).unwrap_err();
+        assert_eq!(e.to_string(), "Generic failure 7");
+        assert!(e.source().is_some());
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever)]
+    #[snafu(display("Oops: {}", message))]
+    struct WithoutSource {
+        message: String,
+    }
+
+    #[test]
+    fn source_field_is_optional() {
+        fn example() -> Result<(), WithoutSource> {
+            whatever!("three is {}", 3);
+        }
+
+        let e = example().unwrap_err();
+        assert_eq!(e.to_string(), "Oops: three is 3");
+        assert!(e.source().is_none());
+    }
+}